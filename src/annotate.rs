@@ -0,0 +1,301 @@
+//! Suggestions for completing the generated document by hand.
+//!
+//! Inference stops short in a few recurring places: a route whose handler cannot be found, a
+//! handler whose return type (`impl IntoResponse`) hides the response body, or a type that is
+//! documented as an `object` placeholder. [`annotate`] lists each of them together with the
+//! annotation that fills the gap, and [`format_report`] groups the suggestions by source file
+//! for the CLI's `--annotate` mode. Source files are never modified.
+
+use crate::extractor::{extract_type_info, RouteInfo, TypeInfo};
+use crate::parser::ParsedFile;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use syn::visit::Visit;
+
+/// A place where inference was incomplete and how to complete it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// File the suggestion applies to, if it could be located
+    pub file: Option<PathBuf>,
+    /// The route or struct field concerned (e.g. `GET /users (list_users)` or `User.avatar`)
+    pub target: String,
+    /// What could not be inferred
+    pub reason: String,
+    /// The annotation or change that fills the gap
+    pub suggestion: String,
+}
+
+/// Where handler functions and struct fields are declared in the scanned sources.
+#[derive(Debug, Default)]
+pub struct SourceIndex {
+    /// Function name -> declaring file and return type (`None` for `()`)
+    functions: HashMap<String, (PathBuf, Option<TypeInfo>)>,
+    /// Type name -> struct fields whose type mentions it, as (file, `Struct.field`)
+    field_uses: HashMap<String, Vec<(PathBuf, String)>>,
+}
+
+impl SourceIndex {
+    /// Index the functions and struct fields of all parsed files
+    pub fn new(parsed_files: &[ParsedFile]) -> Self {
+        let mut index = Self::default();
+        for parsed_file in parsed_files {
+            index.collect_items(&parsed_file.path, &parsed_file.syntax_tree.items);
+        }
+        index
+    }
+
+    /// Record the items of a file or inline module
+    fn collect_items(&mut self, file: &Path, items: &[syn::Item]) {
+        for item in items {
+            match item {
+                syn::Item::Fn(item_fn) => {
+                    let return_type = match &item_fn.sig.output {
+                        syn::ReturnType::Default => None,
+                        syn::ReturnType::Type(_, ty) => Some(extract_type_info(ty)),
+                    };
+                    self.functions
+                        .entry(item_fn.sig.ident.to_string())
+                        .or_insert_with(|| (file.to_path_buf(), return_type));
+                }
+                syn::Item::Struct(item_struct) => {
+                    for (i, field) in item_struct.fields.iter().enumerate() {
+                        let field_name = field
+                            .ident
+                            .as_ref()
+                            .map(|ident| ident.to_string())
+                            .unwrap_or_else(|| i.to_string());
+                        let target = format!("{}.{}", item_struct.ident, field_name);
+                        let mut names = TypeNames::default();
+                        names.visit_type(&field.ty);
+                        for name in names.0 {
+                            self.field_uses
+                                .entry(name)
+                                .or_default()
+                                .push((file.to_path_buf(), target.clone()));
+                        }
+                    }
+                }
+                syn::Item::Mod(item_mod) => {
+                    if let Some((_, items)) = &item_mod.content {
+                        self.collect_items(file, items);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Every path segment name mentioned by a type, including generic arguments
+#[derive(Default)]
+struct TypeNames(BTreeSet<String>);
+
+impl<'ast> Visit<'ast> for TypeNames {
+    fn visit_path_segment(&mut self, node: &'ast syn::PathSegment) {
+        self.0.insert(node.ident.to_string());
+        syn::visit::visit_path_segment(self, node);
+    }
+}
+
+/// Collect a suggestion for every route and type whose documentation is incomplete.
+///
+/// `unresolved_types` are the types the schema generator documented as object placeholders
+/// (see [`crate::schema_generator::SchemaGenerator::get_unresolved_types`]).
+pub fn annotate(
+    index: &SourceIndex,
+    routes: &[RouteInfo],
+    unresolved_types: &BTreeSet<String>,
+) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+
+    for route in routes.iter().filter(|route| !route.serves_files) {
+        let target = format!(
+            "{} {} ({})",
+            route.method_str(),
+            route.path,
+            route.handler_name
+        );
+        let Some((file, return_type)) = index.functions.get(&route.handler_name) else {
+            annotations.push(Annotation {
+                file: None,
+                target,
+                reason: format!("handler `{}` was not found", route.handler_name),
+                suggestion: format!(
+                    "define `{}` as a function in the scanned sources, or check --framework",
+                    route.handler_name
+                ),
+            });
+            continue;
+        };
+
+        let Some(return_type) = return_type else {
+            continue;
+        };
+        let hidden = match &route.response_type {
+            None => true,
+            Some(response) => is_opaque(response),
+        };
+        if hidden {
+            let reason = match return_type.ok_type().unwrap_or(return_type) {
+                opaque if opaque.is_impl_trait => {
+                    format!("response type is hidden behind `impl {}`", opaque.name)
+                }
+                opaque if opaque.is_trait_object => {
+                    format!("response type is hidden behind `dyn {}`", opaque.name)
+                }
+                _ => "response type could not be inferred".to_string(),
+            };
+            annotations.push(Annotation {
+                file: Some(file.clone()),
+                target,
+                reason,
+                suggestion: "#[openapi(response = \"<Type>\")]".to_string(),
+            });
+        }
+    }
+
+    for type_name in unresolved_types {
+        let reason = format!(
+            "type `{}` is documented as an object placeholder",
+            type_name
+        );
+        let uses = index.field_uses.get(type_name).map(Vec::as_slice).unwrap_or(&[]);
+        for (file, target) in uses {
+            annotations.push(Annotation {
+                file: Some(file.clone()),
+                target: target.clone(),
+                reason: reason.clone(),
+                suggestion: format!(
+                    "#[serde_as(as = \"...\")] describing how the field serializes, or scan the file defining `{}`",
+                    type_name
+                ),
+            });
+        }
+        if uses.is_empty() {
+            annotations.push(Annotation {
+                file: None,
+                target: type_name.clone(),
+                reason,
+                suggestion: format!("scan the file defining `{}`", type_name),
+            });
+        }
+    }
+
+    annotations
+}
+
+/// Whether a response type carries no schema (`impl Trait` or `dyn Trait`, or a `Result` of
+/// one)
+fn is_opaque(type_info: &TypeInfo) -> bool {
+    let opaque = |type_info: &TypeInfo| type_info.is_impl_trait || type_info.is_trait_object;
+    opaque(type_info) || type_info.ok_type().is_some_and(opaque)
+}
+
+/// Render annotations grouped by file, with unlocated ones last
+pub fn format_report(annotations: &[Annotation]) -> String {
+    if annotations.is_empty() {
+        return "No annotations needed: every route and type was fully inferred.\n".to_string();
+    }
+
+    let mut by_file: BTreeMap<Option<&Path>, Vec<&Annotation>> = BTreeMap::new();
+    for annotation in annotations {
+        by_file
+            .entry(annotation.file.as_deref())
+            .or_default()
+            .push(annotation);
+    }
+
+    let mut report = String::new();
+    let located = by_file.iter().filter(|(file, _)| file.is_some());
+    let unlocated = by_file.iter().filter(|(file, _)| file.is_none());
+    for (file, entries) in located.chain(unlocated) {
+        let heading = file
+            .map(|f| f.display().to_string())
+            .unwrap_or_else(|| "(unknown location)".to_string());
+        report.push_str(&heading);
+        report.push('\n');
+        for annotation in entries {
+            report.push_str(&format!("  {}: {}\n", annotation.target, annotation.reason));
+            report.push_str(&format!("    suggestion: {}\n", annotation.suggestion));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::axum::AxumExtractor;
+    use crate::extractor::RouteExtractor;
+
+    fn parse(path: &str, code: &str) -> ParsedFile {
+        ParsedFile {
+            path: PathBuf::from(path),
+            syntax_tree: syn::parse_file(code).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_annotate_incomplete_routes_and_types() {
+        let files = vec![
+            parse(
+                "src/routes.rs",
+                r#"
+                use axum::{Router, routing::get, Json, response::IntoResponse};
+
+                pub async fn list_users() -> impl IntoResponse {}
+                pub async fn get_user() -> Json<User> { todo!() }
+
+                #[openapi(response = "Vec<User>")]
+                pub async fn search_users() -> impl IntoResponse {}
+
+                pub fn app() -> Router {
+                    Router::new()
+                        .route("/users", get(list_users))
+                        .route("/users/:id", get(get_user))
+                        .route("/search", get(search_users))
+                        .route("/missing", get(missing))
+                }
+            "#,
+            ),
+            parse(
+                "src/models.rs",
+                r#"
+                pub struct User {
+                    pub id: u32,
+                    pub avatar: Option<ImageRef>,
+                }
+            "#,
+            ),
+        ];
+
+        let routes = AxumExtractor.extract_routes(&files);
+        let index = SourceIndex::new(&files);
+        let unresolved: BTreeSet<String> = ["ImageRef".to_string()].into_iter().collect();
+        let annotations = annotate(&index, &routes, &unresolved);
+
+        let summary: Vec<(Option<&str>, &str)> = annotations
+            .iter()
+            .map(|a| (a.file.as_deref().and_then(|f| f.to_str()), a.target.as_str()))
+            .collect();
+        assert_eq!(annotations.len(), 3, "{:?}", summary);
+        assert!(summary.contains(&(Some("src/routes.rs"), "GET /users (list_users)")));
+        assert!(summary.contains(&(None, "GET /missing (missing)")));
+        assert!(summary.contains(&(Some("src/models.rs"), "User.avatar")));
+
+        let list = annotations.iter().find(|a| a.target.contains("list_users")).unwrap();
+        assert_eq!(list.reason, "response type is hidden behind `impl IntoResponse`");
+        assert_eq!(list.suggestion, "#[openapi(response = \"<Type>\")]");
+
+        let report = format_report(&annotations);
+        let models = report.find("src/models.rs").unwrap();
+        let routes_heading = report.find("src/routes.rs").unwrap();
+        let unknown = report.find("(unknown location)").unwrap();
+        assert!(models < routes_heading && routes_heading < unknown);
+    }
+
+    #[test]
+    fn test_format_report_without_annotations() {
+        assert!(format_report(&[]).starts_with("No annotations needed"));
+    }
+}
@@ -1,7 +1,9 @@
+use crate::schema_generator::SCHEMA_REF_PREFIX;
 use anyhow::Result;
 use clap::{Parser, ValueEnum};
 use log::{debug, info};
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Rust OpenAPI Generator - Automatically generate OpenAPI documentation from Rust web projects
 #[derive(Parser, Debug)]
@@ -27,6 +29,217 @@ pub struct CliArgs {
     /// Enable verbose output
     #[arg(short = 'v', long = "verbose")]
     pub verbose: bool,
+
+    /// When to color log output
+    #[arg(long = "color", value_name = "WHEN", value_enum, default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Do not show the number of files parsed so far, which is shown while parsing when stderr
+    /// is a terminal
+    #[arg(long = "no-progress")]
+    pub no_progress: bool,
+
+    /// Exit with an error if generation was incomplete (parse failures, unknown handlers,
+    /// or types documented as object placeholders)
+    #[arg(long = "fail-on-warnings")]
+    pub fail_on_warnings: bool,
+
+    /// Write a JSON report of the run (files, frameworks, routes, unresolved handlers and
+    /// placeholder types) to FILE
+    #[arg(long = "report", value_name = "FILE")]
+    pub report: Option<PathBuf>,
+
+    /// Compare the generated document against an existing spec file and fail if they differ
+    #[arg(long = "diff", value_name = "FILE")]
+    pub diff: Option<PathBuf>,
+
+    /// Report differences found by --diff without failing
+    #[arg(long = "diff-report-only", requires = "diff")]
+    pub diff_report_only: bool,
+
+    /// Merge the generated paths and schemas into an existing base spec, keeping its info,
+    /// servers and security
+    #[arg(long = "merge", value_name = "FILE")]
+    pub merge: Option<PathBuf>,
+
+    /// Let the generated info replace the base spec's info when merging
+    #[arg(long = "overwrite-info", requires = "merge")]
+    pub overwrite_info: bool,
+
+    /// Maximum number of directory levels to scan below the project path
+    #[arg(long = "max-depth", value_name = "N")]
+    pub max_depth: Option<usize>,
+
+    /// Maximum number of Rust files to collect
+    #[arg(long = "max-files", value_name = "N")]
+    pub max_files: Option<usize>,
+
+    /// Only recognize extractors like Json, Path and Query when imported from the framework crate
+    #[arg(long = "strict-extractors")]
+    pub strict_extractors: bool,
+
+    /// Warn when URL path parameters and the handler's Path extractor disagree
+    #[arg(long = "strict-paths")]
+    pub strict_paths: bool,
+
+    /// Only document routes whose handler functions are `pub` (Axum, Salvo and Tide)
+    #[arg(long = "public-only")]
+    pub public_only: bool,
+
+    /// Document the fallback handler (Axum `.fallback`, Actix `.default_service`) as a
+    /// catch-all GET operation under PATH (default: `/{*path}`)
+    #[arg(
+        long = "document-fallback",
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = "/{*path}"
+    )]
+    pub document_fallback: Option<String>,
+
+    /// Document the `400`, `415` and `422` responses Axum sends when a handler's `Json`,
+    /// `Form`, `Path` or `Query` extractor rejects a request
+    #[arg(long = "document-extractor-rejections")]
+    pub document_extractor_rejections: bool,
+
+    /// Reference `#[openapi(request_example_file = "...")]` files as `externalValue` examples
+    /// instead of inlining their content
+    #[arg(long = "external-examples")]
+    pub external_examples: bool,
+
+    /// Print suggested annotations for routes and types that could not be fully inferred,
+    /// grouped by file, instead of printing the document
+    #[arg(long = "annotate")]
+    pub annotate: bool,
+
+    /// Keep component schemas that no operation references
+    #[arg(long = "keep-unused-schemas")]
+    pub keep_unused_schemas: bool,
+
+    /// List the top-level tags alphabetically instead of in order of first use
+    #[arg(long = "sort-tags")]
+    pub sort_tags: bool,
+
+    /// Document structs with `#[serde(flatten)]` fields as an `allOf` of the flattened types
+    /// and their own fields, instead of copying the flattened properties
+    #[arg(long = "flatten-all-of")]
+    pub flatten_all_of: bool,
+
+    /// Document `Decimal` and `BigDecimal` as JSON numbers instead of `decimal` strings
+    #[arg(long = "decimal-as-number")]
+    pub decimal_as_number: bool,
+
+    /// Mark the `object` placeholders of types that could not be resolved with a `TODO`
+    /// description and an `x-unresolved: true` extension
+    #[arg(long = "emit-unresolved-as-todo")]
+    pub emit_unresolved_as_todo: bool,
+
+    /// Document a HEAD operation, without response bodies, for every GET operation
+    #[arg(long = "auto-head")]
+    pub auto_head: bool,
+
+    /// Omit the `---` document start marker from YAML output
+    #[arg(long = "no-doc-start")]
+    pub no_doc_start: bool,
+
+    /// Success status for routes of a method that declare none, e.g. `post=200` (repeatable;
+    /// defaults: POST 201, DELETE 204, others 200)
+    #[arg(
+        long = "default-status",
+        value_name = "METHOD=STATUS",
+        value_parser = parse_default_status
+    )]
+    pub default_statuses: Vec<(crate::extractor::HttpMethod, u16)>,
+
+    /// Description of success responses with a status, e.g. `200=OK` (repeatable)
+    #[arg(
+        long = "response-description",
+        value_name = "STATUS=TEXT",
+        value_parser = parse_response_description
+    )]
+    pub response_descriptions: Vec<(u16, String)>,
+
+    /// Header every operation accepts, e.g. one required by a middleware layer; append
+    /// `:required` if requests must send it (repeatable)
+    #[arg(
+        long = "global-header",
+        value_name = "NAME[:required]",
+        value_parser = parse_global_header
+    )]
+    pub global_headers: Vec<(String, bool)>,
+
+    /// Shared example read from a JSON file, registered under `components.examples` for
+    /// `#[openapi(request_example = "NAME")]` to reference (repeatable)
+    #[arg(long = "example", value_name = "NAME=FILE", value_parser = parse_named_example)]
+    pub examples: Vec<(String, PathBuf)>,
+
+    /// Attach each handler's file, line and signature to its operation as `x-source-location`
+    /// and `x-source-snippet` extensions
+    #[arg(long = "include-handler-source")]
+    pub include_handler_source: bool,
+
+    /// Record how the document was generated (generator version, time, source commit and
+    /// frameworks) in a top-level `x-generated` extension
+    #[arg(long = "stamp")]
+    pub stamp: bool,
+
+    /// Print syntax errors in files that fail to parse with the offending source lines and a
+    /// caret under the error position
+    #[arg(long = "pretty-errors")]
+    pub pretty_errors: bool,
+
+    /// Document every struct and enum of the project under `components.schemas`, including
+    /// types no route uses
+    #[arg(long = "all-schemas")]
+    pub all_schemas: bool,
+
+    /// API title (`info.title`); defaults to the package name in the project's `Cargo.toml`
+    #[arg(long = "title", value_name = "TITLE")]
+    pub title: Option<String>,
+
+    /// API version (`info.version`); defaults to the package version in the project's
+    /// `Cargo.toml`
+    #[arg(long = "api-version", value_name = "VERSION", value_parser = parse_api_version)]
+    pub api_version: Option<String>,
+
+    /// API description (`info.description`)
+    #[arg(long = "description", value_name = "TEXT")]
+    pub description: Option<String>,
+
+    /// Server URL listed under `servers`, with `{name}` placeholders for variables (repeatable)
+    #[arg(long = "server", value_name = "URL")]
+    pub servers: Vec<String>,
+
+    /// Server listed under `servers` whose URL is read from the environment variable VAR
+    #[arg(long = "base-url-from-env", value_name = "VAR")]
+    pub base_url_from_env: Option<String>,
+
+    /// Values of a server URL placeholder: the default, then any other allowed values, e.g.
+    /// `env=prod|staging` (repeatable)
+    #[arg(
+        long = "server-variable",
+        value_name = "NAME=DEFAULT[|VALUE...]",
+        value_parser = parse_server_variable
+    )]
+    pub server_variables: Vec<(String, Vec<String>)>,
+
+    /// How struct fields of type `Option<T>` are documented
+    #[arg(long = "option-fields", value_enum, default_value = "both")]
+    pub option_fields: OptionFields,
+
+    /// Type documented as a plain `object` without a component schema, e.g. an internal type
+    /// leaking into handler signatures (repeatable)
+    #[arg(long = "exclude-schema", value_name = "NAME")]
+    pub exclude_schemas: Vec<String>,
+
+    /// Prefix of component schema references, e.g. `#/definitions/` for tools expecting
+    /// Swagger 2.0 references; the schemas are listed in the top-level section it names
+    #[arg(
+        long = "ref-prefix",
+        value_name = "PREFIX",
+        default_value = SCHEMA_REF_PREFIX,
+        value_parser = parse_ref_prefix
+    )]
+    pub ref_prefix: String,
 }
 
 /// Output format options
@@ -38,6 +251,28 @@ pub enum OutputFormat {
     Json,
 }
 
+/// When log output is colored
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ColorChoice {
+    /// When stderr is a terminal and `NO_COLOR` is not set
+    Auto,
+    /// Always
+    Always,
+    /// Never
+    Never,
+}
+
+/// How struct fields of type `Option<T>` are documented
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OptionFields {
+    /// Required, but `nullable`
+    Nullable,
+    /// Not required
+    Optional,
+    /// Not required and `nullable`, matching serde's defaults
+    Both,
+}
+
 /// Supported web frameworks
 #[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, Hash)]
 pub enum Framework {
@@ -46,6 +281,10 @@ pub enum Framework {
     /// Actix-Web framework
     #[value(name = "actix-web")]
     ActixWeb,
+    /// Salvo framework
+    Salvo,
+    /// Tide framework
+    Tide,
 }
 
 /// Parse command line arguments
@@ -86,22 +325,48 @@ pub fn parse_args_from_parsed(args: CliArgs) -> Result<CliArgs> {
     } else {
         info!("Framework: auto-detect");
     }
+    if let Some(ref diff_path) = args.diff {
+        if !diff_path.is_file() {
+            anyhow::bail!("Diff file does not exist: {}", diff_path.display());
+        }
+        info!("Diff against: {}", diff_path.display());
+    }
+    if let Some(ref merge_path) = args.merge {
+        if !merge_path.is_file() {
+            anyhow::bail!("Base spec does not exist: {}", merge_path.display());
+        }
+        info!("Merge into: {}", merge_path.display());
+    }
 
     Ok(args)
 }
 
 /// Run the main workflow
 pub fn run(args: CliArgs) -> Result<()> {
+    use crate::annotate::{annotate, format_report, SourceIndex};
     use crate::detector::{DetectionResult, FrameworkDetector};
+    use crate::diagnostics::{DiagnosticKind, Diagnostics};
+    use crate::diff::diff;
     use crate::extractor::actix::ActixExtractor;
     use crate::extractor::axum::AxumExtractor;
-    use crate::extractor::{HttpMethod, RouteExtractor, RouteInfo};
-    use crate::openapi_builder::OpenApiBuilder;
-    use crate::parser::{AstParser, ParsedFile};
+    use crate::extractor::salvo::SalvoExtractor;
+    use crate::extractor::tide::TideExtractor;
+    use crate::extractor::{
+        extract_webhooks, ExtractOptions, HttpMethod, RouteExtractor, RouteInfo,
+    };
+    use crate::merge::{merge, MergeStrategy, Precedence};
+    use crate::openapi_builder::{schema_usage, OpenApiBuilder, Parameter, Usage};
+    use crate::parser::AstParser;
+    use crate::report::GenerationReport;
     use crate::scanner::FileScanner;
-    use crate::schema_generator::SchemaGenerator;
-    use crate::serializer::{serialize_json, serialize_yaml, write_to_file};
+    use crate::schema_generator::{FlattenMode, OptionHandling, Schema, SchemaGenerator};
+    use crate::serializer::{
+        create_output_file, load_document, write_json, write_to_file, write_yaml_with,
+        YamlOptions,
+    };
+    use std::io::{IsTerminal, Write};
     use crate::type_resolver::TypeResolver;
+    use crate::validation::check_path_parameters;
     
     // Helper function to convert HTTP method to string
     let method_str = |method: &HttpMethod| -> &str {
@@ -113,15 +378,25 @@ pub fn run(args: CliArgs) -> Result<()> {
             HttpMethod::Patch => "PATCH",
             HttpMethod::Options => "OPTIONS",
             HttpMethod::Head => "HEAD",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Connect => "CONNECT",
         }
     };
     
     info!("Starting OpenAPI document generation...");
     info!("Project path: {}", args.project_path.display());
+
+    let mut diagnostics = Diagnostics::new();
     
     // Step 1: Scan directory for Rust files
     info!("Scanning project directory...");
-    let scanner = FileScanner::new(args.project_path.clone());
+    let mut scanner = FileScanner::new(args.project_path.clone());
+    if let Some(max_depth) = args.max_depth {
+        scanner = scanner.with_max_depth(max_depth);
+    }
+    if let Some(max_files) = args.max_files {
+        scanner = scanner.with_max_files(max_files);
+    }
     let scan_result = scanner.scan()?;
     
     info!("Found {} Rust files", scan_result.rust_files.len());
@@ -130,6 +405,15 @@ pub fn run(args: CliArgs) -> Result<()> {
             log::warn!("{}", warning);
         }
     }
+    if scan_result.skipped_directories > 0 || scan_result.skipped_files > 0 {
+        diagnostics.push(
+            DiagnosticKind::ScanLimit,
+            format!(
+                "Scan limits skipped {} directories and {} Rust files; their routes are missing",
+                scan_result.skipped_directories, scan_result.skipped_files
+            ),
+        );
+    }
     
     if scan_result.rust_files.is_empty() {
         anyhow::bail!("No Rust files found in the project directory");
@@ -137,22 +421,21 @@ pub fn run(args: CliArgs) -> Result<()> {
     
     // Step 2: Parse files into AST
     info!("Parsing Rust files...");
-    let parse_results = AstParser::parse_files(&scan_result.rust_files);
-    
-    let parsed_files: Vec<ParsedFile> = parse_results
-        .into_iter()
-        .filter_map(|r| {
-            match r {
-                Ok(parsed) => Some(parsed),
-                Err(e) => {
-                    debug!("Skipping file due to parse error: {}", e);
-                    None
-                }
+    // Debug logs name every file, so the progress line would only get in their way
+    let show_progress = !args.no_progress && !args.verbose && std::io::stderr().is_terminal();
+    let parsed_files = AstParser::parse_files_with_progress(
+        &scan_result.rust_files,
+        &mut diagnostics,
+        args.pretty_errors,
+        |parsed, total| {
+            if show_progress {
+                show_parse_progress(parsed, total);
             }
-        })
-        .collect();
+        },
+    );
     
     info!("Successfully parsed {} files", parsed_files.len());
+    let files_parsed = parsed_files.len();
     
     if parsed_files.is_empty() {
         anyhow::bail!("No files could be parsed successfully");
@@ -169,7 +452,7 @@ pub fn run(args: CliArgs) -> Result<()> {
         if detection_result.frameworks.is_empty() {
             anyhow::bail!(
                 "No supported web framework detected. Please specify a framework using --framework option.\n\
-                 Supported frameworks: axum, actix-web"
+                 Supported frameworks: axum, actix-web, salvo, tide"
             );
         }
         
@@ -180,6 +463,13 @@ pub fn run(args: CliArgs) -> Result<()> {
     // Step 4: Extract routes using appropriate extractors
     info!("Extracting routes...");
     let mut all_routes: Vec<RouteInfo> = Vec::new();
+    // Routes of frameworks whose handlers declare path parameters through a Path extractor
+    let mut path_checked_routes: Vec<std::ops::Range<usize>> = Vec::new();
+    let extract_options = ExtractOptions {
+        strict_imports: args.strict_extractors,
+        fallback_path: args.document_fallback.clone(),
+        document_rejections: args.document_extractor_rejections,
+    };
     
     for framework in &frameworks {
         debug!("Extracting routes for framework: {:?}", framework);
@@ -187,15 +477,51 @@ pub fn run(args: CliArgs) -> Result<()> {
         let extractor: Box<dyn RouteExtractor> = match framework {
             Framework::Axum => Box::new(AxumExtractor),
             Framework::ActixWeb => Box::new(ActixExtractor),
+            Framework::Salvo => Box::new(SalvoExtractor),
+            Framework::Tide => Box::new(TideExtractor),
         };
         
         // Extract routes from all files at once (extractor needs access to all functions)
-        let routes = extractor.extract_routes(&parsed_files);
+        let routes =
+            extractor.extract_routes_with_options(&parsed_files, &extract_options, &mut diagnostics);
         debug!("Extracted {} routes for {:?}", routes.len(), framework);
+        let routes: Vec<RouteInfo> = if args.public_only {
+            let (public, private): (Vec<_>, Vec<_>) =
+                routes.into_iter().partition(|route| route.is_public);
+            for route in &private {
+                debug!(
+                    "Excluding route with non-pub handler {}: {} {}",
+                    route.handler_name,
+                    method_str(&route.method),
+                    route.path
+                );
+            }
+            if !private.is_empty() {
+                log::warn!(
+                    "Skipped {} routes with non-pub handlers because of --public-only",
+                    private.len()
+                );
+            }
+            public
+        } else {
+            routes
+        };
+        if !matches!(framework, Framework::Salvo | Framework::Tide) {
+            path_checked_routes.push(all_routes.len()..all_routes.len() + routes.len());
+        }
         all_routes.extend(routes);
     }
     
     info!("Extracted {} total routes", all_routes.len());
+
+    let webhooks = extract_webhooks(&parsed_files);
+    if !webhooks.is_empty() {
+        info!("Found {} webhooks", webhooks.len());
+    }
+
+    if args.include_handler_source {
+        attach_handler_sources(&mut all_routes, &args.project_path);
+    }
     
     if all_routes.is_empty() {
         log::warn!("No routes found in the project");
@@ -203,46 +529,554 @@ pub fn run(args: CliArgs) -> Result<()> {
     
     // Step 5: Initialize type resolver and schema generator
     info!("Initializing type resolver...");
-    let type_resolver = TypeResolver::new(parsed_files);
-    let mut schema_gen = SchemaGenerator::new(type_resolver);
+    let source_index = args.annotate.then(|| SourceIndex::new(&parsed_files));
+    let mut type_resolver = TypeResolver::new(parsed_files);
+
+    if args.strict_paths {
+        info!("Checking path parameters...");
+        for range in path_checked_routes {
+            check_path_parameters(&all_routes[range], &mut type_resolver, &mut diagnostics);
+        }
+    }
+
+    let flatten_mode = if args.flatten_all_of {
+        FlattenMode::AllOf
+    } else {
+        FlattenMode::Inline
+    };
+    let option_handling = match args.option_fields {
+        OptionFields::Nullable => OptionHandling::Nullable,
+        OptionFields::Optional => OptionHandling::Optional,
+        OptionFields::Both => OptionHandling::Both,
+    };
+    let mut schema_gen = SchemaGenerator::new(type_resolver)
+        .with_flatten_mode(flatten_mode)
+        .with_option_handling(option_handling)
+        .with_decimal_numbers(args.decimal_as_number)
+        .with_unresolved_markers(args.emit_unresolved_as_todo)
+        .exclude_types(args.exclude_schemas.iter().cloned().collect())
+        .with_ref_prefix(args.ref_prefix.clone());
     
     // Step 6: Build OpenAPI document
     info!("Building OpenAPI document...");
-    let mut builder = OpenApiBuilder::new();
+    let mut builder = OpenApiBuilder::new()
+        .with_example_files(args.project_path.clone(), args.external_examples)
+        .with_unused_schemas(args.keep_unused_schemas || args.all_schemas)
+        .with_sorted_tags(args.sort_tags)
+        .with_auto_head(args.auto_head);
+    let (package_name, package_version) = package_metadata(&args.project_path);
+    if let Some(title) = args.title.clone().or(package_name) {
+        builder = builder.with_title(title);
+    }
+    if let Some(version) = args.api_version.clone().or(package_version) {
+        builder = builder.with_version(version);
+    }
+    if let Some(description) = &args.description {
+        builder = builder.with_description(description.clone());
+    }
+    for server in servers(&args)? {
+        builder = builder.with_server(server);
+    }
+    for (method, status) in &args.default_statuses {
+        builder = builder.with_default_status(method.clone(), *status);
+    }
+    for (status, description) in &args.response_descriptions {
+        builder = builder.with_response_description(*status, description.clone());
+    }
+    for (name, required) in &args.global_headers {
+        builder = builder.with_global_parameter(Parameter {
+            name: name.clone(),
+            location: "header".to_string(),
+            required: *required,
+            schema: Schema {
+                schema_type: Some("string".to_string()),
+                ..Default::default()
+            },
+            description: None,
+            deprecated: None,
+            style: None,
+            explode: None,
+            extra: Default::default(),
+        });
+    }
+    for (name, path) in &args.examples {
+        builder = builder.with_example(name.clone(), read_example(path)?);
+    }
     
     for route in &all_routes {
         debug!("Adding route: {} {}", method_str(&route.method), route.path);
         builder.add_route(route, &mut schema_gen);
     }
+    for webhook in &webhooks {
+        builder.add_webhook(webhook, &mut schema_gen);
+    }
+    if args.all_schemas {
+        schema_gen.generate_all();
+    }
+
+    for type_name in schema_gen.get_unresolved_types() {
+        diagnostics.push(
+            DiagnosticKind::PlaceholderSchema,
+            format!("Unresolved type documented as object placeholder: {}", type_name),
+        );
+    }
+
+    let annotations = source_index
+        .map(|index| annotate(&index, &all_routes, schema_gen.get_unresolved_types()));
+    let placeholder_types: Vec<String> =
+        schema_gen.get_unresolved_types().iter().cloned().collect();
     
     let document = builder.build(schema_gen);
     info!("OpenAPI document built successfully");
+
+    let mut usage: Vec<(String, Usage)> = schema_usage(&document).into_iter().collect();
+    usage.sort_by(|a, b| a.0.cmp(&b.0));
+    for (schema, usage) in &usage {
+        debug!("Schema {} is used in: {:?}", schema, usage);
+        if *usage == Usage::Both {
+            info!(
+                "Schema {} is used by both requests and responses; consider separate request \
+                 and response types",
+                schema
+            );
+        }
+    }
+
+    let mut document = match &args.merge {
+        Some(merge_path) => {
+            info!("Merging into base spec: {}", merge_path.display());
+            let base = load_document(merge_path)?;
+            let strategy = MergeStrategy {
+                info: if args.overwrite_info {
+                    Precedence::Generated
+                } else {
+                    Precedence::Base
+                },
+                ..Default::default()
+            };
+            merge(base, document, &strategy)
+        }
+        None => document,
+    };
+    if args.stamp {
+        // Honor SOURCE_DATE_EPOCH for reproducible builds
+        let source_date_epoch = std::env::var("SOURCE_DATE_EPOCH")
+            .ok()
+            .and_then(|epoch| epoch.parse().ok());
+        let metadata = generation_metadata(
+            &args.project_path,
+            framework_names(&frameworks),
+            source_date_epoch,
+        );
+        document.extensions.insert("x-generated".to_string(), metadata);
+    }
+
+    // Read the --diff baseline before writing the output, which may be the same file
+    let baseline = match &args.diff {
+        Some(diff_path) => Some((diff_path, load_document(diff_path)?)),
+        None => None,
+    };
     
-    // Step 7: Serialize to requested format
+    // Steps 7 and 8: Serialize to requested format, straight into the file or stdout
     info!("Serializing to {:?} format...", args.output_format);
-    let content = match args.output_format {
-        OutputFormat::Yaml => serialize_yaml(&document)?,
-        OutputFormat::Json => serialize_json(&document)?,
+    let write_document = |writer: &mut dyn Write| match args.output_format {
+        OutputFormat::Yaml => {
+            let options = YamlOptions {
+                document_start: !args.no_doc_start,
+            };
+            write_yaml_with(&document, writer, &options)
+        }
+        OutputFormat::Json => write_json(&document, writer),
     };
-    
-    // Step 8: Output to file or stdout
     if let Some(output_path) = &args.output_path {
         info!("Writing output to: {}", output_path.display());
-        write_to_file(&content, output_path)?;
+        let mut file = create_output_file(output_path)?;
+        write_document(&mut file)?;
+        file.flush()?;
         info!("Successfully wrote OpenAPI document to {}", output_path.display());
-    } else {
-        println!("{}", content);
+    } else if annotations.is_none() {
+        let mut stdout = std::io::stdout().lock();
+        write_document(&mut stdout)?;
+        writeln!(stdout)?;
+    }
+
+    if let Some(annotations) = &annotations {
+        info!("Found {} places to annotate", annotations.len());
+        print!("{}", format_report(annotations));
     }
+
+    // Step 9: Compare against an existing spec if requested
+    let spec_diff = match &baseline {
+        Some((diff_path, existing)) => {
+            info!("Comparing against existing spec: {}", diff_path.display());
+            let spec_diff = diff(existing, &document);
+            if spec_diff.is_empty() {
+                info!("No differences from {}", diff_path.display());
+            } else {
+                for line in spec_diff.to_string().lines() {
+                    log::warn!("{}", line);
+                }
+            }
+            Some(spec_diff)
+        }
+        None => None,
+    };
     
-    // Step 9: Display summary
+    // Step 10: Display summary
     info!("Generation complete!");
     info!("Summary:");
     info!("  - Files scanned: {}", scan_result.rust_files.len());
-    info!("  - Files parsed: {}", all_routes.len());
+    info!("  - Files parsed: {}", files_parsed);
     info!("  - Routes found: {}", all_routes.len());
     info!("  - Frameworks: {:?}", frameworks);
+    info!("  - Warnings: {}", diagnostics.len());
+
+    if let Some(report_path) = &args.report {
+        let report = GenerationReport::new(
+            scan_result.rust_files.len(),
+            files_parsed,
+            framework_names(&frameworks),
+            &all_routes,
+            placeholder_types,
+            &diagnostics,
+        );
+        let content = serde_json::to_string_pretty(&report)?;
+        write_to_file(&content, report_path)?;
+        info!("Wrote generation report to {}", report_path.display());
+    }
+
+    if args.fail_on_warnings && !diagnostics.is_empty() {
+        for diagnostic in diagnostics.iter() {
+            log::warn!("[{}] {}", diagnostic.kind, diagnostic.message);
+        }
+        let breakdown = DiagnosticKind::ALL
+            .iter()
+            .map(|&kind| (kind, diagnostics.count(kind)))
+            .filter(|&(_, count)| count > 0)
+            .map(|(kind, count)| format!("{} {}", count, kind))
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!(
+            "Generation produced {} warning(s) ({}) and --fail-on-warnings is set",
+            diagnostics.len(),
+            breakdown
+        );
+    }
+
+    if let Some(spec_diff) = spec_diff {
+        if !spec_diff.is_empty() && !args.diff_report_only {
+            anyhow::bail!(
+                "Generated document differs from {}",
+                args.diff.as_ref().map(|p| p.display().to_string()).unwrap_or_default()
+            );
+        }
+    }
     
     Ok(())
 }
 
+/// Parse a `--default-status` value such as `post=200`
+fn parse_default_status(value: &str) -> Result<(crate::extractor::HttpMethod, u16), String> {
+    use crate::extractor::HttpMethod;
+
+    let (method, status) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected METHOD=STATUS, got `{}`", value))?;
+    let method = match method.trim().to_ascii_lowercase().as_str() {
+        "get" => HttpMethod::Get,
+        "post" => HttpMethod::Post,
+        "put" => HttpMethod::Put,
+        "delete" => HttpMethod::Delete,
+        "patch" => HttpMethod::Patch,
+        "options" => HttpMethod::Options,
+        "head" => HttpMethod::Head,
+        "trace" => HttpMethod::Trace,
+        other => return Err(format!("unknown HTTP method `{}`", other)),
+    };
+    Ok((method, parse_status(status)?))
+}
+
+/// Redraw the `Parsing N/M files` line on stderr, about once per percent of the files, and
+/// clear it after the last one. The cursor is left at the start of the line, so that warnings
+/// logged meanwhile overwrite it.
+// `usize::is_multiple_of` needs Rust 1.87; a remainder keeps older toolchains building
+#[allow(unknown_lints, clippy::manual_is_multiple_of)]
+fn show_parse_progress(parsed: usize, total: usize) {
+    use std::io::Write;
+
+    let mut stderr = std::io::stderr();
+    if parsed == total {
+        let _ = write!(stderr, "\x1b[2K\r");
+    } else if parsed % (total / 100).max(1) == 0 {
+        let _ = write!(stderr, "\x1b[2K\rParsing {}/{} files\r", parsed, total);
+    }
+    let _ = stderr.flush();
+}
+
+/// Parse a `--ref-prefix` value, accepting only prefixes whose references can be resolved
+fn parse_ref_prefix(value: &str) -> Result<String, String> {
+    crate::openapi_builder::schema_section(value)?;
+    Ok(value.to_string())
+}
+
+/// Parse a `--response-description` value such as `200=OK`
+fn parse_response_description(value: &str) -> Result<(u16, String), String> {
+    let (status, description) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected STATUS=TEXT, got `{}`", value))?;
+    Ok((parse_status(status)?, description.to_string()))
+}
+
+/// Parse an `--api-version` value, which must not be blank
+fn parse_api_version(value: &str) -> Result<String, String> {
+    let version = value.trim();
+    if version.is_empty() {
+        return Err("the API version must not be empty".to_string());
+    }
+    Ok(version.to_string())
+}
 
+/// Parse a `--global-header` value such as `X-API-Version:required` or `X-Request-Id`
+fn parse_global_header(value: &str) -> Result<(String, bool), String> {
+    let (name, required) = match value.rsplit_once(':') {
+        Some((name, "required")) => (name, true),
+        Some((name, "optional")) => (name, false),
+        Some(_) => return Err(format!("expected NAME[:required], got `{}`", value)),
+        None => (value, false),
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("expected NAME[:required], got `{}`", value));
+    }
+    Ok((name.to_string(), required))
+}
+
+/// Parse a `--server-variable` value such as `env=prod|staging` into the variable name and its
+/// values, default first
+fn parse_server_variable(value: &str) -> Result<(String, Vec<String>), String> {
+    let invalid = || format!("expected NAME=DEFAULT[|VALUE...], got `{}`", value);
+    let (name, values) = value.split_once('=').ok_or_else(invalid)?;
+    let values: Vec<String> = values.split('|').map(|v| v.trim().to_string()).collect();
+    if name.trim().is_empty() || values.iter().any(String::is_empty) {
+        return Err(invalid());
+    }
+    Ok((name.trim().to_string(), values))
+}
+
+/// The servers of `--base-url-from-env` and `--server`, in that order, each with the
+/// `--server-variable` values of the placeholders in its URL.
+///
+/// Fails if the environment variable is not set or a URL uses an undeclared placeholder.
+fn servers(args: &CliArgs) -> Result<Vec<crate::openapi_builder::Server>> {
+    use crate::openapi_builder::{Server, ServerVariable};
+
+    let mut urls = Vec::new();
+    if let Some(var) = &args.base_url_from_env {
+        match std::env::var(var) {
+            Ok(url) if !url.trim().is_empty() => urls.push(url.trim().to_string()),
+            _ => anyhow::bail!("Environment variable {} with the base URL is not set", var),
+        }
+    }
+    urls.extend(args.servers.iter().cloned());
+
+    let mut servers = Vec::new();
+    for url in urls {
+        let mut variables = std::collections::BTreeMap::new();
+        for placeholder in url.split('{').skip(1).filter_map(|rest| rest.split_once('}')) {
+            let name = placeholder.0;
+            let Some((_, values)) = args.server_variables.iter().find(|(n, _)| n == name) else {
+                anyhow::bail!(
+                    "Server URL {} uses `{{{}}}`; declare its values with --server-variable",
+                    url,
+                    name
+                );
+            };
+            variables.insert(
+                name.to_string(),
+                ServerVariable {
+                    enum_values: (values.len() > 1).then(|| values.clone()),
+                    default: values[0].clone(),
+                    description: None,
+                },
+            );
+        }
+        servers.push(Server {
+            url,
+            description: None,
+            variables: (!variables.is_empty()).then_some(variables),
+        });
+    }
+    Ok(servers)
+}
+
+/// Parse an `--example` value such as `new_user=examples/new_user.json`
+fn parse_named_example(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((name, path)) if !name.trim().is_empty() && !path.is_empty() => {
+            Ok((name.trim().to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!("expected NAME=FILE, got `{}`", value)),
+    }
+}
+
+/// Parse an HTTP status code
+fn parse_status(value: &str) -> Result<u16, String> {
+    match value.trim().parse::<u16>() {
+        Ok(status) if (100..=599).contains(&status) => Ok(status),
+        _ => Err(format!("invalid HTTP status code `{}`", value)),
+    }
+}
+
+/// Attach the file, line and signature of each resolved handler to its route as
+/// `x-source-location` (relative to the project) and `x-source-snippet` extensions
+fn attach_handler_sources(
+    routes: &mut [crate::extractor::RouteInfo],
+    project_path: &std::path::Path,
+) {
+    let mut sources: std::collections::HashMap<PathBuf, Option<String>> =
+        std::collections::HashMap::new();
+    for route in routes {
+        let Some(location) = &route.handler_location else {
+            continue;
+        };
+        let source = sources
+            .entry(location.file.clone())
+            .or_insert_with(|| std::fs::read_to_string(&location.file).ok());
+        let Some(source) = source else {
+            continue;
+        };
+        let file = location.file.strip_prefix(project_path).unwrap_or(&location.file);
+        let snippet = location.snippet(source);
+        route.extensions.insert(
+            "x-source-location".to_string(),
+            format!("{}:{}", file.display(), location.start_line).into(),
+        );
+        route.extensions.insert("x-source-snippet".to_string(), snippet.into());
+    }
+}
+
+/// The `[package]` name and version in the project's `Cargo.toml`, each `None` if the manifest
+/// or the field is missing or the field is not a plain string (`version.workspace = true`)
+fn package_metadata(project_path: &std::path::Path) -> (Option<String>, Option<String>) {
+    let manifest = project_path.join("Cargo.toml");
+    let Ok(content) = std::fs::read_to_string(&manifest) else {
+        return (None, None);
+    };
+    let table: toml::Table = match content.parse() {
+        Ok(table) => table,
+        Err(e) => {
+            log::warn!("Ignoring unparseable {}: {}", manifest.display(), e);
+            return (None, None);
+        }
+    };
+    let field = |key: &str| {
+        table
+            .get("package")
+            .and_then(|package| package.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+    (field("name"), field("version"))
+}
+
+/// Command-line names of frameworks (`actix-web`)
+fn framework_names(frameworks: &[Framework]) -> Vec<String> {
+    frameworks
+        .iter()
+        .filter_map(|framework| framework.to_possible_value())
+        .map(|value| value.get_name().to_string())
+        .collect()
+}
+
+/// The `x-generated` extension written by `--stamp`. The time is `source_date_epoch` seconds
+/// after the Unix epoch when given, or else now; the commit is omitted when the project is not
+/// a git checkout.
+fn generation_metadata(
+    project_path: &std::path::Path,
+    frameworks: Vec<String>,
+    source_date_epoch: Option<u64>,
+) -> serde_json::Value {
+    let time = source_date_epoch
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+        .unwrap_or_else(SystemTime::now);
+    let mut metadata = serde_json::json!({
+        "generator": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "timestamp": utc_timestamp(time),
+        "frameworks": frameworks,
+    });
+    if let Some(commit) = source_commit(project_path) {
+        metadata["commit"] = commit.into();
+    }
+    metadata
+}
+
+/// The commit checked out in the project's git repository, if git is installed and the project
+/// is in a repository
+fn source_commit(project_path: &std::path::Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(project_path)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        debug!("No git commit for {}", project_path.display());
+        return None;
+    }
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// RFC 3339 UTC timestamp of `time` to the second, e.g. `2024-10-23T08:15:00Z`
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let (days, seconds) = (secs / 86_400, secs % 86_400);
+
+    // Civil date of a day count since 1970-01-01, in 400-year eras starting on March 1st
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+/// Read the JSON payload of an `--example` file
+fn read_example(path: &std::path::Path) -> Result<serde_json::Value> {
+    use anyhow::Context;
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read example file: {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse JSON example: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_metadata_at_source_date_epoch() {
+        let project = tempfile::TempDir::new().unwrap();
+        let metadata =
+            generation_metadata(project.path(), vec!["axum".to_string()], Some(951_827_696));
+        assert_eq!(metadata["timestamp"], "2000-02-29T12:34:56Z");
+        assert_eq!(metadata["frameworks"], serde_json::json!(["axum"]));
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+    }
+}
@@ -13,6 +13,8 @@ use syn::{Item, UseTree};
 /// Currently supports detection of:
 /// - Axum (via `use axum::...`)
 /// - Actix-Web (via `use actix_web::...`)
+/// - Salvo (via `use salvo::...`)
+/// - Tide (via `use tide::...`)
 pub struct FrameworkDetector;
 
 /// Result of framework detection.
@@ -84,6 +86,16 @@ impl FrameworkDetector {
                 if ident == "actix_web" {
                     detected.insert(Framework::ActixWeb);
                 }
+
+                // Check for salvo
+                if ident == "salvo" {
+                    detected.insert(Framework::Salvo);
+                }
+
+                // Check for tide
+                if ident == "tide" {
+                    detected.insert(Framework::Tide);
+                }
                 
                 // Recursively check the rest of the path
                 Self::check_use_tree(&path.tree, detected);
@@ -103,6 +115,12 @@ impl FrameworkDetector {
                 if ident == "actix_web" {
                     detected.insert(Framework::ActixWeb);
                 }
+                if ident == "salvo" {
+                    detected.insert(Framework::Salvo);
+                }
+                if ident == "tide" {
+                    detected.insert(Framework::Tide);
+                }
             }
             UseTree::Name(name) => {
                 // Check the name
@@ -113,6 +131,12 @@ impl FrameworkDetector {
                 if ident == "actix_web" {
                     detected.insert(Framework::ActixWeb);
                 }
+                if ident == "salvo" {
+                    detected.insert(Framework::Salvo);
+                }
+                if ident == "tide" {
+                    detected.insert(Framework::Tide);
+                }
             }
             UseTree::Glob(_) => {
                 // Glob imports don't help us identify the framework
@@ -195,6 +219,55 @@ mod tests {
         assert!(result.frameworks.contains(&Framework::ActixWeb));
     }
 
+    #[test]
+    fn test_detect_salvo_framework() {
+        let temp_dir = TempDir::new().unwrap();
+        
+        let salvo_code = r#"
+            use salvo::prelude::*;
+            
+            #[handler]
+            async fn hello() -> &'static str {
+                "Hello, World!"
+            }
+            
+            pub fn router() -> Router {
+                Router::new().get(hello)
+            }
+        "#;
+        
+        let parsed = parse_test_file(&temp_dir, "salvo.rs", salvo_code);
+        let result = FrameworkDetector::detect(&[parsed]);
+        
+        assert_eq!(result.frameworks.len(), 1);
+        assert!(result.frameworks.contains(&Framework::Salvo));
+    }
+
+    #[test]
+    fn test_detect_tide_framework() {
+        let temp_dir = TempDir::new().unwrap();
+        
+        let tide_code = r#"
+            use tide::Request;
+            
+            async fn hello(_req: Request<()>) -> tide::Result {
+                Ok("Hello, World!".into())
+            }
+            
+            pub fn app() -> tide::Server<()> {
+                let mut app = tide::new();
+                app.at("/").get(hello);
+                app
+            }
+        "#;
+        
+        let parsed = parse_test_file(&temp_dir, "tide.rs", tide_code);
+        let result = FrameworkDetector::detect(&[parsed]);
+        
+        assert_eq!(result.frameworks.len(), 1);
+        assert!(result.frameworks.contains(&Framework::Tide));
+    }
+
     #[test]
     fn test_detect_mixed_frameworks() {
         let temp_dir = TempDir::new().unwrap();
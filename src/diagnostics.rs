@@ -0,0 +1,141 @@
+//! Diagnostics collected while generating an OpenAPI document.
+//!
+//! Most problems encountered during generation are not fatal: a file that fails to parse is
+//! skipped, an unknown handler produces an operation without type information, and an
+//! unresolvable type is documented as an `object` placeholder. These events are recorded as
+//! [`Diagnostic`]s so callers can decide whether an incomplete document is acceptable
+//! (for example, the CLI's `--fail-on-warnings` flag).
+
+use serde::Serialize;
+
+/// Category of a diagnostic event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// A source file could not be parsed and was skipped
+    ParseFailure,
+    /// A route references a handler function that could not be found
+    UnresolvedHandler,
+    /// A type could not be resolved and was documented as an `object` placeholder
+    PlaceholderSchema,
+    /// A route's URL parameters do not match the parameters extracted by its handler
+    PathParameterMismatch,
+    /// A handler declares more than one request body extractor; only the first is documented
+    MultipleRequestBodies,
+    /// `--max-depth` or `--max-files` left part of the project unscanned
+    ScanLimit,
+}
+
+impl DiagnosticKind {
+    /// Every kind, in the order summaries list them
+    pub const ALL: [DiagnosticKind; 6] = [
+        DiagnosticKind::ParseFailure,
+        DiagnosticKind::UnresolvedHandler,
+        DiagnosticKind::PlaceholderSchema,
+        DiagnosticKind::PathParameterMismatch,
+        DiagnosticKind::MultipleRequestBodies,
+        DiagnosticKind::ScanLimit,
+    ];
+}
+
+/// A single diagnostic event with a human-readable message.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    /// The category of this diagnostic
+    pub kind: DiagnosticKind,
+    /// Description of what went wrong
+    pub message: String,
+    /// Likely fixes, such as the names of functions similar to an unresolved handler
+    pub suggestions: Vec<String>,
+}
+
+/// Collection of diagnostics gathered across all generation stages.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    entries: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Create an empty diagnostics collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new diagnostic
+    pub fn push(&mut self, kind: DiagnosticKind, message: impl Into<String>) {
+        self.push_with_suggestions(kind, message, Vec::new());
+    }
+
+    /// Record a new diagnostic together with likely fixes
+    pub fn push_with_suggestions(
+        &mut self,
+        kind: DiagnosticKind,
+        message: impl Into<String>,
+        suggestions: Vec<String>,
+    ) {
+        self.entries.push(Diagnostic {
+            kind,
+            message: message.into(),
+            suggestions,
+        });
+    }
+
+    /// Whether no diagnostics have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Total number of recorded diagnostics
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Number of diagnostics of the given kind
+    pub fn count(&self, kind: DiagnosticKind) -> usize {
+        self.entries.iter().filter(|d| d.kind == kind).count()
+    }
+
+    /// Iterate over all recorded diagnostics in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.entries.iter()
+    }
+}
+
+impl std::fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DiagnosticKind::ParseFailure => write!(f, "parse failure"),
+            DiagnosticKind::UnresolvedHandler => write!(f, "unresolved handler"),
+            DiagnosticKind::PlaceholderSchema => write!(f, "placeholder schema"),
+            DiagnosticKind::PathParameterMismatch => write!(f, "path parameter mismatch"),
+            DiagnosticKind::MultipleRequestBodies => write!(f, "multiple request bodies"),
+            DiagnosticKind::ScanLimit => write!(f, "scan limit"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_diagnostics() {
+        let diagnostics = Diagnostics::new();
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(diagnostics.len(), 0);
+    }
+
+    #[test]
+    fn test_count_by_kind() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(DiagnosticKind::UnresolvedHandler, "Unknown handler: a");
+        diagnostics.push(DiagnosticKind::UnresolvedHandler, "Unknown handler: b");
+        diagnostics.push(DiagnosticKind::PlaceholderSchema, "Unresolved type: Foo");
+
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics.count(DiagnosticKind::UnresolvedHandler), 2);
+        assert_eq!(diagnostics.count(DiagnosticKind::PlaceholderSchema), 1);
+        assert_eq!(diagnostics.count(DiagnosticKind::ParseFailure), 0);
+    }
+}
@@ -0,0 +1,250 @@
+//! Structural comparison of OpenAPI documents.
+//!
+//! Unlike a textual diff, comparing the parsed documents ignores key ordering and formatting,
+//! so only real API changes are reported. This is used by the CLI's `--diff` mode to detect
+//! unintended API changes in CI.
+
+use crate::openapi_builder::{OpenApiDocument, Operation};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Differences between two OpenAPI documents.
+///
+/// All lists are sorted. Operations are identified as `"METHOD /path"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecDiff {
+    /// Paths present only in the new document
+    pub added_paths: Vec<String>,
+    /// Paths present only in the old document
+    pub removed_paths: Vec<String>,
+    /// Operations present only in the new document
+    pub added_operations: Vec<String>,
+    /// Operations present only in the old document
+    pub removed_operations: Vec<String>,
+    /// Operations present in both documents whose definitions differ
+    pub changed_operations: Vec<String>,
+    /// Component schemas present only in the new document
+    pub added_schemas: Vec<String>,
+    /// Component schemas present only in the old document
+    pub removed_schemas: Vec<String>,
+    /// Component schemas present in both documents whose definitions differ
+    pub changed_schemas: Vec<String>,
+}
+
+impl SpecDiff {
+    /// Whether the two documents are structurally identical
+    pub fn is_empty(&self) -> bool {
+        self.added_paths.is_empty()
+            && self.removed_paths.is_empty()
+            && self.added_operations.is_empty()
+            && self.removed_operations.is_empty()
+            && self.changed_operations.is_empty()
+            && self.added_schemas.is_empty()
+            && self.removed_schemas.is_empty()
+            && self.changed_schemas.is_empty()
+    }
+}
+
+impl std::fmt::Display for SpecDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No differences");
+        }
+
+        let sections = [
+            ("Added paths", &self.added_paths),
+            ("Removed paths", &self.removed_paths),
+            ("Added operations", &self.added_operations),
+            ("Removed operations", &self.removed_operations),
+            ("Changed operations", &self.changed_operations),
+            ("Added schemas", &self.added_schemas),
+            ("Removed schemas", &self.removed_schemas),
+            ("Changed schemas", &self.changed_schemas),
+        ];
+
+        let mut first = true;
+        for (title, entries) in sections {
+            if entries.is_empty() {
+                continue;
+            }
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "{}:", title)?;
+            for entry in entries {
+                write!(f, "\n  {}", entry)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare two documents structurally.
+///
+/// `old` is typically the previously published spec and `new` the freshly generated one.
+///
+/// # Example
+///
+/// ```
+/// use openapi_from_source::diff::diff;
+/// use openapi_from_source::openapi_builder::OpenApiBuilder;
+/// use openapi_from_source::schema_generator::SchemaGenerator;
+/// use openapi_from_source::type_resolver::TypeResolver;
+///
+/// let doc = OpenApiBuilder::new().build(SchemaGenerator::new(TypeResolver::new(vec![])));
+/// assert!(diff(&doc, &doc).is_empty());
+/// ```
+pub fn diff(old: &OpenApiDocument, new: &OpenApiDocument) -> SpecDiff {
+    let mut result = SpecDiff::default();
+
+    // Paths
+    let old_paths: BTreeSet<&String> = old.paths.keys().collect();
+    let new_paths: BTreeSet<&String> = new.paths.keys().collect();
+    result.added_paths = new_paths.difference(&old_paths).map(|p| p.to_string()).collect();
+    result.removed_paths = old_paths.difference(&new_paths).map(|p| p.to_string()).collect();
+
+    // Operations
+    let old_ops = collect_operations(old);
+    let new_ops = collect_operations(new);
+    for (key, new_op) in &new_ops {
+        match old_ops.get(key) {
+            None => result.added_operations.push(key.clone()),
+            Some(old_op) if to_value(old_op) != to_value(new_op) => {
+                result.changed_operations.push(key.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    result.removed_operations = old_ops
+        .keys()
+        .filter(|key| !new_ops.contains_key(*key))
+        .cloned()
+        .collect();
+
+    // Schemas
+    let empty = Default::default();
+    let old_schemas = old
+        .components
+        .as_ref()
+        .and_then(|c| c.schemas.as_ref())
+        .unwrap_or(&empty);
+    let new_schemas = new
+        .components
+        .as_ref()
+        .and_then(|c| c.schemas.as_ref())
+        .unwrap_or(&empty);
+    let old_names: BTreeSet<&String> = old_schemas.keys().collect();
+    let new_names: BTreeSet<&String> = new_schemas.keys().collect();
+    result.added_schemas = new_names.difference(&old_names).map(|n| n.to_string()).collect();
+    result.removed_schemas = old_names.difference(&new_names).map(|n| n.to_string()).collect();
+    result.changed_schemas = new_names
+        .intersection(&old_names)
+        .filter(|name| to_value(&old_schemas[**name]) != to_value(&new_schemas[**name]))
+        .map(|n| n.to_string())
+        .collect();
+
+    result
+}
+
+/// Index all operations of a document by `"METHOD /path"`
+fn collect_operations(doc: &OpenApiDocument) -> BTreeMap<String, &Operation> {
+    let mut operations = BTreeMap::new();
+    for (path, item) in &doc.paths {
+        for (method, operation) in item.operations() {
+            operations.insert(format!("{} {}", method.to_uppercase(), path), operation);
+        }
+    }
+    operations
+}
+
+/// Convert a value to JSON for order-insensitive comparison
+fn to_value<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or(serde_json::Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::{HttpMethod, RouteInfo, TypeInfo};
+    use crate::openapi_builder::OpenApiBuilder;
+    use crate::parser::ParsedFile;
+    use crate::schema_generator::SchemaGenerator;
+    use crate::type_resolver::TypeResolver;
+    use std::path::PathBuf;
+
+    /// Helper function to build a document from source code and routes
+    fn build_document(code: &str, routes: &[RouteInfo]) -> OpenApiDocument {
+        let parsed = ParsedFile {
+            path: PathBuf::from("test.rs"),
+            syntax_tree: syn::parse_file(code).unwrap(),
+        };
+        let mut schema_gen = SchemaGenerator::new(TypeResolver::new(vec![parsed]));
+        let mut builder = OpenApiBuilder::new();
+        for route in routes {
+            builder.add_route(route, &mut schema_gen);
+        }
+        builder.build(schema_gen)
+    }
+
+    fn route(path: &str, method: HttpMethod, handler: &str) -> RouteInfo {
+        RouteInfo::new(path.to_string(), method, handler.to_string())
+    }
+
+    #[test]
+    fn test_identical_documents() {
+        let routes = vec![route("/users", HttpMethod::Get, "list_users")];
+        let old = build_document("", &routes);
+        let new = build_document("", &routes);
+
+        let result = diff(&old, &new);
+        assert!(result.is_empty());
+        assert_eq!(result.to_string(), "No differences");
+    }
+
+    #[test]
+    fn test_added_and_removed_paths() {
+        let old = build_document("", &[route("/users", HttpMethod::Get, "list_users")]);
+        let new = build_document("", &[route("/posts", HttpMethod::Get, "list_posts")]);
+
+        let result = diff(&old, &new);
+        assert_eq!(result.added_paths, vec!["/posts"]);
+        assert_eq!(result.removed_paths, vec!["/users"]);
+        assert_eq!(result.added_operations, vec!["GET /posts"]);
+        assert_eq!(result.removed_operations, vec!["GET /users"]);
+    }
+
+    #[test]
+    fn test_added_operation_on_existing_path() {
+        let old = build_document("", &[route("/users", HttpMethod::Get, "list_users")]);
+        let new = build_document(
+            "",
+            &[
+                route("/users", HttpMethod::Get, "list_users"),
+                route("/users", HttpMethod::Post, "create_user"),
+            ],
+        );
+
+        let result = diff(&old, &new);
+        assert!(result.added_paths.is_empty());
+        assert_eq!(result.added_operations, vec!["POST /users"]);
+        assert!(result.changed_operations.is_empty());
+    }
+
+    #[test]
+    fn test_changed_schema() {
+        let mut create = route("/users", HttpMethod::Post, "create_user");
+        create.request_body = Some(TypeInfo::new("User".to_string()));
+
+        let old = build_document("pub struct User { pub id: u32 }", &[create.clone()]);
+        let new = build_document(
+            "pub struct User { pub id: u32, pub name: String }",
+            &[create],
+        );
+
+        let result = diff(&old, &new);
+        assert_eq!(result.changed_schemas, vec!["User"]);
+        assert!(result.added_schemas.is_empty());
+        assert!(result.changed_operations.is_empty());
+    }
+}
@@ -1,76 +1,183 @@
+use crate::diagnostics::Diagnostics;
+use crate::extractor::functions::{is_entry_point, FunctionIndex};
+use crate::extractor::imports::ImportMap;
 use crate::extractor::{
-    HttpMethod, Parameter, ParameterLocation, RouteExtractor, RouteInfo, TypeInfo,
+    extract_type_info, extractor_allowed, parse_openapi_attributes, peel_option,
+    raw_extractor_name, report_ignored_bodies, ExtractOptions, HttpMethod, ModuleTags,
+    OpenApiAttributes, Parameter, ParameterLocation, RouteExtractor, RouteInfo, TypeInfo,
+    FALLBACK_SUMMARY, PATH_EXTRACTOR_PARAM,
 };
-use crate::parser::ParsedFile;
+use crate::parser::{extract_doc_comment, ParsedFile};
+use log::debug;
 use syn::{visit::Visit, Attribute, Expr, Lit, Meta};
 
 /// Actix-Web route extractor
 pub struct ActixExtractor;
 
+/// Crates whose extractors are recognized in strict import mode
+const ACTIX_CRATES: &[&str] = &["actix_web"];
+
+/// Crates `Bytes` raw body extractors are recognized from in strict import mode
+const BYTES_CRATES: &[&str] = &["actix_web", "bytes"];
+
+/// The media type of a raw request body read by a `web::Bytes` or `String` handler argument
+fn raw_body_media_type(ty: &syn::Type) -> Option<&'static str> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if !segment.arguments.is_none() {
+        return None;
+    }
+    match segment.ident.to_string().as_str() {
+        "Bytes" => Some("application/octet-stream"),
+        "String" => Some("text/plain"),
+        _ => None,
+    }
+}
+
 impl RouteExtractor for ActixExtractor {
     fn extract_routes(&self, parsed_files: &[ParsedFile]) -> Vec<RouteInfo> {
-        let mut visitor = ActixVisitor::new();
+        self.extract_routes_with_diagnostics(parsed_files, &mut Diagnostics::new())
+    }
+
+    fn extract_routes_with_options(
+        &self,
+        parsed_files: &[ParsedFile],
+        options: &ExtractOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<RouteInfo> {
+        let mut visitor = ActixVisitor::new(options.clone());
 
         // First pass: collect all function signatures and routes from all files
         for parsed_file in parsed_files {
+            visitor.imports.push(ImportMap::from_file(&parsed_file.syntax_tree));
+            visitor.function_index.enter_file(&parsed_file.path);
+            visitor.module_tags.enter(&parsed_file.syntax_tree.attrs);
             visitor.visit_file(&parsed_file.syntax_tree);
+            visitor.module_tags.exit();
         }
 
         // After collecting routes and functions from all files, analyze handlers
-        visitor.analyze_handlers();
+        visitor.analyze_handlers(diagnostics);
 
         visitor.routes
     }
 }
 
+/// What the extractors of a handler signature read from a request
+struct ExtractedInputs {
+    /// Parameters from `web::Path<T>` and `web::Query<T>` extractors
+    parameters: Vec<Parameter>,
+    /// Type of the request body, read by the first `web::Json<T>`, `web::Bytes` or `String`
+    body: Option<TypeInfo>,
+    /// Whether the body is required, i.e. not extracted as an `Option`
+    body_required: bool,
+    /// Types of further body extractors, which are ignored in favour of the first
+    ignored_bodies: Vec<TypeInfo>,
+    /// Media type of a raw `web::Bytes` or `String` body
+    body_media_type: Option<&'static str>,
+}
+
 /// Visitor for traversing the AST and finding Actix-Web routes
 struct ActixVisitor {
     routes: Vec<RouteInfo>,
     current_scope: String,
     functions: std::collections::HashMap<String, syn::Signature>,
+    /// `#[openapi(...)]` overrides declared on handler functions
+    openapi_attrs: std::collections::HashMap<String, OpenApiAttributes>,
+    /// Doc comments of the visited functions
+    handler_docs: std::collections::HashMap<String, String>,
+    /// Imports of each visited file, in visiting order
+    imports: Vec<ImportMap>,
+    /// Index into `imports` of the file declaring each function
+    function_files: std::collections::HashMap<String, usize>,
+    /// Tags inherited from enclosing files and modules
+    module_tags: ModuleTags,
+    /// Module-qualified keys of the visited functions
+    function_index: FunctionIndex,
+    options: ExtractOptions,
 }
 
 impl ActixVisitor {
-    fn new() -> Self {
+    fn new(options: ExtractOptions) -> Self {
         Self {
             routes: Vec::new(),
             current_scope: String::new(),
             functions: std::collections::HashMap::new(),
+            openapi_attrs: std::collections::HashMap::new(),
+            handler_docs: std::collections::HashMap::new(),
+            imports: Vec::new(),
+            function_files: std::collections::HashMap::new(),
+            module_tags: ModuleTags::default(),
+            function_index: FunctionIndex::default(),
+            options,
         }
     }
 
     /// Analyze routes with handler information
-    fn analyze_handlers(&mut self) {
+    fn analyze_handlers(&mut self, diagnostics: &mut Diagnostics) {
         // Create a copy of routes to avoid borrow checker issues
         let routes_to_update: Vec<_> = self
             .routes
             .iter()
             .enumerate()
-            .map(|(idx, route)| (idx, route.handler_name.clone()))
+            .map(|(idx, route)| (idx, route.handler_path.clone()))
             .collect();
 
-        for (idx, handler_name) in routes_to_update {
-            if let Some(fn_sig) = self.functions.get(&handler_name) {
-                let (params, request_body) = self.parse_extractors(fn_sig);
+        for (idx, handler_path) in routes_to_update {
+            // Resolve the handler as written at the registration to a module-qualified key
+            let handler_key = self
+                .function_index
+                .resolve(&handler_path)
+                .map(str::to_string)
+                .unwrap_or(handler_path);
+            if let Some(attrs) = self.openapi_attrs.get(&handler_key) {
+                attrs.apply_to(&mut self.routes[idx]);
+            }
+            self.routes[idx].doc = self.handler_docs.get(&handler_key).cloned();
+            self.module_tags.apply_to(&handler_key, &mut self.routes[idx]);
+
+            if let Some(fn_sig) = self.functions.get(&handler_key) {
+                self.routes[idx].handler_path = handler_key.clone();
+                self.routes[idx].handler_resolved = true;
+                self.routes[idx].handler_location =
+                    self.function_index.location(&handler_key, fn_sig);
+                let imports = self
+                    .function_files
+                    .get(&handler_key)
+                    .and_then(|&idx| self.imports.get(idx));
+                let inputs = self.parse_extractors(fn_sig, imports);
 
                 // Merge path parameters from URL with parameters from extractors
                 let mut all_params = self.routes[idx].parameters.clone();
-                all_params.extend(params);
+                all_params.extend(inputs.parameters);
 
                 self.routes[idx].parameters = all_params;
-                self.routes[idx].request_body = request_body;
+                if let Some(body) = &inputs.body {
+                    let ignored = &inputs.ignored_bodies;
+                    report_ignored_bodies(&self.routes[idx], body, ignored, diagnostics);
+                }
+                // An `#[openapi(request_body = "...")]` override takes precedence
+                if self.routes[idx].request_body.is_none() {
+                    self.routes[idx].request_body = inputs.body;
+                    self.routes[idx].request_body_required = inputs.body_required;
+                    // An `#[openapi(consumes = "...")]` override takes precedence
+                    if self.routes[idx].consumes.is_empty() {
+                        self.routes[idx].consumes =
+                            inputs.body_media_type.into_iter().map(str::to_string).collect();
+                    }
+                }
             }
         }
     }
 
     /// Find and parse route macros (#[get], #[post], etc.)
-    fn find_route_macros(&mut self, item_fn: &syn::ItemFn) {
-        let fn_name = item_fn.sig.ident.to_string();
-
+    fn find_route_macros(&mut self, item_fn: &syn::ItemFn, key: &str) {
         for attr in &item_fn.attrs {
             if let Some((method, path)) = self.parse_route_macro(attr) {
                 let full_path = self.combine_paths(&self.current_scope, &path);
-                let mut route = RouteInfo::new(full_path.clone(), method, fn_name.clone());
+                let mut route = RouteInfo::for_handler(full_path.clone(), method, key.to_string());
                 route.parameters = self.extract_path_parameters(&full_path);
                 self.routes.push(route);
             }
@@ -119,6 +226,8 @@ impl ActixVisitor {
             "patch" => Some(HttpMethod::Patch),
             "head" => Some(HttpMethod::Head),
             "options" => Some(HttpMethod::Options),
+            "trace" => Some(HttpMethod::Trace),
+            "connect" => Some(HttpMethod::Connect),
             _ => None,
         }
     }
@@ -148,6 +257,7 @@ impl ActixVisitor {
                 let param_name = segment
                     .trim_start_matches('{')
                     .trim_end_matches('}')
+                    .trim_start_matches('*')
                     .to_string();
                 parameters.push(Parameter::new(
                     param_name,
@@ -162,24 +272,68 @@ impl ActixVisitor {
     }
 
     /// Parse extractors from a function signature
-    fn parse_extractors(&self, fn_sig: &syn::Signature) -> (Vec<Parameter>, Option<TypeInfo>) {
+    fn parse_extractors(
+        &self,
+        fn_sig: &syn::Signature,
+        imports: Option<&ImportMap>,
+    ) -> ExtractedInputs {
         let mut parameters = Vec::new();
         let mut request_body = None;
+        let mut request_body_required = true;
+        let mut ignored_bodies = Vec::new();
+        let mut request_body_media_type = None;
 
         for input in &fn_sig.inputs {
             if let syn::FnArg::Typed(pat_type) = input {
+                // Raw extractors carry no typed schema and are deliberately skipped
+                if let Some(raw) = raw_extractor_name(&pat_type.ty) {
+                    debug!("Skipping raw extractor: {}", raw);
+                    continue;
+                }
+
+                // Optional extractors like Option<Json<T>> are unwrapped first
+                let (extractor_ty, is_optional) = peel_option(&pat_type.ty);
+
+                // web::Bytes and String read the whole body without deserializing it
+                if let Some(media_type) = raw_body_media_type(extractor_ty) {
+                    let body_type = extract_type_info(extractor_ty);
+                    let is_bytes = body_type.name == "Bytes";
+                    if is_bytes
+                        && !extractor_allowed(extractor_ty, imports, &self.options, BYTES_CRATES)
+                    {
+                        debug!("Skipping Bytes not imported from actix_web or bytes");
+                    } else if request_body.is_none() {
+                        request_body = Some(body_type);
+                        request_body_required = !is_optional;
+                        request_body_media_type = Some(media_type);
+                    } else {
+                        ignored_bodies.push(body_type);
+                    }
+                    continue;
+                }
+
                 // Extract type information
-                if let Some((extractor_type, inner_type)) = self.parse_extractor_type(&pat_type.ty)
+                if let Some((extractor_type, inner_type)) = self.parse_extractor_type(extractor_ty)
                 {
+                    if !extractor_allowed(extractor_ty, imports, &self.options, ACTIX_CRATES) {
+                        debug!("Skipping {} not imported from actix_web", extractor_type);
+                        continue;
+                    }
+
                     match extractor_type.as_str() {
                         "Json" => {
-                            // web::Json<T> is a request body
-                            request_body = Some(inner_type);
+                            // web::Json<T> is a request body; only the first one can consume it
+                            if request_body.is_none() {
+                                request_body = Some(inner_type);
+                                request_body_required = !is_optional;
+                            } else {
+                                ignored_bodies.push(inner_type);
+                            }
                         }
                         "Path" => {
                             // web::Path<T> contains path parameters
                             parameters.push(Parameter::new(
-                                "path_params".to_string(),
+                                PATH_EXTRACTOR_PARAM.to_string(),
                                 ParameterLocation::Path,
                                 inner_type,
                                 true,
@@ -200,7 +354,13 @@ impl ActixVisitor {
             }
         }
 
-        (parameters, request_body)
+        ExtractedInputs {
+            parameters,
+            body: request_body,
+            body_required: request_body_required,
+            ignored_bodies,
+            body_media_type: request_body_media_type,
+        }
     }
 
     /// Parse an extractor type like web::Json<T>, web::Path<T>, web::Query<T>
@@ -214,7 +374,7 @@ impl ActixVisitor {
                     // Extract the generic type argument
                     if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
                         if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                            let type_info = self.extract_type_info(inner_ty);
+                            let type_info = extract_type_info(inner_ty);
                             return Some((extractor_name, type_info));
                         }
                     }
@@ -223,53 +383,38 @@ impl ActixVisitor {
         }
         None
     }
-
-    /// Extract TypeInfo from a syn::Type
-    fn extract_type_info(&self, ty: &syn::Type) -> TypeInfo {
-        match ty {
-            syn::Type::Path(type_path) => {
-                if let Some(segment) = type_path.path.segments.last() {
-                    let type_name = segment.ident.to_string();
-
-                    // Check for Option<T>
-                    if type_name == "Option" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                                let inner_type_info = self.extract_type_info(inner_ty);
-                                return TypeInfo::option(inner_type_info);
-                            }
-                        }
-                    }
-
-                    // Check for Vec<T>
-                    if type_name == "Vec" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                                let inner_type_info = self.extract_type_info(inner_ty);
-                                return TypeInfo::vec(inner_type_info);
-                            }
-                        }
-                    }
-
-                    // Simple type
-                    TypeInfo::new(type_name)
-                } else {
-                    TypeInfo::new("unknown".to_string())
-                }
-            }
-            _ => TypeInfo::new("unknown".to_string()),
-        }
-    }
 }
 
 impl<'ast> Visit<'ast> for ActixVisitor {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        self.module_tags.enter(&node.attrs);
+        self.function_index.enter_module(&node.ident);
+        syn::visit::visit_item_mod(self, node);
+        self.function_index.exit_module();
+        self.module_tags.exit();
+    }
+
     fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
         // Store function signatures for later analysis
-        let fn_name = node.sig.ident.to_string();
-        self.functions.insert(fn_name, node.sig.clone());
+        // Entry points only build the app; they can never be handlers
+        if !is_entry_point(node) {
+            let key = self.function_index.insert(&node.sig);
+            let openapi_attrs = parse_openapi_attributes(&node.attrs);
+            if !openapi_attrs.is_empty() {
+                self.openapi_attrs.insert(key.clone(), openapi_attrs);
+            }
+            if let Some(doc) = extract_doc_comment(&node.attrs) {
+                self.handler_docs.insert(key.clone(), doc);
+            }
+            if let Some(file_idx) = self.imports.len().checked_sub(1) {
+                self.function_files.insert(key.clone(), file_idx);
+            }
+            self.module_tags.record(&key);
+            self.functions.insert(key.clone(), node.sig.clone());
 
-        // Look for route macros on this function
-        self.find_route_macros(node);
+            // Look for route macros on this function
+            self.find_route_macros(node, &key);
+        }
 
         // Continue visiting child nodes
         syn::visit::visit_item_fn(self, node);
@@ -293,6 +438,13 @@ impl<'ast> Visit<'ast> for ActixVisitor {
             }
         }
 
+        // .default_service(web::to(handler)) answers unmatched requests
+        if method_name == "default_service" {
+            if let Some(route) = node.args.first().and_then(|arg| self.parse_default_service(arg)) {
+                self.routes.push(route);
+            }
+        }
+
         // Continue visiting child nodes
         syn::visit::visit_expr_method_call(self, node);
     }
@@ -309,6 +461,46 @@ impl ActixVisitor {
         self.extract_string_literal(&expr.args[0])
     }
 
+    /// Parse the service passed to `.default_service()` as a catch-all GET route, if fallbacks
+    /// are documented and the service delegates to a handler function
+    fn parse_default_service(&self, service: &Expr) -> Option<RouteInfo> {
+        let Some(fallback_path) = &self.options.fallback_path else {
+            debug!("Ignoring default service");
+            return None;
+        };
+        let handler_path = Self::service_handler(service)?;
+
+        let full_path = self.combine_paths(&self.current_scope, fallback_path);
+        let mut route = RouteInfo::for_handler(full_path.clone(), HttpMethod::Get, handler_path);
+        route.parameters = self.extract_path_parameters(&full_path);
+        route.summary = Some(FALLBACK_SUMMARY.to_string());
+        Some(route)
+    }
+
+    /// Handler a service delegates to: `web::to(handler)`, `web::route().to(handler)`
+    fn service_handler(service: &Expr) -> Option<String> {
+        let handler = match service {
+            Expr::Call(call) => match &*call.func {
+                Expr::Path(func) if func.path.segments.last()?.ident == "to" => call.args.first(),
+                _ => None,
+            },
+            Expr::MethodCall(call) if call.method == "to" => call.args.first(),
+            _ => None,
+        }?;
+        match handler {
+            Expr::Path(path_expr) => Some(
+                path_expr
+                    .path
+                    .segments
+                    .iter()
+                    .map(|s| s.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::"),
+            ),
+            _ => None,
+        }
+    }
+
     /// Extract a string literal from an expression
     fn extract_string_literal(&self, expr: &Expr) -> Option<String> {
         match expr {
@@ -387,13 +579,18 @@ mod tests {
             async fn patch_resource() -> HttpResponse {
                 HttpResponse::Ok().finish()
             }
+
+            #[trace("/resource")]
+            async fn trace_resource() -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
         "#;
 
         let parsed = parse_code(code);
         let extractor = ActixExtractor;
         let routes = extractor.extract_routes(&[parsed]);
 
-        assert_eq!(routes.len(), 5);
+        assert_eq!(routes.len(), 6);
 
         let methods: Vec<_> = routes.iter().map(|r| &r.method).collect();
         assert!(methods.contains(&&HttpMethod::Get));
@@ -401,6 +598,7 @@ mod tests {
         assert!(methods.contains(&&HttpMethod::Put));
         assert!(methods.contains(&&HttpMethod::Delete));
         assert!(methods.contains(&&HttpMethod::Patch));
+        assert!(methods.contains(&&HttpMethod::Trace));
     }
 
     #[test]
@@ -711,4 +909,169 @@ mod tests {
         assert!(param_names.contains(&"project_id"));
         assert!(param_names.contains(&"task_id"));
     }
+
+    #[test]
+    fn test_openapi_attribute_overrides() {
+        let code = r#"
+            use actix_web::{get, HttpResponse};
+
+            #[openapi(operation_id = "listUsers", summary = "List all users", tag = "users")]
+            #[get("/users")]
+            async fn list_users() -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+
+            #[get("/users/{id}")]
+            async fn get_user() -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = ActixExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 2);
+        let list = routes.iter().find(|r| r.handler_name == "list_users").unwrap();
+        assert_eq!(list.operation_id, Some("listUsers".to_string()));
+        assert_eq!(list.summary, Some("List all users".to_string()));
+        assert_eq!(list.tags, vec!["users"]);
+
+        let get = routes.iter().find(|r| r.handler_name == "get_user").unwrap();
+        assert_eq!(get.operation_id, None);
+        assert!(get.tags.is_empty());
+    }
+
+    #[test]
+    fn test_macro_handler_doc_comment_is_captured() {
+        let code = r#"
+            use actix_web::{get, HttpResponse};
+
+            /// List users.
+            ///
+            /// Newest first.
+            #[get("/users")]
+            async fn list_users() -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+
+            #[get("/health")]
+            async fn health() -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let routes = ActixExtractor.extract_routes(&[parsed]);
+
+        let list = routes.iter().find(|r| r.handler_name == "list_users").unwrap();
+        assert_eq!(list.doc.as_deref(), Some("List users.\n\nNewest first."));
+        let health = routes.iter().find(|r| r.handler_name == "health").unwrap();
+        assert!(health.doc.is_none());
+    }
+
+    #[test]
+    fn test_strict_imports_accept_web_extractors() {
+        let code = r#"
+            use actix_web::{post, web, HttpResponse};
+            use validator::Valid as Query;
+
+            #[post("/users")]
+            async fn create_user(user: web::Json<User>, filter: Query<Filter>) -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+        "#;
+
+        let options = ExtractOptions {
+            strict_imports: true,
+            ..Default::default()
+        };
+        let routes = ActixExtractor.extract_routes_with_options(
+            &[parse_code(code)],
+            &options,
+            &mut Diagnostics::new(),
+        );
+
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].request_body.as_ref().unwrap().name, "User");
+        assert!(routes[0].parameters.is_empty());
+    }
+
+    #[test]
+    fn test_raw_request_bodies() {
+        let code = r#"
+            use actix_web::{post, web, HttpResponse};
+
+            #[post("/webhooks")]
+            async fn receive_webhook(body: web::Bytes) -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+
+            #[post("/notes")]
+            async fn create_note(path: web::Path<u32>, text: String) -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+
+            #[post("/imports")]
+            #[openapi(consumes = "text/csv")]
+            async fn import(body: Option<String>) -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+        "#;
+
+        let routes = ActixExtractor.extract_routes(&[parse_code(code)]);
+
+        let webhook = routes.iter().find(|r| r.handler_name == "receive_webhook").unwrap();
+        assert_eq!(webhook.request_body.as_ref().unwrap().name, "Bytes");
+        assert_eq!(webhook.consumes, vec!["application/octet-stream"]);
+
+        let note = routes.iter().find(|r| r.handler_name == "create_note").unwrap();
+        assert_eq!(note.request_body.as_ref().unwrap().name, "String");
+        assert_eq!(note.consumes, vec!["text/plain"]);
+
+        let import = routes.iter().find(|r| r.handler_name == "import").unwrap();
+        assert!(!import.request_body_required);
+        assert_eq!(import.consumes, vec!["text/csv"]);
+    }
+
+    #[test]
+    fn test_default_service_documented_on_request() {
+        let code = r#"
+            use actix_web::{get, web, App, HttpResponse};
+
+            #[get("/health")]
+            async fn health() -> HttpResponse {
+                HttpResponse::Ok().finish()
+            }
+
+            async fn not_found() -> HttpResponse {
+                HttpResponse::NotFound().finish()
+            }
+
+            fn app() {
+                App::new()
+                    .service(health)
+                    .default_service(web::route().to(not_found));
+            }
+        "#;
+
+        let routes = ActixExtractor.extract_routes(&[parse_code(code)]);
+        assert_eq!(routes.len(), 1);
+
+        let options = ExtractOptions {
+            fallback_path: Some("/{*path}".to_string()),
+            ..Default::default()
+        };
+        let routes = ActixExtractor.extract_routes_with_options(
+            &[parse_code(code)],
+            &options,
+            &mut Diagnostics::new(),
+        );
+        assert_eq!(routes.len(), 2);
+        let fallback = routes.iter().find(|r| r.handler_name == "not_found").unwrap();
+        assert_eq!(fallback.path, "/{*path}");
+        assert_eq!(fallback.method, HttpMethod::Get);
+        assert_eq!(fallback.summary.as_deref(), Some(FALLBACK_SUMMARY));
+        assert_eq!(fallback.parameters[0].name, "path");
+    }
 }
@@ -0,0 +1,207 @@
+//! Type alias expansion for handler return types.
+//!
+//! Production handlers often return an alias such as `ApiResult<User>` defined as
+//! `type ApiResult<T> = Result<Json<T>, ApiError>;`. Extractors only recognize `Result` and
+//! `Json` by name, so [`TypeAliases`] expands an alias (substituting its generic arguments)
+//! before the return type is inspected. Aliases nested in the type are expanded too, so
+//! `Json<Users>` with `type Users = Vec<User>;` is documented as an array.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use syn::visit_mut::VisitMut;
+
+/// `type` aliases declared in the scanned files, keyed by alias name.
+#[derive(Debug, Default)]
+pub(crate) struct TypeAliases {
+    aliases: HashMap<String, syn::ItemType>,
+}
+
+impl TypeAliases {
+    /// Record a `type Name<..> = ..;` item
+    pub fn insert(&mut self, item: &syn::ItemType) {
+        self.aliases.insert(item.ident.to_string(), item.clone());
+    }
+
+    /// Expand every known alias in `ty`, at any depth, substituting the alias's generic
+    /// parameters with the arguments written at the use site.
+    ///
+    /// An alias is never expanded inside its own expansion, so
+    /// `type Result<T> = std::result::Result<T, E>;` stops at the standard `Result` instead
+    /// of expanding forever.
+    pub fn expand<'a>(&self, ty: &'a syn::Type) -> Cow<'a, syn::Type> {
+        let mut expanded = ty.clone();
+        let mut expander = Expand {
+            aliases: self,
+            ancestors: Vec::new(),
+            changed: false,
+        };
+        expander.visit_type_mut(&mut expanded);
+        if expander.changed {
+            Cow::Owned(expanded)
+        } else {
+            Cow::Borrowed(ty)
+        }
+    }
+
+    /// Expand the outermost alias of `ty`, returning the alias name and its expansion
+    fn expand_once(&self, ty: &syn::Type) -> Option<(String, syn::Type)> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        if type_path.qself.is_some() {
+            return None;
+        }
+        // Aliases named like standard types never shadow the fully-qualified originals
+        let first = type_path.path.segments.first()?;
+        if matches!(first.ident.to_string().as_str(), "std" | "core" | "alloc") {
+            return None;
+        }
+
+        let segment = type_path.path.segments.last()?;
+        let name = segment.ident.to_string();
+        let alias = self.aliases.get(&name)?;
+
+        let args: Vec<&syn::Type> = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let mut substitutions = HashMap::new();
+        let params = alias.generics.params.iter().filter_map(|param| match param {
+            syn::GenericParam::Type(type_param) => Some(type_param),
+            _ => None,
+        });
+        for (i, param) in params.enumerate() {
+            let replacement = args.get(i).copied().or(param.default.as_ref());
+            if let Some(replacement) = replacement {
+                substitutions.insert(param.ident.to_string(), replacement.clone());
+            }
+        }
+
+        let mut expanded = (*alias.ty).clone();
+        Substitute(&substitutions).visit_type_mut(&mut expanded);
+        Some((name, expanded))
+    }
+}
+
+/// Expands the aliases of a type and of the types nested in it
+struct Expand<'a> {
+    aliases: &'a TypeAliases,
+    /// Aliases whose expansion is being visited
+    ancestors: Vec<String>,
+    /// Whether any alias was expanded
+    changed: bool,
+}
+
+impl VisitMut for Expand<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        let depth = self.ancestors.len();
+        while let Some((name, expanded)) = self.aliases.expand_once(ty) {
+            if self.ancestors.contains(&name) {
+                break;
+            }
+            self.ancestors.push(name);
+            self.changed = true;
+            *ty = expanded;
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+        self.ancestors.truncate(depth);
+    }
+}
+
+/// Replaces generic parameters of an alias by the types they were instantiated with
+struct Substitute<'a>(&'a HashMap<String, syn::Type>);
+
+impl VisitMut for Substitute<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(type_path) = ty {
+            if type_path.qself.is_none() {
+                if let Some(ident) = type_path.path.get_ident() {
+                    if let Some(replacement) = self.0.get(&ident.to_string()) {
+                        *ty = replacement.clone();
+                        return;
+                    }
+                }
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aliases(code: &str) -> TypeAliases {
+        let file = syn::parse_file(code).unwrap();
+        let mut aliases = TypeAliases::default();
+        for item in &file.items {
+            if let syn::Item::Type(item_type) = item {
+                aliases.insert(item_type);
+            }
+        }
+        aliases
+    }
+
+    fn ty(code: &str) -> syn::Type {
+        syn::parse_str(code).unwrap()
+    }
+
+    #[test]
+    fn test_expand_generic_alias() {
+        let aliases = aliases(
+            r#"
+            type ApiResult<T, E = ApiError> = Result<Json<T>, E>;
+            type UserResult = ApiResult<User>;
+        "#,
+        );
+
+        assert_eq!(
+            *aliases.expand(&ty("UserResult")),
+            ty("Result<Json<User>, ApiError>")
+        );
+        assert_eq!(
+            *aliases.expand(&ty("ApiResult<Vec<User>, String>")),
+            ty("Result<Json<Vec<User>>, String>")
+        );
+        assert_eq!(*aliases.expand(&ty("Json<User>")), ty("Json<User>"));
+    }
+
+    #[test]
+    fn test_expand_nested_aliases() {
+        let aliases = aliases(
+            r#"
+            type Users = Vec<User>;
+            type MaybeUser = Option<User>;
+            type Page<T> = Vec<T>;
+            type Tree = Vec<Tree>;
+        "#,
+        );
+
+        assert_eq!(*aliases.expand(&ty("Json<Users>")), ty("Json<Vec<User>>"));
+        assert_eq!(
+            *aliases.expand(&ty("Result<Json<MaybeUser>, ApiError>")),
+            ty("Result<Json<Option<User>>, ApiError>")
+        );
+        assert_eq!(*aliases.expand(&ty("Json<Page<Users>>")), ty("Json<Vec<Vec<User>>>"));
+        // A recursive alias is expanded once along each path
+        assert_eq!(*aliases.expand(&ty("Json<Tree>")), ty("Json<Vec<Tree>>"));
+    }
+
+    #[test]
+    fn test_expand_result_alias_once() {
+        let aliases = aliases("type Result<T> = std::result::Result<T, AppError>;");
+
+        assert_eq!(
+            *aliases.expand(&ty("Result<Json<User>>")),
+            ty("std::result::Result<Json<User>, AppError>")
+        );
+    }
+}
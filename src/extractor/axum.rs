@@ -1,48 +1,375 @@
+use crate::extractor::aliases::TypeAliases;
+use crate::extractor::functions::{is_entry_point, FunctionIndex};
+use crate::extractor::imports::ImportMap;
 use crate::extractor::{
-    HttpMethod, Parameter, ParameterLocation, RouteExtractor, RouteInfo, TypeInfo,
+    extract_type_info, extractor_allowed, lazy_statics, parse_openapi_attributes, peel_option,
+    raw_extractor_name, report_ignored_bodies, result_arms, AdditionalResponse, ExtractOptions,
+    HttpMethod, ModuleTags, OpenApiAttributes, Parameter, ParameterLocation, RouteExtractor,
+    RouteInfo, TypeInfo, FALLBACK_SUMMARY, PATH_EXTRACTOR_PARAM,
 };
-use crate::parser::ParsedFile;
-use syn::{visit::Visit, Expr, ExprCall, ExprMethodCall, Lit};
+use crate::diagnostics::Diagnostics;
+use crate::parser::{extract_doc_comment, ParsedFile};
+use syn::{visit::Visit, Expr, ExprMethodCall, Lit};
 
 use log::{debug, warn};
+use std::collections::{BTreeMap, HashSet};
 
 /// Axum route extractor
 pub struct AxumExtractor;
 
+/// Crates whose extractors are recognized in strict import mode
+const AXUM_CRATES: &[&str] = &["axum", "axum_extra"];
+
+/// `StatusCode` constants of successful responses and their codes
+const SUCCESS_STATUS_CODES: &[(&str, u16)] = &[
+    ("OK", 200),
+    ("CREATED", 201),
+    ("ACCEPTED", 202),
+    ("NON_AUTHORITATIVE_INFORMATION", 203),
+    ("NO_CONTENT", 204),
+    ("RESET_CONTENT", 205),
+    ("PARTIAL_CONTENT", 206),
+];
+
 impl RouteExtractor for AxumExtractor {
     fn extract_routes(&self, parsed_files: &[ParsedFile]) -> Vec<RouteInfo> {
-        let mut visitor = AxumVisitor::new();
+        self.extract_routes_with_diagnostics(parsed_files, &mut Diagnostics::new())
+    }
+
+    fn extract_routes_with_options(
+        &self,
+        parsed_files: &[ParsedFile],
+        options: &ExtractOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<RouteInfo> {
+        let mut visitor = AxumVisitor::new(options.clone());
         
         // First pass: collect all function signatures from all files
         for parsed_file in parsed_files {
+            visitor.imports.push(ImportMap::from_file(&parsed_file.syntax_tree));
+            visitor.function_index.enter_file(&parsed_file.path);
+            visitor.module_tags.enter(&parsed_file.syntax_tree.attrs);
             visitor.visit_file(&parsed_file.syntax_tree);
+            visitor.module_tags.exit();
         }
+
+        // Apply .nest()/.merge() composition between router-building functions and variables
+        visitor.resolve_router_mounts();
         
         // After collecting routes and functions from all files, analyze handlers
-        visitor.analyze_handlers();
+        visitor.analyze_handlers(diagnostics);
 
         visitor.routes
     }
 }
 
+/// A router mounted into another router via `.nest()` or `.merge()`
+#[derive(Debug, Clone)]
+struct RouterMount {
+    /// Owner of the router the child is mounted into (`None` outside any function)
+    parent: Option<String>,
+    /// Owner of the mounted router
+    child: String,
+    /// Path prefix relative to the parent (empty for `.merge()`)
+    prefix: String,
+}
+
+/// Whether a response tuple element only sets headers (`HeaderMap`, `[(HeaderName, V); N]`,
+/// `AppendHeaders<..>`, `TypedHeader<..>`)
+fn is_header_part(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Array(_) => true,
+        syn::Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| {
+            matches!(
+                segment.ident.to_string().as_str(),
+                "HeaderMap" | "AppendHeaders" | "TypedHeader"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// Media type of the body of an Axum response wrapper: `Json<T>`, `Html<T>` or `Form<T>`
+fn response_media_type(wrapper: &str) -> Option<&'static str> {
+    match wrapper {
+        "Json" => Some("application/json"),
+        "Html" => Some("text/html"),
+        "Form" => Some("application/x-www-form-urlencoded"),
+        _ => None,
+    }
+}
+
+/// Standard rejections of Axum's extractors: extractor, status and what failed to parse.
+///
+/// Bodies are rejected with `400` for a syntax error, `415` for a missing or wrong
+/// `Content-Type` and `422` for content of the wrong shape.
+const EXTRACTOR_REJECTIONS: &[(&str, u16, &str)] = &[
+    ("Json", 400, "JSON body"),
+    ("Json", 415, "JSON body"),
+    ("Json", 422, "JSON body"),
+    ("Form", 415, "form body"),
+    ("Form", 422, "form body"),
+    ("Path", 400, "path parameters"),
+    ("Query", 400, "query string"),
+];
+
+/// Responses Axum sends when the extractors of a handler with the given parameters (and a
+/// required body read by the `body` extractor, `Json` or `Form`) reject a request, one per
+/// status: a handler extracting a JSON body and a `Path<T>` documents a single "Invalid JSON
+/// body or path parameters"
+fn rejection_responses(params: &[Parameter], body: Option<&str>) -> Vec<AdditionalResponse> {
+    let uses = |extractor: &str| match extractor {
+        "Json" | "Form" => body == Some(extractor),
+        "Path" => params.iter().any(|p| p.name == PATH_EXTRACTOR_PARAM),
+        "Query" => params.iter().any(|p| p.location == ParameterLocation::Query),
+        _ => false,
+    };
+
+    let mut by_status: BTreeMap<u16, Vec<&str>> = BTreeMap::new();
+    for &(extractor, status, subject) in EXTRACTOR_REJECTIONS {
+        if uses(extractor) {
+            by_status.entry(status).or_default().push(subject);
+        }
+    }
+    by_status
+        .into_iter()
+        .map(|(status, subjects)| {
+            let subjects = subjects.join(" or ");
+            let description = match status {
+                415 => format!("Missing or unsupported `Content-Type` of the {}", subjects),
+                422 => format!("The {} does not match the expected type", subjects),
+                _ => format!("Invalid {}", subjects),
+            };
+            AdditionalResponse {
+                status,
+                description,
+                body: None,
+            }
+        })
+        .collect()
+}
+
+/// What the extractors of a handler signature read from a request
+struct ExtractedInputs {
+    /// Parameters from `Path<T>` and `Query<T>` extractors
+    parameters: Vec<Parameter>,
+    /// Type of the request body, read by the first `Json<T>` or `Form<T>` extractor
+    body: Option<TypeInfo>,
+    /// Whether the body is required, i.e. not extracted as `Option<Json<T>>`
+    body_required: bool,
+    /// Whether the body is read by `Form<T>`, as URL-encoded form data
+    form_body: bool,
+    /// Types of further body extractors, which are ignored in favour of the first
+    ignored_bodies: Vec<TypeInfo>,
+}
+
+/// Find the success status code a handler returns through a `(StatusCode::CREATED, ..)`
+/// response tuple.
+///
+/// Error tuples such as `(StatusCode::NOT_FOUND, ..)` are ignored, so the first 2xx constant
+/// in tuple-leading position wins.
+fn success_status(block: &syn::Block) -> Option<u16> {
+    let mut finder = StatusFinder(None);
+    finder.visit_block(block);
+    finder.0
+}
+
+/// Finds the first 2xx `StatusCode` constant leading a tuple, see [`success_status`]
+struct StatusFinder(Option<u16>);
+
+impl<'ast> Visit<'ast> for StatusFinder {
+    fn visit_expr_tuple(&mut self, node: &'ast syn::ExprTuple) {
+        if self.0.is_some() {
+            return;
+        }
+        if let Some(Expr::Path(path)) = node.elems.first() {
+            if let Some(constant) = status_code_constant(path) {
+                self.0 = SUCCESS_STATUS_CODES
+                    .iter()
+                    .find(|(name, _)| *name == constant)
+                    .map(|(_, code)| *code);
+            }
+        }
+        syn::visit::visit_expr_tuple(self, node);
+    }
+}
+
+/// The constant named by a `StatusCode::CREATED` path
+fn status_code_constant(path: &syn::ExprPath) -> Option<String> {
+    let mut segments = path.path.segments.iter().rev();
+    let constant = segments.next()?.ident.to_string();
+    segments
+        .next()
+        .is_some_and(|s| s.ident == "StatusCode")
+        .then_some(constant)
+}
+
+/// Synthetic handler name of a closure registered for `method` on `path`, e.g.
+/// `users_id_get_closure` for `.route("/users/:id", get(|| async { .. }))`
+fn closure_handler_name(path: &str, method: &str) -> String {
+    let segments: Vec<String> = path
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let path = if segments.is_empty() {
+        "root".to_string()
+    } else {
+        segments.join("_")
+    };
+    format!("{}_{}_closure", path, method.to_lowercase())
+}
+
+/// Response type of a closure handler without a return type annotation, inferred from the
+/// tail expression of its body.
+///
+/// String literals, `format!(..)`, `Json(..)` of a struct literal or `json!(..)`, and tuples
+/// of those with a `StatusCode` constant are recognized.
+fn tail_response_type(expr: &Expr) -> Option<syn::Type> {
+    match expr {
+        Expr::Async(async_block) => block_tail(&async_block.block).and_then(tail_response_type),
+        Expr::Block(block) => block_tail(&block.block).and_then(tail_response_type),
+        Expr::Paren(paren) => tail_response_type(&paren.expr),
+        Expr::Lit(expr_lit) if matches!(expr_lit.lit, Lit::Str(_)) => {
+            Some(syn::parse_quote!(&'static str))
+        }
+        Expr::Macro(expr_macro) if expr_macro.mac.path.is_ident("format") => {
+            Some(syn::parse_quote!(String))
+        }
+        Expr::Call(call) => {
+            let Expr::Path(func) = &*call.func else {
+                return None;
+            };
+            if func.path.segments.last().is_none_or(|s| s.ident != "Json") {
+                return None;
+            }
+            let inner = json_value_type(call.args.first()?)?;
+            Some(syn::parse_quote!(Json<#inner>))
+        }
+        Expr::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(|elem| match elem {
+                    Expr::Path(path) if status_code_constant(path).is_some() => {
+                        Some(syn::parse_quote!(StatusCode))
+                    }
+                    other => tail_response_type(other),
+                })
+                .collect::<Option<Vec<syn::Type>>>()?;
+            Some(syn::parse_quote!((#(#elems),*)))
+        }
+        _ => None,
+    }
+}
+
+/// Type of the value wrapped in a `Json(..)` response expression
+fn json_value_type(expr: &Expr) -> Option<syn::Type> {
+    match expr {
+        Expr::Struct(expr_struct) => Some(syn::Type::Path(syn::TypePath {
+            qself: None,
+            path: expr_struct.path.clone(),
+        })),
+        Expr::Macro(expr_macro)
+            if expr_macro.mac.path.segments.last().is_some_and(|s| s.ident == "json") =>
+        {
+            Some(syn::parse_quote!(serde_json::Value))
+        }
+        Expr::Lit(expr_lit) if matches!(expr_lit.lit, Lit::Str(_)) => {
+            Some(syn::parse_quote!(String))
+        }
+        _ => None,
+    }
+}
+
+/// The tail expression of a block, if it has one
+fn block_tail(block: &syn::Block) -> Option<&Expr> {
+    match block.stmts.last()? {
+        syn::Stmt::Expr(expr, None) => Some(expr),
+        _ => None,
+    }
+}
+
+/// Type name of a service expression: `ServeDir` for `ServeDir::new("assets")` or
+/// `ServeDir::new("assets").not_found_service(..)`
+fn service_type_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(path_expr) => {
+                let segments = &path_expr.path.segments;
+                let constructor = segments.len().checked_sub(2).map(|i| &segments[i]);
+                constructor
+                    .or(segments.last())
+                    .map(|segment| segment.ident.to_string())
+            }
+            _ => None,
+        },
+        Expr::MethodCall(method_call) => service_type_name(&method_call.receiver),
+        Expr::Path(path_expr) => path_expr.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
 /// Visitor for traversing the AST and finding Axum routes
 struct AxumVisitor {
     routes: Vec<RouteInfo>,
+    /// Router owner of each entry in `routes`, see [`AxumVisitor::current_owner`]
+    route_owners: Vec<Option<String>>,
+    /// Routers mounted into other routers
+    mounts: Vec<RouterMount>,
+    /// Function currently being visited
+    current_fn: Option<String>,
+    /// Router the visited routes belong to: a function name, or `fn::variable` for routers
+    /// bound to a local variable
+    current_owner: Option<String>,
     current_prefix: String,
     functions: std::collections::HashMap<String, syn::Signature>,
+    /// `#[openapi(...)]` overrides declared on handler functions
+    openapi_attrs: std::collections::HashMap<String, OpenApiAttributes>,
+    /// Doc comments of the visited functions
+    handler_docs: std::collections::HashMap<String, String>,
+    /// Imports of each visited file, in visiting order
+    imports: Vec<ImportMap>,
+    /// Index into `imports` of the file declaring each function
+    function_files: std::collections::HashMap<String, usize>,
+    /// Functions declared `pub`
+    public_functions: HashSet<String>,
+    /// Success status code returned by each function, see [`success_status`]
+    response_statuses: std::collections::HashMap<String, u16>,
+    /// Tags inherited from enclosing files and modules
+    module_tags: ModuleTags,
+    /// Module-qualified keys of the visited functions
+    function_index: FunctionIndex,
+    /// `type` aliases, expanded in handler return types
+    type_aliases: TypeAliases,
+    options: ExtractOptions,
 }
 
 impl AxumVisitor {
-    fn new() -> Self {
+    fn new(options: ExtractOptions) -> Self {
         Self {
             routes: Vec::new(),
+            route_owners: Vec::new(),
+            mounts: Vec::new(),
+            current_fn: None,
+            current_owner: None,
             current_prefix: String::new(),
             functions: std::collections::HashMap::new(),
+            openapi_attrs: std::collections::HashMap::new(),
+            handler_docs: std::collections::HashMap::new(),
+            imports: Vec::new(),
+            function_files: std::collections::HashMap::new(),
+            public_functions: HashSet::new(),
+            response_statuses: std::collections::HashMap::new(),
+            module_tags: ModuleTags::default(),
+            function_index: FunctionIndex::default(),
+            type_aliases: TypeAliases::default(),
+            options,
         }
     }
 
     /// Analyze routes with handler information
-    fn analyze_handlers(&mut self) {
+    fn analyze_handlers(&mut self, diagnostics: &mut Diagnostics) {
         debug!(
             "Analyzing handlers. Found {} functions and {} routes",
             self.functions.len(),
@@ -54,32 +381,80 @@ impl AxumVisitor {
             .routes
             .iter()
             .enumerate()
-            .map(|(idx, route)| (idx, route.handler_name.clone()))
+            .map(|(idx, route)| (idx, route.handler_path.clone()))
             .collect();
 
-        for (idx, handler_name) in routes_to_update {
-            if let Some(fn_sig) = self.functions.get(&handler_name) {
-                debug!("Found handler function: {}", handler_name);
-                let (params, request_body) = self.parse_extractors(fn_sig);
-                let response_type = self.parse_response_type(fn_sig);
+        for (idx, handler_path) in routes_to_update {
+            if self.routes[idx].serves_files {
+                continue;
+            }
+            // Resolve the handler as written at the registration to a module-qualified key
+            let handler_key = self
+                .function_index
+                .resolve(&handler_path)
+                .map(str::to_string)
+                .unwrap_or(handler_path);
+            if let Some(attrs) = self.openapi_attrs.get(&handler_key) {
+                attrs.apply_to(&mut self.routes[idx]);
+            }
+            self.routes[idx].doc = self.handler_docs.get(&handler_key).cloned();
+            self.module_tags.apply_to(&handler_key, &mut self.routes[idx]);
+
+            if let Some(fn_sig) = self.functions.get(&handler_key) {
+                debug!("Found handler function: {}", handler_key);
+                self.routes[idx].handler_path = handler_key.clone();
+                self.routes[idx].handler_resolved = true;
+                self.routes[idx].handler_location =
+                    self.function_index.location(&handler_key, fn_sig);
+                let imports = self
+                    .function_files
+                    .get(&handler_key)
+                    .and_then(|&idx| self.imports.get(idx));
+                let inputs = self.parse_extractors(fn_sig, imports);
+                let (response_type, response_media_type) =
+                    self.parse_response_type(fn_sig).unzip();
+                if self.options.document_rejections {
+                    // `Option<Json<T>>` turns a rejection into `None`
+                    let body = (inputs.body.is_some() && inputs.body_required)
+                        .then_some(if inputs.form_body { "Form" } else { "Json" });
+                    self.routes[idx].rejection_responses =
+                        rejection_responses(&inputs.parameters, body);
+                }
 
                 // Merge path parameters from URL with parameters from extractors
                 let mut all_params = self.routes[idx].parameters.clone();
-                all_params.extend(params);
+                all_params.extend(inputs.parameters);
 
                 self.routes[idx].parameters = all_params;
-                self.routes[idx].request_body = request_body;
-                self.routes[idx].response_type = response_type;
+                if let Some(body) = &inputs.body {
+                    let ignored = &inputs.ignored_bodies;
+                    report_ignored_bodies(&self.routes[idx], body, ignored, diagnostics);
+                }
+                // An `#[openapi(request_body = "...")]` override takes precedence
+                if self.routes[idx].request_body.is_none() {
+                    self.routes[idx].request_body = inputs.body;
+                    self.routes[idx].request_body_required = inputs.body_required;
+                    // `Form<T>` reads a URL-encoded body unless `consumes` says otherwise
+                    if inputs.form_body && self.routes[idx].consumes.is_empty() {
+                        self.routes[idx].consumes =
+                            vec!["application/x-www-form-urlencoded".to_string()];
+                    }
+                }
+                // An `#[openapi(response = "...")]` override takes precedence
+                if self.routes[idx].response_type.is_none() {
+                    self.routes[idx].response_type = response_type;
+                }
+                // As does an `#[openapi(produces = "...")]` override
+                if self.routes[idx].produces.is_none() {
+                    self.routes[idx].produces =
+                        response_media_type.flatten().map(str::to_string);
+                }
+                self.routes[idx].response_status =
+                    self.response_statuses.get(&handler_key).copied();
+                self.routes[idx].is_public = self.public_functions.contains(&handler_key);
             } else {
-                // warn!(
-                //     "Unknown handler: {} (available: {:?})",
-                //     handler_name,
-                //     self.functions.keys().collect::<Vec<_>>()
-                // );
-                warn!(
-                    "Unknown handler: {}",
-                    handler_name,
-                );
+                self.function_index
+                    .report_unresolved(&self.routes[idx], &handler_key, diagnostics);
             }
         }
     }
@@ -90,13 +465,19 @@ impl AxumVisitor {
 
         match method_name.as_str() {
             "route" => {
-                if let Some(route_info) = self.parse_route_method(expr, prefix) {
-                    self.routes.push(route_info);
+                for route_info in self.parse_route_method(expr, prefix) {
+                    self.push_route(route_info);
                 }
             }
-            "get" | "post" | "put" | "delete" | "patch" | "head" | "options" => {
+            "get" | "post" | "put" | "delete" | "patch" | "head" | "options" | "trace"
+            | "connect" => {
                 if let Some(route_info) = self.parse_shorthand_method(expr, prefix, &method_name) {
-                    self.routes.push(route_info);
+                    self.push_route(route_info);
+                }
+            }
+            "route_service" | "nest_service" => {
+                if let Some(route_info) = self.parse_service_method(expr, prefix, &method_name) {
+                    self.push_route(route_info);
                 }
             }
             "nest" => {
@@ -107,42 +488,89 @@ impl AxumVisitor {
                     }
                 }
             }
+            "merge" => {
+                // .merge(router) - the merged routes keep the current prefix
+                if let Some(merged_expr) = expr.args.first() {
+                    self.parse_router_expr(merged_expr, prefix.to_string());
+                }
+            }
+            "fallback" => {
+                if let Some(route_info) = self.parse_fallback_method(expr, prefix) {
+                    self.push_route(route_info);
+                }
+            }
             _ => {}
         }
     }
 
-    /// Parse a .route() method call
-    fn parse_route_method(&self, expr: &ExprMethodCall, prefix: &str) -> Option<RouteInfo> {
+    /// Parse a .route() method call into one route per method of its method router
+    fn parse_route_method(&mut self, expr: &ExprMethodCall, prefix: &str) -> Vec<RouteInfo> {
         // .route(path, method_router)
         if expr.args.len() < 2 {
-            return None;
+            return Vec::new();
         }
 
-        let path = self.extract_string_literal(&expr.args[0])?;
+        let Some(path) = self.extract_string_literal(&expr.args[0]) else {
+            return Vec::new();
+        };
         let full_path = self.combine_paths(prefix, &path);
 
-        // Try to extract HTTP method from the second argument
-        // This could be get(handler), post(handler), etc.
-        if let Expr::Call(call_expr) = &expr.args[1] {
-            if let Expr::Path(path_expr) = &*call_expr.func {
-                if let Some(segment) = path_expr.path.segments.last() {
-                    let method_name = segment.ident.to_string();
-                    if let Some(method) = self.parse_http_method(&method_name) {
-                        let handler_name = self.extract_handler_name(call_expr);
-                        let mut route = RouteInfo::new(full_path.clone(), method, handler_name);
-                        route.parameters = self.extract_path_parameters(&full_path);
-                        return Some(route);
+        // The method router is get(handler), post(handler), etc., possibly followed by more
+        // methods as in get(list_users).post(create_user); collect the calls innermost first
+        let mut calls = Vec::new();
+        let mut method_router = &expr.args[1];
+        loop {
+            match method_router {
+                Expr::MethodCall(method_call) => {
+                    calls.push((method_call.method.to_string(), method_call.args.first()));
+                    method_router = &method_call.receiver;
+                }
+                Expr::Call(call_expr) => {
+                    if let Expr::Path(path_expr) = &*call_expr.func {
+                        if let Some(segment) = path_expr.path.segments.last() {
+                            calls.push((segment.ident.to_string(), call_expr.args.first()));
+                        }
                     }
+                    break;
                 }
+                _ => break,
             }
         }
 
-        None
+        calls
+            .into_iter()
+            .rev()
+            .filter_map(|(method_name, arg)| self.method_route(&full_path, &method_name, arg))
+            .collect()
+    }
+
+    /// Route of one method of a method router, e.g. `post(create_user)`; other calls such as
+    /// `.layer(...)` yield none
+    fn method_route(
+        &mut self,
+        full_path: &str,
+        method_name: &str,
+        arg: Option<&Expr>,
+    ) -> Option<RouteInfo> {
+        if let Some(method) = self.parse_http_method(method_name) {
+            let handler_path = match arg {
+                Some(handler) => self.handler_path(handler, full_path, method_name),
+                None => "unknown".to_string(),
+            };
+            let mut route = RouteInfo::for_handler(full_path.to_string(), method, handler_path);
+            route.parameters = self.extract_path_parameters(full_path);
+            return Some(route);
+        }
+        // get_service(ServeDir::new("assets"))
+        let method = method_name
+            .strip_suffix("_service")
+            .and_then(|method| self.parse_http_method(method))?;
+        Some(self.static_route(full_path.to_string(), method, arg?))
     }
 
     /// Parse shorthand methods like .get(), .post(), etc.
     fn parse_shorthand_method(
-        &self,
+        &mut self,
         expr: &ExprMethodCall,
         prefix: &str,
         method_name: &str,
@@ -158,29 +586,80 @@ impl AxumVisitor {
         if let Some(path) = self.extract_string_literal(&expr.args[0]) {
             // .get("/path", handler) style
             let full_path = self.combine_paths(prefix, &path);
-            let handler_name = if expr.args.len() > 1 {
-                self.extract_handler_name_from_expr(&expr.args[1])
+            let handler_path = if expr.args.len() > 1 {
+                self.handler_path(&expr.args[1], &full_path, method_name)
             } else {
                 // It is not a route.
                 return None;
             };
-            let mut route = RouteInfo::new(full_path.clone(), method, handler_name);
+            let mut route = RouteInfo::for_handler(full_path.clone(), method, handler_path);
             route.parameters = self.extract_path_parameters(&full_path);
             Some(route)
         } else {
             // .get(handler) style - path comes from parent context
-            let handler_name = self.extract_handler_name_from_expr(&expr.args[0]);
-            if prefix.len() < 1 {
-                warn!("Ignored handler with 0-length path: {}", handler_name);
+            let handler_path = self.handler_path(&expr.args[0], prefix, method_name);
+            if prefix.is_empty() {
+                warn!("Ignored handler with 0-length path: {}", handler_path);
                 None
             } else {
-                let mut route = RouteInfo::new(prefix.to_string(), method, handler_name);
+                let mut route = RouteInfo::for_handler(prefix.to_string(), method, handler_path);
                 route.parameters = self.extract_path_parameters(prefix);
                 Some(route)
             }
         }
     }
 
+    /// Parse a `.fallback(handler)` call as a catch-all GET route, if fallbacks are documented
+    fn parse_fallback_method(&mut self, expr: &ExprMethodCall, prefix: &str) -> Option<RouteInfo> {
+        let handler = expr.args.first()?;
+        let Some(fallback_path) = self.options.fallback_path.clone() else {
+            debug!("Ignoring fallback handler {}", self.extract_handler_path_from_expr(handler));
+            return None;
+        };
+        let full_path = self.combine_paths(prefix, &fallback_path);
+        let handler_path = self.handler_path(handler, &full_path, "fallback");
+        if handler_path == "unknown" {
+            return None;
+        }
+
+        let mut route = RouteInfo::for_handler(full_path.clone(), HttpMethod::Get, handler_path);
+        route.parameters = self.extract_path_parameters(&full_path);
+        route.summary = Some(FALLBACK_SUMMARY.to_string());
+        Some(route)
+    }
+
+    /// Parse a `.route_service(path, service)` or `.nest_service(path, service)` call.
+    ///
+    /// Services have no handler function to analyze; they are documented as GET routes
+    /// serving files, with a nested service serving every path below its prefix.
+    fn parse_service_method(
+        &self,
+        expr: &ExprMethodCall,
+        prefix: &str,
+        method_name: &str,
+    ) -> Option<RouteInfo> {
+        if expr.args.len() < 2 {
+            return None;
+        }
+
+        let path = self.extract_string_literal(&expr.args[0])?;
+        let mut full_path = self.combine_paths(prefix, &path);
+        if method_name == "nest_service" {
+            full_path = self.combine_paths(&full_path, ":path");
+        }
+        Some(self.static_route(full_path, HttpMethod::Get, &expr.args[1]))
+    }
+
+    /// Create a route served by a service such as `ServeDir::new("assets")`
+    fn static_route(&self, path: String, method: HttpMethod, service: &Expr) -> RouteInfo {
+        let service_name = service_type_name(service).unwrap_or_else(|| "service".to_string());
+        debug!("Route {} is served by {}", path, service_name);
+        let mut route = RouteInfo::new(path.clone(), method, service_name);
+        route.parameters = self.extract_path_parameters(&path);
+        route.serves_files = true;
+        route
+    }
+
     /// Parse a .nest() method call
     fn parse_nest_method(&self, expr: &ExprMethodCall, prefix: &str) -> Option<String> {
         // .nest(path, router)
@@ -193,9 +672,162 @@ impl AxumVisitor {
     }
 
     /// Parse a router expression (could be Router::new() or a variable)
-    fn parse_router_expr(&mut self, _expr: &Expr, _prefix: String) {
-        // The visitor will handle method calls automatically
-        // This method is kept for potential future use with nested routers
+    ///
+    /// Inline `Router::new()...` chains are handled by the visitor itself. Routers built by
+    /// another function (`users_router()`) or bound to a local variable (`user_routes`) are
+    /// recorded as mounts and resolved once all files have been visited.
+    fn parse_router_expr(&mut self, expr: &Expr, prefix: String) {
+        if let Some(child) = self.router_owner_of(expr) {
+            // `let r = r.route(...)` and `r = r.route(...)` extend the same router
+            if prefix.is_empty() && self.current_owner.as_ref() == Some(&child) {
+                return;
+            }
+            debug!("Mounting router {} at '{}'", child, prefix);
+            self.mounts.push(RouterMount {
+                parent: self.current_owner.clone(),
+                child,
+                prefix,
+            });
+        }
+    }
+
+    /// Find the router owner an expression refers to, if it is not an inline router
+    fn router_owner_of(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            // users_router() or users::router(state)
+            Expr::Call(call_expr) => match &*call_expr.func {
+                Expr::Path(path_expr) => {
+                    let segments = &path_expr.path.segments;
+                    let name = segments.last()?.ident.to_string();
+                    // Router::new() is an inline router
+                    if segments.len() > 1 && segments[segments.len() - 2].ident == "Router" {
+                        None
+                    } else {
+                        Some(name)
+                    }
+                }
+                _ => None,
+            },
+            // user_routes
+            Expr::Path(path_expr) => {
+                let name = path_expr.path.get_ident()?.to_string();
+                match &self.current_fn {
+                    Some(fn_name) => Some(format!("{}::{}", fn_name, name)),
+                    None => Some(name),
+                }
+            }
+            // users_router().with_state(state), user_routes.clone()
+            Expr::MethodCall(method_call) => self.router_owner_of(&method_call.receiver),
+            Expr::Paren(paren) => self.router_owner_of(&paren.expr),
+            Expr::Reference(reference) => self.router_owner_of(&reference.expr),
+            _ => None,
+        }
+    }
+
+    /// Record a route together with the router that defines it
+    fn push_route(&mut self, route: RouteInfo) {
+        self.routes.push(route);
+        self.route_owners.push(self.current_owner.clone());
+    }
+
+    /// Re-root routes according to the recorded `.nest()`/`.merge()` mounts.
+    ///
+    /// Routes of a router that is mounted somewhere are emitted once per mount point, under
+    /// the accumulated prefix. Routers that are never mounted are treated as top-level. A
+    /// router bound to a local variable that is never mounted explicitly belongs to the
+    /// enclosing function's router (e.g. it is returned or extended later).
+    fn resolve_router_mounts(&mut self) {
+        let owners: HashSet<String> = self.route_owners.iter().flatten().cloned().collect();
+        let mut known: HashSet<String> = owners.clone();
+        known.extend(self.mounts.iter().filter_map(|m| m.parent.clone()));
+
+        // Ignore mounts of routers we know nothing about (e.g. defined in another crate)
+        let mut mounts: Vec<RouterMount> = self
+            .mounts
+            .iter()
+            .filter_map(|m| {
+                if known.contains(&m.child) {
+                    return Some(m.clone());
+                }
+                // `API.clone()` in a function names the static router `API`, not a local
+                let (_, name) = m.child.split_once("::")?;
+                owners.contains(name).then(|| RouterMount {
+                    child: name.to_string(),
+                    ..m.clone()
+                })
+            })
+            .collect();
+        if mounts.is_empty() {
+            return;
+        }
+
+        let mounted: HashSet<String> = mounts.iter().map(|m| m.child.clone()).collect();
+        let mut enclosing_fns = Vec::new();
+        for owner in &known {
+            if let Some((fn_name, _)) = owner.split_once("::") {
+                if !mounted.contains(owner) {
+                    mounts.push(RouterMount {
+                        parent: Some(fn_name.to_string()),
+                        child: owner.clone(),
+                        prefix: String::new(),
+                    });
+                    enclosing_fns.push(fn_name.to_string());
+                }
+            }
+        }
+        // The enclosing function may define no routes itself, as in
+        // `let app = create_router().with_state(state);`, yet must still be emitted
+        known.extend(enclosing_fns);
+        let mounted: HashSet<String> = mounts.iter().map(|m| m.child.clone()).collect();
+
+        let mut roots: Vec<Option<String>> = vec![None];
+        let mut sorted_known: Vec<&String> = known.iter().collect();
+        sorted_known.sort();
+        roots.extend(
+            sorted_known
+                .into_iter()
+                .filter(|owner| !mounted.contains(*owner))
+                .map(|owner| Some(owner.clone())),
+        );
+
+        let mut resolved = Vec::new();
+        for root in &roots {
+            let mut stack = Vec::new();
+            self.emit_router_routes(root.as_ref(), "", &mounts, &mut stack, &mut resolved);
+        }
+
+        self.route_owners = vec![None; resolved.len()];
+        self.routes = resolved;
+    }
+
+    /// Emit all routes of `owner` and its mounted routers under `prefix`
+    fn emit_router_routes(
+        &self,
+        owner: Option<&String>,
+        prefix: &str,
+        mounts: &[RouterMount],
+        stack: &mut Vec<String>,
+        out: &mut Vec<RouteInfo>,
+    ) {
+        for (route, route_owner) in self.routes.iter().zip(&self.route_owners) {
+            if route_owner.as_ref() == owner {
+                let mut route = route.clone();
+                route.path = self.combine_paths(prefix, &route.path);
+                route.parameters = self.extract_path_parameters(&route.path);
+                out.push(route);
+            }
+        }
+
+        for mount in mounts.iter().filter(|m| m.parent.as_ref() == owner) {
+            if stack.contains(&mount.child) {
+                warn!("Ignoring recursive router mount of {}", mount.child);
+                continue;
+            }
+            stack.push(mount.child.clone());
+            let child_prefix = self.combine_paths(prefix, &mount.prefix);
+            self.emit_router_routes(Some(&mount.child), &child_prefix, mounts, stack, out);
+            stack.pop();
+        }
     }
 
     /// Extract a string literal from an expression
@@ -238,39 +870,88 @@ impl AxumVisitor {
             "patch" => Some(HttpMethod::Patch),
             "head" => Some(HttpMethod::Head),
             "options" => Some(HttpMethod::Options),
+            "trace" => Some(HttpMethod::Trace),
+            "connect" => Some(HttpMethod::Connect),
             _ => None,
         }
     }
 
-    /// Extract handler name from a Call expression
-    fn extract_handler_name(&self, call_expr: &ExprCall) -> String {
-        if let Some(arg) = call_expr.args.first() {
-            self.extract_handler_name_from_expr(arg)
-        } else {
-            "unknown".to_string()
+    /// The handler path of a handler expression registered for `method` on `path`; closures
+    /// are registered as handlers named after their route, see [`closure_handler_name`]
+    fn handler_path(&mut self, handler: &Expr, path: &str, method: &str) -> String {
+        match handler {
+            Expr::Closure(closure) => self.register_closure(closure, path, method),
+            _ => self.extract_handler_path_from_expr(handler),
         }
     }
 
-    /// Extract handler name from any expression
-    fn extract_handler_name_from_expr(&self, expr: &Expr) -> String {
+    /// Record an inline closure handler like a handler function and return its synthetic name.
+    ///
+    /// Typed closure arguments are analyzed as extractors. Without a return type annotation
+    /// the response type is inferred from the tail expression of the body.
+    fn register_closure(&mut self, closure: &syn::ExprClosure, path: &str, method: &str) -> String {
+        let key = closure_handler_name(path, method);
+        debug!("Found closure handler: {}", key);
+
+        let mut sig: syn::Signature = syn::parse_quote!(async fn closure());
+        sig.inputs = closure
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::Pat::Type(pat_type) => Some(syn::FnArg::Typed(pat_type.clone())),
+                _ => None,
+            })
+            .collect();
+        sig.output = match &closure.output {
+            syn::ReturnType::Default => tail_response_type(&closure.body)
+                .map_or(syn::ReturnType::Default, |ty| {
+                    syn::ReturnType::Type(Default::default(), Box::new(ty))
+                }),
+            output => output.clone(),
+        };
+
+        let mut finder = StatusFinder(None);
+        finder.visit_expr(&closure.body);
+        if let Some(status) = finder.0 {
+            self.response_statuses.insert(key.clone(), status);
+        }
+        if let Some(file_idx) = self.imports.len().checked_sub(1) {
+            self.function_files.insert(key.clone(), file_idx);
+        }
+        // A closure is not an item with a visibility; it is as reachable as its router
+        self.public_functions.insert(key.clone());
+        self.functions.insert(key.clone(), sig);
+        key
+    }
+
+    /// Extract the handler path as written (`get_user`, `users::get_user`) from any expression
+    fn extract_handler_path_from_expr(&self, expr: &Expr) -> String {
         match expr {
-            Expr::Path(path_expr) => path_expr
+            Expr::Path(path_expr) if !path_expr.path.segments.is_empty() => path_expr
                 .path
                 .segments
-                .last()
+                .iter()
                 .map(|s| s.ident.to_string())
-                .unwrap_or_else(|| "unknown".to_string()),
+                .collect::<Vec<_>>()
+                .join("::"),
             _ => "unknown".to_string(),
         }
     }
 
-    /// Extract path parameters from a route path (e.g., "/users/:id" -> Parameter{name: "id"})
+    /// Extract path parameters from a route path (e.g., "/users/:id" or "/users/{id}" ->
+    /// Parameter{name: "id"})
     fn extract_path_parameters(&self, path: &str) -> Vec<Parameter> {
         let mut parameters = Vec::new();
 
         for segment in path.split('/') {
-            if segment.starts_with(':') {
-                let param_name = segment.trim_start_matches(':').to_string();
+            // Axum 0.6 `:id`/`*rest` and Axum 0.7+ `{id}`/`{*rest}`
+            let name = segment
+                .strip_prefix('{')
+                .and_then(|s| s.strip_suffix('}'))
+                .map(|s| s.trim_start_matches('*'))
+                .or_else(|| segment.strip_prefix([':', '*']));
+            if let Some(param_name) = name.filter(|name| !name.is_empty()) {
+                let param_name = param_name.to_string();
                 parameters.push(Parameter::new(
                     param_name,
                     ParameterLocation::Path,
@@ -283,8 +964,12 @@ impl AxumVisitor {
         parameters
     }
 
-    /// Parse the response type from a function signature
-    fn parse_response_type(&self, fn_sig: &syn::Signature) -> Option<TypeInfo> {
+    /// Parse the response type from a function signature, with the media type of its response
+    /// wrapper; see [`Self::parse_return_type`]
+    fn parse_response_type(
+        &self,
+        fn_sig: &syn::Signature,
+    ) -> Option<(TypeInfo, Option<&'static str>)> {
         // Get the return type from the function signature
         match &fn_sig.output {
             syn::ReturnType::Default => None,
@@ -296,7 +981,18 @@ impl AxumVisitor {
     }
 
     /// Parse a return type, handling common Axum response patterns
-    fn parse_return_type(&self, ty: &syn::Type) -> Option<TypeInfo> {
+    ///
+    /// The body of a `Json<T>`, `Html<T>` or `Form<T>` wrapper comes with the wrapper's media
+    /// type; other types are returned without one.
+    fn parse_return_type(&self, ty: &syn::Type) -> Option<(TypeInfo, Option<&'static str>)> {
+        // `ApiResult<User>` -> `Result<Json<User>, ApiError>`
+        let ty = &*self.type_aliases.expand(ty);
+
+        // Handle Result<T, E> - recursively parse the Ok type (might be Json<T>)
+        if let Some((ok_ty, _)) = result_arms(ty) {
+            return self.parse_return_type(ok_ty);
+        }
+
         match ty {
             // Handle impl Trait types (e.g., impl IntoResponse)
             syn::Type::ImplTrait(_) => {
@@ -306,64 +1002,46 @@ impl AxumVisitor {
             // Handle reference types (e.g., &'static str)
             syn::Type::Reference(type_ref) => {
                 // Extract the inner type from the reference
-                Some(self.extract_type_info(&type_ref.elem))
+                Some((extract_type_info(&type_ref.elem), None))
             }
             // Handle path types (most common case)
             syn::Type::Path(type_path) => {
-                if let Some(segment) = type_path.path.segments.last() {
-                    let type_name = segment.ident.to_string();
-
-                    // Handle Json<T> response wrapper
-                    if type_name == "Json" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                                return Some(self.extract_type_info(inner_ty));
-                            }
-                        }
-                    }
-
-                    // Handle Result<T, E> - extract the Ok type
-                    if type_name == "Result" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if let Some(syn::GenericArgument::Type(ok_ty)) = args.args.first() {
-                                // Recursively parse the Ok type (might be Json<T>)
-                                return self.parse_return_type(ok_ty);
-                            }
-                        }
-                    }
+                type_path.path.segments.last()?;
 
-                    // Handle tuple types like (StatusCode, Json<T>)
-                    // For now, we'll just return the type as-is
-                    // A more sophisticated implementation could extract Json<T> from tuples
-
-                    // For other types, return the type info
-                    Some(self.extract_type_info(ty))
-                } else {
-                    None
+                // Handle Json<T>, Html<T> and Form<T> response wrappers
+                if let Some((body, media_type)) = self.extract_body_from_type(ty) {
+                    return Some((body, Some(media_type)));
                 }
+
+                // For other types, return the type info
+                Some((extract_type_info(ty), None))
             }
-            // Handle tuple types (e.g., (StatusCode, Json<T>))
+            // Handle tuple types (e.g., (StatusCode, HeaderMap, Json<T>)); the status code
+            // constant comes from the handler body, see `success_status`
             syn::Type::Tuple(tuple) => {
-                // Look for Json<T> in the tuple elements
+                let mut body = None;
                 for elem in &tuple.elems {
-                    if let Some(type_info) = self.extract_json_from_type(elem) {
-                        return Some(type_info);
+                    if is_header_part(elem) {
+                        debug!("Response tuple sets headers, which are not documented yet");
+                    } else if body.is_none() {
+                        body = self.extract_body_from_type(elem);
                     }
                 }
-                None
+                body.map(|(body, media_type)| (body, Some(media_type)))
             }
             _ => None,
         }
     }
 
-    /// Extract Json<T> type from a type expression
-    fn extract_json_from_type(&self, ty: &syn::Type) -> Option<TypeInfo> {
+    /// Extract the `T` of a `Json<T>`, `Html<T>` or `Form<T>` type expression, with the
+    /// media type the wrapper responds with
+    fn extract_body_from_type(&self, ty: &syn::Type) -> Option<(TypeInfo, &'static str)> {
         if let syn::Type::Path(type_path) = ty {
             if let Some(segment) = type_path.path.segments.last() {
-                if segment.ident == "Json" {
+                if let Some(media_type) = response_media_type(&segment.ident.to_string()) {
                     if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
                         if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                            return Some(self.extract_type_info(inner_ty));
+                            return Some((extract_type_info(inner_ty), media_type));
                         }
                     }
                 }
@@ -373,26 +1051,54 @@ impl AxumVisitor {
     }
 
     /// Parse extractors from a function signature
-    fn parse_extractors(&self, fn_sig: &syn::Signature) -> (Vec<Parameter>, Option<TypeInfo>) {
+    fn parse_extractors(
+        &self,
+        fn_sig: &syn::Signature,
+        imports: Option<&ImportMap>,
+    ) -> ExtractedInputs {
         let mut parameters = Vec::new();
         let mut request_body = None;
+        let mut request_body_required = true;
+        let mut form_body = false;
+        let mut ignored_bodies = Vec::new();
 
         for input in &fn_sig.inputs {
             if let syn::FnArg::Typed(pat_type) = input {
+                // Raw extractors carry no typed schema and are deliberately skipped
+                if let Some(raw) = raw_extractor_name(&pat_type.ty) {
+                    debug!("Skipping raw extractor: {}", raw);
+                    continue;
+                }
+
+                // Optional extractors like Option<Json<T>> are unwrapped first
+                let (extractor_ty, is_optional) = peel_option(&pat_type.ty);
+
                 // Extract type information
-                if let Some((extractor_type, inner_type)) = self.parse_extractor_type(&pat_type.ty)
+                if let Some((extractor_type, inner_type)) = self.parse_extractor_type(extractor_ty)
                 {
+                    if !extractor_allowed(extractor_ty, imports, &self.options, AXUM_CRATES) {
+                        debug!("Skipping {} not imported from axum", extractor_type);
+                        continue;
+                    }
+
                     match extractor_type.as_str() {
-                        "Json" => {
-                            // Json<T> is a request body
-                            request_body = Some(inner_type);
+                        "Json" | "Form" => {
+                            // Json<T> and Form<T> are request bodies; only the first one can
+                            // consume it
+                            if request_body.is_none() {
+                                request_body = Some(inner_type);
+                                request_body_required = !is_optional;
+                                form_body = extractor_type == "Form";
+                            } else {
+                                ignored_bodies.push(inner_type);
+                            }
                         }
                         "Path" => {
                             // Path<T> contains path parameters
                             // We'll need to analyze T to extract individual parameters
                             // For now, create a generic path parameter
                             parameters.push(Parameter::new(
-                                "path_params".to_string(),
+                                PATH_EXTRACTOR_PARAM.to_string(),
                                 ParameterLocation::Path,
                                 inner_type,
                                 true,
@@ -413,21 +1119,27 @@ impl AxumVisitor {
             }
         }
 
-        (parameters, request_body)
+        ExtractedInputs {
+            parameters,
+            body: request_body,
+            body_required: request_body_required,
+            form_body,
+            ignored_bodies,
+        }
     }
 
-    /// Parse an extractor type like Json<T>, Path<T>, Query<T>
+    /// Parse an extractor type like Json<T>, Form<T>, Path<T>, Query<T>
     fn parse_extractor_type(&self, ty: &syn::Type) -> Option<(String, TypeInfo)> {
         if let syn::Type::Path(type_path) = ty {
             if let Some(segment) = type_path.path.segments.last() {
                 let extractor_name = segment.ident.to_string();
 
                 // Check if this is a known extractor
-                if matches!(extractor_name.as_str(), "Json" | "Path" | "Query") {
+                if matches!(extractor_name.as_str(), "Json" | "Form" | "Path" | "Query") {
                     // Extract the generic type argument
                     if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
                         if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                            let type_info = self.extract_type_info(inner_ty);
+                            let type_info = extract_type_info(inner_ty);
                             return Some((extractor_name, type_info));
                         }
                     }
@@ -436,43 +1148,6 @@ impl AxumVisitor {
         }
         None
     }
-
-    /// Extract TypeInfo from a syn::Type
-    fn extract_type_info(&self, ty: &syn::Type) -> TypeInfo {
-        match ty {
-            syn::Type::Path(type_path) => {
-                if let Some(segment) = type_path.path.segments.last() {
-                    let type_name = segment.ident.to_string();
-
-                    // Check for Option<T>
-                    if type_name == "Option" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                                let inner_type_info = self.extract_type_info(inner_ty);
-                                return TypeInfo::option(inner_type_info);
-                            }
-                        }
-                    }
-
-                    // Check for Vec<T>
-                    if type_name == "Vec" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
-                                let inner_type_info = self.extract_type_info(inner_ty);
-                                return TypeInfo::vec(inner_type_info);
-                            }
-                        }
-                    }
-
-                    // Simple type
-                    TypeInfo::new(type_name)
-                } else {
-                    TypeInfo::new("unknown".to_string())
-                }
-            }
-            _ => TypeInfo::new("unknown".to_string()),
-        }
-    }
 }
 
 impl<'ast> Visit<'ast> for AxumVisitor {
@@ -483,24 +1158,150 @@ impl<'ast> Visit<'ast> for AxumVisitor {
         // The parse_method_chain will handle the recursion, but we don't call it recursively from here
         if matches!(
             method_name.as_str(),
-            "route" | "get" | "post" | "put" | "delete" | "patch" | "head" | "options" | "nest"
+            "route" | "get" | "post" | "put" | "delete" | "patch" | "head" | "options" | "trace"
+                | "connect" | "route_service" | "nest_service" | "nest" | "merge" | "fallback"
         ) {
             // Process this single method call (not the whole chain)
             self.parse_single_method(node, &self.current_prefix.clone());
         }
 
+        // The method router of .route() has been parsed as a whole; its .post(...) and similar
+        // calls must not be taken for routes of their own
+        if method_name == "route" && node.args.len() >= 2 {
+            self.visit_expr(&node.receiver);
+            self.visit_expr(&node.args[0]);
+            return;
+        }
+
+        // Routes of an inline router passed to .nest() live under the nested prefix
+        if method_name == "nest" && node.args.len() >= 2 {
+            if let Some(nested_prefix) = self.parse_nest_method(node, &self.current_prefix) {
+                self.visit_expr(&node.receiver);
+                self.visit_expr(&node.args[0]);
+                let saved_prefix = std::mem::replace(&mut self.current_prefix, nested_prefix);
+                self.visit_expr(&node.args[1]);
+                self.current_prefix = saved_prefix;
+                return;
+            }
+        }
+
         // Continue visiting child nodes
         syn::visit::visit_expr_method_call(self, node);
     }
 
+    fn visit_local(&mut self, node: &'ast syn::Local) {
+        // Routes built in `let x = Router::new()...` belong to the router bound to `x`
+        let owner = match (&self.current_fn, &node.pat) {
+            (Some(fn_name), syn::Pat::Ident(pat_ident)) => {
+                Some(format!("{}::{}", fn_name, pat_ident.ident))
+            }
+            _ => None,
+        };
+
+        match owner {
+            Some(owner) => {
+                let saved_owner = self.current_owner.replace(owner);
+                // `let api = users_router();` makes `api` an alias of the other router
+                if let Some(init) = &node.init {
+                    self.parse_router_expr(&init.expr, String::new());
+                }
+                syn::visit::visit_local(self, node);
+                self.current_owner = saved_owner;
+            }
+            None => syn::visit::visit_local(self, node),
+        }
+    }
+
+    fn visit_expr_assign(&mut self, node: &'ast syn::ExprAssign) {
+        // Routes added by `router = router.route(...)` belong to the router bound to `router`
+        let owner = match (&self.current_fn, &*node.left) {
+            (Some(fn_name), Expr::Path(path_expr)) => path_expr
+                .path
+                .get_ident()
+                .map(|ident| format!("{}::{}", fn_name, ident)),
+            _ => None,
+        };
+
+        match owner {
+            Some(owner) => {
+                let saved_owner = self.current_owner.replace(owner);
+                self.parse_router_expr(&node.right, String::new());
+                syn::visit::visit_expr_assign(self, node);
+                self.current_owner = saved_owner;
+            }
+            None => syn::visit::visit_expr_assign(self, node),
+        }
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        self.module_tags.enter(&node.attrs);
+        self.function_index.enter_module(&node.ident);
+        syn::visit::visit_item_mod(self, node);
+        self.function_index.exit_module();
+        self.module_tags.exit();
+    }
+
+    fn visit_item_type(&mut self, node: &'ast syn::ItemType) {
+        self.type_aliases.insert(node);
+    }
+
+    fn visit_item_static(&mut self, node: &'ast syn::ItemStatic) {
+        // `static API: Lazy<Router> = Lazy::new(|| Router::new()...)` defines the router `API`
+        let saved_owner = self.current_owner.replace(node.ident.to_string());
+        syn::visit::visit_item_static(self, node);
+        self.current_owner = saved_owner;
+    }
+
+    fn visit_item_const(&mut self, node: &'ast syn::ItemConst) {
+        let saved_owner = self.current_owner.replace(node.ident.to_string());
+        syn::visit::visit_item_const(self, node);
+        self.current_owner = saved_owner;
+    }
+
+    fn visit_item_macro(&mut self, node: &'ast syn::ItemMacro) {
+        for (ident, init) in lazy_statics(&node.mac) {
+            let saved_owner = self.current_owner.replace(ident.to_string());
+            self.visit_expr(&init);
+            self.current_owner = saved_owner;
+        }
+        syn::visit::visit_item_macro(self, node);
+    }
+
     fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
         // Store function signatures for later analysis
         let fn_name = node.sig.ident.to_string();
         debug!("Found function: {}", fn_name);
-        self.functions.insert(fn_name, node.sig.clone());
+        // Entry points only build the router; they can never be handlers. Any other attribute
+        // macro (`#[debug_handler]`, `#[instrument]`, custom ones) cannot be expanded, so the
+        // signature as written is recorded unchanged.
+        if !is_entry_point(node) {
+            let key = self.function_index.insert(&node.sig);
+            let openapi_attrs = parse_openapi_attributes(&node.attrs);
+            if !openapi_attrs.is_empty() {
+                self.openapi_attrs.insert(key.clone(), openapi_attrs);
+            }
+            if let Some(doc) = extract_doc_comment(&node.attrs) {
+                self.handler_docs.insert(key.clone(), doc);
+            }
+            if let Some(file_idx) = self.imports.len().checked_sub(1) {
+                self.function_files.insert(key.clone(), file_idx);
+            }
+            if matches!(node.vis, syn::Visibility::Public(_)) {
+                self.public_functions.insert(key.clone());
+            }
+            if let Some(status) = success_status(&node.block) {
+                self.response_statuses.insert(key.clone(), status);
+            }
+            self.module_tags.record(&key);
+            self.functions.insert(key, node.sig.clone());
+        }
 
-        // Continue visiting child nodes
+        // Continue visiting child nodes, attributing routes to this function's router
+        let saved_fn = self.current_fn.replace(fn_name.clone());
+        let saved_owner = self.current_owner.replace(fn_name);
         syn::visit::visit_item_fn(self, node);
+        self.current_fn = saved_fn;
+        self.current_owner = saved_owner;
     }
 }
 
@@ -604,6 +1405,45 @@ mod tests {
         assert!(routes[0].parameters[0].required);
     }
 
+    #[test]
+    fn test_path_parameter_syntaxes() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            async fn get_post() {}
+            async fn get_comment() {}
+            async fn get_file() {}
+            async fn get_asset() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users/{user_id}/posts/{post_id}", get(get_post))
+                    .route("/posts/:post_id/comments/{id}", get(get_comment))
+                    .route("/files/{*path}", get(get_file))
+                    .route("/assets/*rest", get(get_asset))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        let names = |handler: &str| {
+            routes
+                .iter()
+                .find(|r| r.handler_name == handler)
+                .unwrap()
+                .parameters
+                .iter()
+                .map(|p| p.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("get_post"), vec!["user_id", "post_id"]);
+        assert_eq!(names("get_comment"), vec!["post_id", "id"]);
+        assert_eq!(names("get_file"), vec!["path"]);
+        assert_eq!(names("get_asset"), vec!["rest"]);
+    }
+
     #[test]
     fn test_nested_routes() {
         let code = r#"
@@ -627,12 +1467,13 @@ mod tests {
         let extractor = AxumExtractor;
         let routes = extractor.extract_routes(&[parsed]);
 
-        // Note: This test may not work perfectly due to the complexity of tracking nested routers
-        // The current implementation handles .nest() calls but may not fully resolve router variables
-        // This is a known limitation that would require more sophisticated analysis
+        let mut paths: Vec<_> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/api/users", "/api/users/:id"]);
 
-        // For now, we just verify that routes are extracted
-        assert!(!routes.is_empty());
+        let get_user = routes.iter().find(|r| r.handler_name == "get_user").unwrap();
+        assert_eq!(get_user.parameters.len(), 1);
+        assert_eq!(get_user.parameters[0].name, "id");
     }
 
     #[test]
@@ -882,22 +1723,115 @@ mod tests {
         if let Some(ref response) = routes[0].response_type {
             assert_eq!(response.name, "CreatedUser");
         }
+        assert_eq!(routes[0].response_status, Some(201));
     }
 
     #[test]
-    fn test_vec_response_type() {
+    fn test_tuple_response_with_headers() {
         let code = r#"
-            use axum::{Router, routing::get, Json};
-            use serde::Serialize;
-            
-            #[derive(Serialize)]
-            struct User {
-                id: u32,
-                name: String,
+            use axum::{Router, routing::post, Json, http::{HeaderMap, StatusCode}};
+
+            async fn create_user(Json(user): Json<NewUser>) -> Result<(StatusCode, HeaderMap, Json<User>), AppError> {
+                if user.name.is_empty() {
+                    return Err(AppError::from((StatusCode::BAD_REQUEST, "empty name")));
+                }
+                Ok((StatusCode::ACCEPTED, HeaderMap::new(), Json(User::from(user))))
             }
-            
-            async fn list_users() -> Json<Vec<User>> {
-                Json(vec![])
+
+            async fn export_users() -> ([(HeaderName, &'static str); 1], Json<Vec<User>>) {
+                ([(header::CONTENT_DISPOSITION, "attachment")], Json(Vec::new()))
+            }
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", post(create_user))
+                    .route("/users/export", post(export_users))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 2);
+        let create = routes.iter().find(|r| r.handler_name == "create_user").unwrap();
+        assert_eq!(create.response_type.as_ref().unwrap().name, "User");
+        assert_eq!(create.response_status, Some(202));
+
+        let export = routes.iter().find(|r| r.handler_name == "export_users").unwrap();
+        let response = export.response_type.as_ref().unwrap();
+        assert!(response.is_vec);
+        assert_eq!(response.generic_args[0].name, "User");
+        assert_eq!(export.response_status, None);
+    }
+
+    #[test]
+    fn test_response_wrapper_media_types() {
+        let code = r#"
+            use axum::{Router, routing::get, response::Html, Form, Json};
+
+            async fn index() -> Html<&'static str> {
+                Html("<h1>Hello</h1>")
+            }
+
+            async fn token() -> Result<(StatusCode, Form<Token>), AppError> {
+                todo!()
+            }
+
+            #[openapi(produces = "application/xhtml+xml")]
+            async fn page() -> Html<String> {
+                todo!()
+            }
+
+            async fn user() -> Json<User> {
+                todo!()
+            }
+
+            async fn health() -> &'static str {
+                "OK"
+            }
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/", get(index))
+                    .route("/token", get(token))
+                    .route("/page", get(page))
+                    .route("/user", get(user))
+                    .route("/health", get(health))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let routes = AxumExtractor.extract_routes(&[parsed]);
+        let route = |name: &str| routes.iter().find(|r| r.handler_name == name).unwrap();
+
+        assert_eq!(route("index").produces.as_deref(), Some("text/html"));
+        assert_eq!(route("index").response_type.as_ref().unwrap().name, "str");
+        assert_eq!(
+            route("token").produces.as_deref(),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(route("token").response_type.as_ref().unwrap().name, "Token");
+        // An explicit `produces` wins over the wrapper
+        assert_eq!(route("page").produces.as_deref(), Some("application/xhtml+xml"));
+        assert_eq!(route("user").produces.as_deref(), Some("application/json"));
+        assert_eq!(route("health").produces, None);
+    }
+
+    #[test]
+    fn test_vec_response_type() {
+        let code = r#"
+            use axum::{Router, routing::get, Json};
+            use serde::Serialize;
+            
+            #[derive(Serialize)]
+            struct User {
+                id: u32,
+                name: String,
+            }
+            
+            async fn list_users() -> Json<Vec<User>> {
+                Json(vec![])
             }
             
             fn app() -> Router {
@@ -1004,4 +1938,923 @@ mod tests {
             "health should have response type"
         );
     }
+
+    #[test]
+    fn test_raw_extractors_are_skipped() {
+        let code = r#"
+            use axum::{Router, routing::post, Json};
+            use axum::extract::{OriginalUri, RawQuery};
+
+            async fn search(
+                OriginalUri(uri): OriginalUri,
+                RawQuery(query): RawQuery,
+                Json(payload): Json<SearchRequest>,
+            ) -> &'static str {
+                "ok"
+            }
+
+            fn app() -> Router {
+                Router::new().route("/search", post(search))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 1);
+        assert!(routes[0].parameters.is_empty());
+        assert_eq!(
+            routes[0].request_body.as_ref().map(|b| b.name.as_str()),
+            Some("SearchRequest")
+        );
+    }
+
+    #[test]
+    fn test_optional_json_body() {
+        let code = r#"
+            use axum::{Router, routing::patch, Json};
+
+            async fn merge_settings(body: Option<Json<SettingsPatch>>) -> &'static str {
+                "ok"
+            }
+
+            fn app() -> Router {
+                Router::new().route("/settings", patch(merge_settings))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 1);
+        assert_eq!(
+            routes[0].request_body.as_ref().map(|b| b.name.as_str()),
+            Some("SettingsPatch")
+        );
+        assert!(!routes[0].request_body_required);
+    }
+
+    #[test]
+    fn test_openapi_attribute_overrides() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            #[openapi(operation_id = "listUsers", summary = "List all users", tag = "users")]
+            async fn list_users() {}
+
+            async fn get_user() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", get(list_users))
+                    .route("/users/:id", get(get_user))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 2);
+        let list = routes.iter().find(|r| r.handler_name == "list_users").unwrap();
+        assert_eq!(list.operation_id, Some("listUsers".to_string()));
+        assert_eq!(list.summary, Some("List all users".to_string()));
+        assert_eq!(list.tags, vec!["users"]);
+
+        let get = routes.iter().find(|r| r.handler_name == "get_user").unwrap();
+        assert_eq!(get.operation_id, None);
+        assert!(get.tags.is_empty());
+    }
+
+    #[test]
+    fn test_openapi_type_overrides() {
+        let code = r#"
+            use axum::{Router, routing::post, Json, body::Bytes};
+
+            #[openapi(request_body = "CreateUser", response = "User")]
+            async fn create_user(Json(body): Json<serde_json::Value>) -> impl IntoResponse {}
+
+            #[openapi(request_body = "Vec<Upload>")]
+            async fn upload(body: Bytes) {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", post(create_user))
+                    .route("/uploads", post(upload))
+            }
+        "#;
+
+        let routes = AxumExtractor.extract_routes(&[parse_code(code)]);
+
+        let create = routes.iter().find(|r| r.handler_name == "create_user").unwrap();
+        assert_eq!(create.request_body.as_ref().unwrap().name, "CreateUser");
+        assert!(create.request_body_required);
+        assert_eq!(create.response_type.as_ref().unwrap().name, "User");
+
+        let upload = routes.iter().find(|r| r.handler_name == "upload").unwrap();
+        let body = upload.request_body.as_ref().unwrap();
+        assert!(body.is_vec);
+        assert_eq!(body.generic_args[0].name, "Upload");
+    }
+
+    #[test]
+    fn test_module_level_tags() {
+        let code = r#"
+            //! User management routes.
+            //!
+            //! @tag accounts
+
+            use axum::{Router, routing::{get, delete}};
+
+            #[openapi(tag = "users")]
+            mod users {
+                pub async fn list_users() {}
+
+                #[openapi(tag = "admin")]
+                pub async fn delete_user() {}
+            }
+
+            async fn health() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", get(users::list_users))
+                    .route("/users/:id", delete(users::delete_user))
+                    .route("/health", get(health))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 3);
+        let list = routes.iter().find(|r| r.handler_name == "list_users").unwrap();
+        assert_eq!(list.tags, vec!["accounts", "users"]);
+
+        let delete = routes.iter().find(|r| r.handler_name == "delete_user").unwrap();
+        assert_eq!(delete.tags, vec!["admin"]);
+
+        let health = routes.iter().find(|r| r.handler_name == "health").unwrap();
+        assert_eq!(health.tags, vec!["accounts"]);
+    }
+
+    #[test]
+    fn test_same_named_functions_in_different_modules() {
+        let code = r#"
+            use axum::{Router, routing::get, extract::Path};
+
+            mod users {
+                pub async fn get_user(Path(id): Path<u32>) {}
+            }
+
+            mod db {
+                pub fn get_user() -> Option<String> { None }
+            }
+
+            #[tokio::main]
+            async fn main() {
+                let app = Router::new().route("/users/:id", get(users::get_user));
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].handler_name, "get_user");
+        assert_eq!(routes[0].handler_path, "test::users::get_user");
+        // The handler's `Path<u32>` extractor was analyzed, not the helper's signature
+        assert!(routes[0]
+            .parameters
+            .iter()
+            .any(|p| p.location == ParameterLocation::Path && p.type_info.name == "u32"));
+    }
+
+    #[test]
+    fn test_handlers_under_attribute_macros() {
+        let code = r#"
+            use axum::{Router, routing::post, Json, extract::Path};
+
+            #[my_macro]
+            #[axum_macros::debug_handler]
+            pub async fn create_user(Json(body): Json<CreateUser>) -> Json<User> {
+                todo!()
+            }
+
+            #[tracing::instrument(skip(body), fields(id = %id))]
+            #[my_crate::audited(action = "update")]
+            pub async fn update_user(Path(id): Path<u64>, Json(body): Json<UpdateUser>) {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", post(create_user))
+                    .route("/users/:id", post(update_user))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let mut diagnostics = Diagnostics::new();
+        let routes = extractor.extract_routes_with_diagnostics(&[parsed], &mut diagnostics);
+
+        assert_eq!(routes.len(), 2);
+        assert!(diagnostics.is_empty());
+        let create = routes.iter().find(|r| r.handler_name == "create_user").unwrap();
+        assert_eq!(create.request_body.as_ref().unwrap().name, "CreateUser");
+        assert_eq!(create.response_type.as_ref().unwrap().name, "User");
+        assert!(create.is_public);
+
+        let update = routes.iter().find(|r| r.handler_name == "update_user").unwrap();
+        assert_eq!(update.request_body.as_ref().unwrap().name, "UpdateUser");
+        assert!(update
+            .parameters
+            .iter()
+            .any(|p| p.location == ParameterLocation::Path && p.type_info.name == "u64"));
+    }
+
+    #[test]
+    fn test_routes_under_state_and_middleware_calls() {
+        let code = r#"
+            use axum::{Router, routing::{get, post}, middleware};
+
+            async fn list_users() {}
+            async fn create_user() {}
+            async fn get_user() {}
+            async fn not_found() {}
+
+            fn create_router() -> Router<AppState> {
+                Router::new()
+                    .route("/users", get(list_users))
+                    .route_layer(middleware::from_fn(auth))
+                    .route("/users/new", post(create_user))
+                    .fallback(not_found)
+            }
+
+            async fn main() {
+                let app = create_router()
+                    .route("/users/:id", get(get_user))
+                    .with_state(state)
+                    .layer(TraceLayer::new_for_http());
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let routes = AxumExtractor.extract_routes(&[parsed]);
+
+        let mut paths: Vec<&str> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/users", "/users/:id", "/users/new"]);
+    }
+
+    #[test]
+    fn test_routes_in_static_initializers() {
+        let code = r#"
+            use axum::{Router, routing::get};
+            use once_cell::sync::Lazy;
+
+            async fn list_users() {}
+            async fn health() {}
+            async fn version() {}
+
+            static API: Lazy<Router> = Lazy::new(|| {
+                Router::new().route("/users", get(list_users))
+            });
+
+            const HEALTH: fn() -> Router = || Router::new().route("/health", get(health));
+
+            lazy_static! {
+                static ref META: Router = Router::new().route("/version", get(version));
+            }
+
+            async fn main() {
+                let app = Router::new().nest("/api", API.clone()).merge(HEALTH());
+            }
+        "#;
+
+        let routes = AxumExtractor.extract_routes(&[parse_code(code)]);
+
+        let mut paths: Vec<&str> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/api/users", "/health", "/version"]);
+    }
+
+    #[test]
+    fn test_chained_method_router() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            async fn list_users() {}
+            async fn create_user() {}
+            async fn get_user() {}
+            async fn delete_user() {}
+
+            fn app() -> Router {
+                let users = Router::new()
+                    .route("/", get(list_users).post(create_user))
+                    .route("/:id", get(get_user).layer(TraceLayer::new()).delete(delete_user));
+                Router::new().nest("/users", users)
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let routes = AxumExtractor.extract_routes(&[parsed]);
+
+        let mut found: Vec<String> = routes
+            .iter()
+            .map(|r| format!("{} {} {}", r.method_str(), r.path, r.handler_name))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                "DELETE /users/:id delete_user",
+                "GET /users list_users",
+                "GET /users/:id get_user",
+                "POST /users create_user",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_handler_doc_comment_is_captured() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            /// List users.
+            ///
+            /// # Returns
+            ///
+            /// All registered users
+            async fn list_users() {}
+
+            async fn health() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", get(list_users))
+                    .route("/health", get(health))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let routes = AxumExtractor.extract_routes(&[parsed]);
+
+        let list = routes.iter().find(|r| r.handler_name == "list_users").unwrap();
+        assert_eq!(
+            list.doc.as_deref(),
+            Some("List users.\n\n# Returns\n\nAll registered users")
+        );
+        let health = routes.iter().find(|r| r.handler_name == "health").unwrap();
+        assert!(health.doc.is_none());
+    }
+
+    #[test]
+    fn test_static_file_services() {
+        let code = r#"
+            use axum::{Router, routing::{get, get_service}};
+            use tower_http::services::{ServeDir, ServeFile};
+
+            async fn health() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/health", get(health))
+                    .route("/", get_service(ServeFile::new("index.html")))
+                    .route_service("/favicon.ico", ServeFile::new("favicon.ico"))
+                    .nest_service("/assets", ServeDir::new("assets").precompressed_gzip())
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let mut diagnostics = Diagnostics::new();
+        let routes = extractor.extract_routes_with_options(
+            &[parsed],
+            &ExtractOptions::default(),
+            &mut diagnostics,
+        );
+
+        assert_eq!(routes.len(), 4);
+        assert!(diagnostics.is_empty());
+
+        let index = routes.iter().find(|r| r.path == "/").unwrap();
+        assert!(index.serves_files);
+        assert_eq!(index.method, HttpMethod::Get);
+        assert_eq!(index.handler_name, "ServeFile");
+
+        let favicon = routes.iter().find(|r| r.path == "/favicon.ico").unwrap();
+        assert!(favicon.serves_files);
+
+        let assets = routes.iter().find(|r| r.path == "/assets/:path").unwrap();
+        assert!(assets.serves_files);
+        assert_eq!(assets.handler_name, "ServeDir");
+        assert_eq!(assets.parameters[0].name, "path");
+
+        let health = routes.iter().find(|r| r.path == "/health").unwrap();
+        assert!(!health.serves_files);
+    }
+
+    #[test]
+    fn test_response_through_result_alias() {
+        let code = r#"
+            use axum::{Router, routing::get, Json};
+
+            pub type ApiResult<T> = Result<Json<T>, ApiError>;
+
+            async fn get_user() -> ApiResult<User> { todo!() }
+
+            fn app() -> Router {
+                Router::new().route("/users/:id", get(get_user))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 1);
+        let response = routes[0].response_type.as_ref().unwrap();
+        assert_eq!(response.name, "User");
+    }
+
+    #[test]
+    fn test_response_through_collection_alias() {
+        let code = r#"
+            use axum::{Router, routing::get, Json};
+
+            pub type Users = Vec<User>;
+            pub type MaybeUser = Option<User>;
+
+            async fn list_users() -> Json<Users> { todo!() }
+            async fn find_user() -> Result<Json<MaybeUser>, ApiError> { todo!() }
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", get(list_users))
+                    .route("/users/find", get(find_user))
+            }
+        "#;
+
+        let routes = AxumExtractor.extract_routes(&[parse_code(code)]);
+        let response = |handler: &str| {
+            let route = routes.iter().find(|r| r.handler_name == handler).unwrap();
+            route.response_type.clone().unwrap()
+        };
+        let list = response("list_users");
+        assert!(list.is_vec);
+        assert_eq!(list.name, "User");
+        let find = response("find_user");
+        assert!(find.is_option);
+        assert_eq!(find.name, "User");
+    }
+
+    #[test]
+    fn test_strict_imports_skip_foreign_extractors() {
+        let code = r#"
+            use axum::{Router, routing::post, extract::Path};
+            use crate::wrappers::Json;
+
+            async fn create_user(Path(id): Path<u32>, Json(user): Json<User>) {}
+
+            fn app() -> Router {
+                Router::new().route("/users/:id", post(create_user))
+            }
+        "#;
+
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parse_code(code)]);
+        assert!(routes[0].request_body.is_some());
+
+        let options = ExtractOptions {
+            strict_imports: true,
+            ..Default::default()
+        };
+        let routes = extractor.extract_routes_with_options(
+            &[parse_code(code)],
+            &options,
+            &mut Diagnostics::new(),
+        );
+        assert_eq!(routes.len(), 1);
+        assert!(routes[0].request_body.is_none());
+        assert!(routes[0]
+            .parameters
+            .iter()
+            .any(|p| p.location == ParameterLocation::Path));
+    }
+
+    #[test]
+    fn test_unresolved_handler_suggests_similar_functions() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            pub async fn get_users() {}
+
+            fn app() -> Router {
+                Router::new().route("/users/:id", get(get_user))
+            }
+        "#;
+
+        let mut diagnostics = Diagnostics::new();
+        AxumExtractor.extract_routes_with_diagnostics(&[parse_code(code)], &mut diagnostics);
+
+        let diagnostic = diagnostics.iter().next().unwrap();
+        assert_eq!(diagnostic.kind, crate::diagnostics::DiagnosticKind::UnresolvedHandler);
+        assert_eq!(diagnostic.suggestions, vec!["test::get_users"]);
+        assert_eq!(
+            diagnostic.message,
+            "Unknown handler: get_user (GET /users/:id); did you mean test::get_users?"
+        );
+    }
+
+    #[test]
+    fn test_closure_handlers() {
+        let code = r#"
+            use axum::{Router, routing::{get, post}, http::StatusCode, Json};
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/ping", get(|| async { "pong" }))
+                    .route(
+                        "/users/:id",
+                        post(|Json(body): Json<CreateUser>| async move {
+                            (StatusCode::CREATED, Json(User { id: 1, name: body.name }))
+                        }),
+                    )
+            }
+        "#;
+
+        let mut diagnostics = Diagnostics::new();
+        let routes =
+            AxumExtractor.extract_routes_with_diagnostics(&[parse_code(code)], &mut diagnostics);
+        assert!(diagnostics.is_empty());
+        assert_eq!(routes.len(), 2);
+
+        let ping = routes.iter().find(|r| r.path == "/ping").unwrap();
+        assert_eq!(ping.handler_name, "ping_get_closure");
+        assert_eq!(ping.response_type.as_ref().unwrap().name, "str");
+
+        let create = routes.iter().find(|r| r.path == "/users/:id").unwrap();
+        assert_eq!(create.handler_name, "users_id_post_closure");
+        assert_eq!(create.request_body.as_ref().unwrap().name, "CreateUser");
+        assert_eq!(create.response_type.as_ref().unwrap().name, "User");
+        assert_eq!(create.response_status, Some(201));
+        assert!(create.is_public);
+    }
+
+    #[test]
+    fn test_first_request_body_is_kept() {
+        let code = r#"
+            use axum::{Router, routing::post, extract::{Extension, State}, Json};
+
+            async fn create_user(
+                State(state): State<AppState>,
+                Extension(user): Extension<CurrentUser>,
+                Json(body): Json<CreateUser>,
+                Json(extra): Json<Metadata>,
+            ) {}
+
+            fn app() -> Router {
+                Router::new().route("/users", post(create_user))
+            }
+        "#;
+
+        let mut diagnostics = Diagnostics::new();
+        let routes =
+            AxumExtractor.extract_routes_with_diagnostics(&[parse_code(code)], &mut diagnostics);
+
+        assert_eq!(routes[0].request_body.as_ref().unwrap().name, "CreateUser");
+        assert!(routes[0].parameters.is_empty());
+        let diagnostic = diagnostics.iter().next().unwrap();
+        assert_eq!(
+            diagnostic.kind,
+            crate::diagnostics::DiagnosticKind::MultipleRequestBodies
+        );
+        assert_eq!(
+            diagnostic.message,
+            "Handler test::create_user (POST /users) declares several request bodies; \
+             documenting CreateUser, ignoring Metadata"
+        );
+    }
+
+    #[test]
+    fn test_fallback_documented_on_request() {
+        let code = r#"
+            use axum::{Router, routing::get, Json};
+
+            async fn list_users() {}
+            async fn not_found() -> Json<ErrorBody> {
+                todo!()
+            }
+
+            fn app() -> Router {
+                let api = Router::new()
+                    .route("/users", get(list_users))
+                    .fallback(not_found);
+                Router::new().nest("/api", api)
+            }
+        "#;
+
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parse_code(code)]);
+        assert_eq!(routes.len(), 1);
+
+        let options = ExtractOptions {
+            fallback_path: Some("/{*path}".to_string()),
+            ..Default::default()
+        };
+        let routes = extractor.extract_routes_with_options(
+            &[parse_code(code)],
+            &options,
+            &mut Diagnostics::new(),
+        );
+        assert_eq!(routes.len(), 2);
+        let fallback = routes.iter().find(|r| r.handler_name == "not_found").unwrap();
+        assert_eq!(fallback.path, "/api/{*path}");
+        assert_eq!(fallback.method, HttpMethod::Get);
+        assert_eq!(fallback.summary.as_deref(), Some(FALLBACK_SUMMARY));
+        assert_eq!(fallback.response_type.as_ref().unwrap().name, "ErrorBody");
+        assert_eq!(fallback.parameters[0].name, "path");
+    }
+
+    #[test]
+    fn test_extractor_rejections() {
+        let code = r#"
+            use axum::{Router, routing::{get, post}, extract::{Path, Query}, Form, Json};
+
+            pub async fn create_post(Path(user_id): Path<u32>, Json(post): Json<Post>) {}
+            pub async fn subscribe(Form(signup): Form<Signup>) {}
+            pub async fn search(Query(params): Query<Search>) {}
+            pub async fn upsert(body: Option<Json<Post>>) {}
+            pub async fn health() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users/:user_id/posts", post(create_post))
+                    .route("/subscribe", post(subscribe))
+                    .route("/search", get(search))
+                    .route("/upsert", post(upsert))
+                    .route("/health", get(health))
+            }
+        "#;
+
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parse_code(code)]);
+        assert!(routes.iter().all(|r| r.rejection_responses.is_empty()));
+
+        let options = ExtractOptions {
+            document_rejections: true,
+            ..Default::default()
+        };
+        let routes = extractor.extract_routes_with_options(
+            &[parse_code(code)],
+            &options,
+            &mut Diagnostics::new(),
+        );
+        let rejections = |handler: &str| {
+            let route = routes.iter().find(|r| r.handler_name == handler).unwrap();
+            route
+                .rejection_responses
+                .iter()
+                .map(|r| (r.status, r.description.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rejections("create_post"),
+            vec![
+                (400, "Invalid JSON body or path parameters".to_string()),
+                (415, "Missing or unsupported `Content-Type` of the JSON body".to_string()),
+                (422, "The JSON body does not match the expected type".to_string()),
+            ]
+        );
+        assert_eq!(
+            rejections("subscribe"),
+            vec![
+                (415, "Missing or unsupported `Content-Type` of the form body".to_string()),
+                (422, "The form body does not match the expected type".to_string()),
+            ]
+        );
+        let subscribe = routes.iter().find(|r| r.handler_name == "subscribe").unwrap();
+        assert_eq!(subscribe.request_body.as_ref().unwrap().name, "Signup");
+        assert_eq!(subscribe.consumes, vec!["application/x-www-form-urlencoded"]);
+        assert_eq!(rejections("search"), vec![(400, "Invalid query string".to_string())]);
+        assert!(rejections("upsert").is_empty());
+        assert!(rejections("health").is_empty());
+    }
+
+    #[test]
+    fn test_router_built_across_statements() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            async fn list_users() {}
+            async fn get_user() {}
+            async fn list_posts() {}
+            async fn health() {}
+
+            fn posts_router() -> Router {
+                Router::new().route("/posts", get(list_posts))
+            }
+
+            const fn base() -> Router {
+                Router::new().route("/health", get(health))
+            }
+
+            fn app() -> Router {
+                let mut api = Router::new();
+                api = api.route("/users", get(list_users));
+                let api = api.route("/users/:id", get(get_user));
+                api = api.merge(posts_router());
+                base().nest("/api", api)
+            }
+        "#;
+
+        let routes = AxumExtractor.extract_routes(&[parse_code(code)]);
+        let mut paths: Vec<&str> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec!["/api/posts", "/api/users", "/api/users/:id", "/health"]
+        );
+    }
+
+    #[test]
+    fn test_handler_visibility() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            pub async fn list_users() {}
+            pub(crate) async fn get_stats() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", get(list_users))
+                    .route("/stats", get(get_stats))
+                    .route("/missing", get(missing))
+            }
+        "#;
+
+        let routes = AxumExtractor.extract_routes(&[parse_code(code)]);
+        let is_public = |handler: &str| {
+            routes
+                .iter()
+                .find(|r| r.handler_name == handler)
+                .unwrap()
+                .is_public
+        };
+
+        assert!(is_public("list_users"));
+        assert!(!is_public("get_stats"));
+        // Unknown handlers are kept
+        assert!(is_public("missing"));
+    }
+
+    #[test]
+    fn test_merge_router_function() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            async fn list_users() {}
+            async fn list_posts() {}
+            async fn health() {}
+
+            fn users_router() -> Router {
+                Router::new().route("/users", get(list_users))
+            }
+
+            fn posts_router() -> Router {
+                Router::new().route("/posts", get(list_posts))
+            }
+
+            fn api_router() -> Router {
+                Router::new()
+                    .merge(users_router())
+                    .merge(posts_router())
+            }
+
+            fn app() -> Router {
+                Router::new()
+                    .nest("/api", api_router())
+                    .route("/health", get(health))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        let mut paths: Vec<_> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/api/posts", "/api/users", "/health"]);
+    }
+
+    #[test]
+    fn test_merge_router_variable() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            async fn list_users() {}
+            async fn get_user() {}
+            async fn health() {}
+
+            fn app() -> Router {
+                let user_routes = Router::new()
+                    .route("/users", get(list_users))
+                    .route("/users/:id", get(get_user));
+
+                let api = Router::new().merge(user_routes);
+
+                Router::new()
+                    .nest("/v1", api)
+                    .merge(Router::new().route("/health", get(health)))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        let mut paths: Vec<_> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/health", "/v1/users", "/v1/users/:id"]);
+    }
+
+    #[test]
+    fn test_inline_nested_router() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            async fn list_users() {}
+
+            fn app() -> Router {
+                Router::new().nest("/api", Router::new().route("/users", get(list_users)))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = AxumExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path, "/api/users");
+    }
+
+    #[test]
+    fn test_conditional_route_registration() {
+        let code = r#"
+            use axum::{Router, routing::get};
+
+            async fn list_users() {}
+            async fn admin_panel() {}
+            async fn legacy_users() {}
+            async fn beta() {}
+            async fn metrics() {}
+            async fn health() {}
+
+            fn api_router(config: &Config) -> Router {
+                let router = Router::new().route("/users", get(list_users));
+                if config.admin {
+                    return router.route("/admin", get(admin_panel));
+                }
+                match config.mode {
+                    Mode::Legacy => router.route("/legacy/users", get(legacy_users)),
+                    _ => router.route("/beta", get(beta)),
+                }
+            }
+
+            fn health_routes() -> Result<impl Into<Router>, Error> {
+                let routes = Router::new().route("/health", get(health));
+                Ok(routes)
+            }
+
+            fn app(config: Config) -> Result<Router, Error> {
+                let mut app = Router::new().merge(health_routes()?);
+                if cfg!(feature = "metrics") {
+                    app = app.route("/metrics", get(metrics));
+                }
+                Ok(app.nest("/api", api_router(&config)))
+            }
+        "#;
+
+        let routes = AxumExtractor.extract_routes(&[parse_code(code)]);
+        let mut paths: Vec<&str> = routes.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                "/api/admin",
+                "/api/beta",
+                "/api/legacy/users",
+                "/api/users",
+                "/health",
+                "/metrics",
+            ]
+        );
+        assert!(routes.iter().all(|route| route.handler_resolved));
+    }
 }
@@ -0,0 +1,279 @@
+//! Module-aware function indexing.
+//!
+//! [`FunctionIndex`] keys every function of the scanned files by its module path
+//! (`handlers::users::get_user`) and resolves the handler path written at a route registration
+//! to the most plausible of the functions sharing its name, so same-named functions in different
+//! modules never shadow each other.
+
+use crate::diagnostics::{DiagnosticKind, Diagnostics};
+use crate::extractor::{RouteInfo, SourceLocation};
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use syn::spanned::Spanned;
+
+/// Functions of the scanned files, keyed by module path.
+#[derive(Debug, Default)]
+pub(crate) struct FunctionIndex {
+    /// File of the items being visited
+    file: PathBuf,
+    /// Module path of the items being visited
+    module: Vec<String>,
+    /// Function name -> functions declared with that name, in visiting order
+    by_name: HashMap<String, Vec<IndexedFunction>>,
+}
+
+/// A function known to the index
+#[derive(Debug)]
+struct IndexedFunction {
+    /// Module-qualified path, e.g. `handlers::users::get_user`
+    key: String,
+    /// Whether the function is `async`, as framework handlers practically always are
+    is_async: bool,
+    /// File declaring the function
+    file: PathBuf,
+}
+
+impl FunctionIndex {
+    /// Start visiting the items of a file
+    pub fn enter_file(&mut self, path: &Path) {
+        self.file = path.to_path_buf();
+        self.module = file_module_path(path);
+    }
+
+    /// Start visiting the items of an inline module
+    pub fn enter_module(&mut self, name: &syn::Ident) {
+        self.module.push(name.to_string());
+    }
+
+    /// Leave the innermost inline module
+    pub fn exit_module(&mut self) {
+        self.module.pop();
+    }
+
+    /// Record a function declared at the current position and return its key.
+    pub fn insert(&mut self, sig: &syn::Signature) -> String {
+        let name = sig.ident.to_string();
+        let key = self
+            .module
+            .iter()
+            .chain(std::iter::once(&name))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("::");
+        self.by_name.entry(name).or_default().push(IndexedFunction {
+            key: key.clone(),
+            is_async: sig.asyncness.is_some(),
+            file: self.file.clone(),
+        });
+        key
+    }
+
+    /// Where the signature `sig` of the function with key `key` is written, if the function
+    /// is known and the signature carries source positions
+    pub fn location(&self, key: &str, sig: &syn::Signature) -> Option<SourceLocation> {
+        let name = key.rsplit("::").next()?;
+        let function = self.by_name.get(name)?.iter().find(|f| f.key == key)?;
+        let span = sig.span();
+        (span.start().line > 0).then(|| SourceLocation {
+            file: function.file.clone(),
+            start_line: span.start().line,
+            start_column: span.start().column,
+            end_line: span.end().line,
+            end_column: span.end().column,
+        })
+    }
+
+    /// Resolve a handler path as written at a route registration (`get_user`,
+    /// `users::get_user`, `crate::handlers::users::get_user`) to the key of a known function.
+    ///
+    /// Functions whose module path ends with the written qualifier are preferred, then
+    /// `async` functions, then the first one declared.
+    pub fn resolve(&self, handler_path: &str) -> Option<&str> {
+        let segments: Vec<&str> = handler_path
+            .split("::")
+            .filter(|s| !matches!(*s, "crate" | "self" | "super"))
+            .collect();
+        let name = segments.last()?;
+        let candidates = self.by_name.get(*name)?;
+
+        let suffix = format!("::{}", segments.join("::"));
+        let qualified: Vec<&IndexedFunction> = candidates
+            .iter()
+            .filter(|f| f.key == segments.join("::") || f.key.ends_with(&suffix))
+            .collect();
+        let pool: Vec<&IndexedFunction> = if qualified.is_empty() {
+            candidates.iter().collect()
+        } else {
+            qualified
+        };
+
+        if pool.len() > 1 {
+            debug!(
+                "Handler {} matches {} functions: {:?}",
+                handler_path,
+                pool.len(),
+                pool.iter().map(|f| &f.key).collect::<Vec<_>>()
+            );
+        }
+        pool.iter()
+            .find(|f| f.is_async)
+            .or_else(|| pool.first())
+            .map(|f| f.key.as_str())
+    }
+
+    /// Keys of the functions named most like the handler of `handler_path`, nearest first.
+    ///
+    /// Names within an edit distance of a third of the handler name's length (at least one)
+    /// are considered; at most three keys are returned.
+    pub fn similar(&self, handler_path: &str) -> Vec<&str> {
+        let name = handler_path.rsplit("::").next().unwrap_or(handler_path);
+        let max_distance = (name.chars().count() / 3).max(1);
+
+        let mut candidates: Vec<(usize, &str)> = self
+            .by_name
+            .iter()
+            .filter_map(|(other, functions)| {
+                let distance = edit_distance(name, other);
+                (distance <= max_distance).then_some((distance, functions))
+            })
+            .flat_map(|(distance, functions)| {
+                functions.iter().map(move |f| (distance, f.key.as_str()))
+            })
+            .collect();
+        candidates.sort();
+        candidates.into_iter().take(3).map(|(_, key)| key).collect()
+    }
+
+    /// Record that the handler of `route`, resolved to `handler_key`, is not a known function,
+    /// suggesting similarly named functions
+    pub fn report_unresolved(
+        &self,
+        route: &RouteInfo,
+        handler_key: &str,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let suggestions: Vec<String> =
+            self.similar(handler_key).into_iter().map(str::to_string).collect();
+        let mut message = format!(
+            "Unknown handler: {} ({} {})",
+            handler_key,
+            route.method_str(),
+            route.path
+        );
+        if !suggestions.is_empty() {
+            message.push_str(&format!("; did you mean {}?", suggestions.join(", ")));
+        }
+        warn!("{}", message);
+        diagnostics.push_with_suggestions(DiagnosticKind::UnresolvedHandler, message, suggestions);
+    }
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Module path of a source file relative to its crate's `src` directory.
+///
+/// `src/handlers/users.rs` and `src/handlers/users/mod.rs` are `handlers::users`; crate roots
+/// (`lib.rs`, `main.rs`) are the empty path.
+pub(crate) fn file_module_path(path: &Path) -> Vec<String> {
+    let components: Vec<String> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    let start = components
+        .iter()
+        .rposition(|c| c == "src")
+        .map_or(components.len().saturating_sub(1), |i| i + 1);
+
+    let mut module: Vec<String> = components[start..].to_vec();
+    if let Some(file) = module.pop() {
+        let stem = file.strip_suffix(".rs").unwrap_or(&file);
+        if !matches!(stem, "lib" | "main" | "mod") {
+            module.push(stem.to_string());
+        }
+    }
+    module
+}
+
+/// Whether a function is a program or test entry point rather than a possible handler:
+/// `main`, or a function marked `#[tokio::main]`, `#[actix_web::main]` or `#[test]`
+pub(crate) fn is_entry_point(item_fn: &syn::ItemFn) -> bool {
+    item_fn.sig.ident == "main"
+        || item_fn.attrs.iter().any(|attr| {
+            let path = attr.path();
+            path.is_ident("test")
+                || path.segments.last().is_some_and(|s| s.ident == "main" || s.ident == "test")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sig(code: &str) -> syn::Signature {
+        syn::parse_str::<syn::ItemFn>(code).unwrap().sig
+    }
+
+    #[test]
+    fn test_file_module_path() {
+        assert!(file_module_path(Path::new("/work/app/src/main.rs")).is_empty());
+        assert_eq!(
+            file_module_path(Path::new("/work/app/src/handlers/users.rs")),
+            vec!["handlers", "users"]
+        );
+        assert_eq!(
+            file_module_path(Path::new("src/handlers/mod.rs")),
+            vec!["handlers"]
+        );
+        assert_eq!(file_module_path(Path::new("test.rs")), vec!["test"]);
+    }
+
+    #[test]
+    fn test_resolve_prefers_qualified_then_async() {
+        let mut index = FunctionIndex::default();
+        index.enter_file(Path::new("src/main.rs"));
+        index.insert(&sig("fn get_user() {}"));
+        index.enter_file(Path::new("src/handlers/users.rs"));
+        index.insert(&sig("async fn get_user() {}"));
+        index.enter_file(Path::new("src/admin.rs"));
+        index.insert(&sig("async fn get_user() {}"));
+
+        assert_eq!(index.resolve("get_user"), Some("handlers::users::get_user"));
+        assert_eq!(index.resolve("admin::get_user"), Some("admin::get_user"));
+        assert_eq!(
+            index.resolve("crate::handlers::users::get_user"),
+            Some("handlers::users::get_user")
+        );
+        assert_eq!(index.resolve("list_users"), None);
+    }
+
+    #[test]
+    fn test_similar_functions() {
+        let mut index = FunctionIndex::default();
+        index.enter_file(Path::new("src/handlers.rs"));
+        index.insert(&sig("async fn get_users() {}"));
+        index.insert(&sig("async fn get_user_posts() {}"));
+        index.insert(&sig("async fn delete_user() {}"));
+
+        assert_eq!(index.similar("get_user"), vec!["handlers::get_users"]);
+        assert_eq!(index.similar("handlers::gte_users"), vec!["handlers::get_users"]);
+        assert!(index.similar("health").is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
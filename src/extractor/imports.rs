@@ -0,0 +1,153 @@
+//! Per-file `use` import tracking.
+//!
+//! Extractors match handler argument types by their last path segment (`Json`, `Path`, ...),
+//! which misclassifies custom wrappers sharing those names. [`ImportMap`] resolves a type path
+//! written in a file to the fully-qualified path it was imported from, so extractors can check
+//! that `Json` really is the framework's `Json`.
+
+use std::collections::HashMap;
+use syn::UseTree;
+
+/// The `use` imports of a single file.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImportMap {
+    /// Local name (or alias) -> fully-qualified path segments
+    names: HashMap<String, Vec<String>>,
+    /// Paths imported with a glob (`use axum::extract::*;`)
+    globs: Vec<Vec<String>>,
+}
+
+impl ImportMap {
+    /// Collect all top-level `use` items of a file
+    pub fn from_file(file: &syn::File) -> Self {
+        let mut imports = Self::default();
+        for item in &file.items {
+            if let syn::Item::Use(item_use) = item {
+                imports.collect(&item_use.tree, Vec::new());
+            }
+        }
+        imports
+    }
+
+    /// Recursively record the names introduced by a use tree
+    fn collect(&mut self, tree: &UseTree, mut prefix: Vec<String>) {
+        match tree {
+            UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                self.collect(&path.tree, prefix);
+            }
+            UseTree::Name(name) => {
+                let ident = name.ident.to_string();
+                if ident == "self" {
+                    // `use axum::extract::{self, Json}` imports `extract`
+                    if let Some(last) = prefix.last().cloned() {
+                        self.names.insert(last, prefix);
+                    }
+                } else {
+                    prefix.push(ident.clone());
+                    self.names.insert(ident, prefix);
+                }
+            }
+            UseTree::Rename(rename) => {
+                prefix.push(rename.ident.to_string());
+                self.names.insert(rename.rename.to_string(), prefix);
+            }
+            UseTree::Glob(_) => self.globs.push(prefix),
+            UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect(item, prefix.clone());
+                }
+            }
+        }
+    }
+
+    /// Resolve a type path to its fully-qualified segments.
+    ///
+    /// Returns `None` if the first segment was not imported by name (it may come from a glob
+    /// import, the prelude, or be defined locally).
+    pub fn resolve(&self, path: &syn::Path) -> Option<Vec<String>> {
+        let mut segments = path.segments.iter().map(|s| s.ident.to_string());
+        let first = segments.next()?;
+        let mut resolved = self.names.get(&first)?.clone();
+        resolved.extend(segments);
+        Some(resolved)
+    }
+
+    /// Whether `path` refers to an item of one of the given crates.
+    ///
+    /// A path counts as coming from a crate if it is written fully qualified
+    /// (`axum::Json`), was imported from it (`use axum::Json;`), or could have come from a
+    /// glob import of it (`use axum::extract::*;`).
+    pub fn is_from_crate(&self, path: &syn::Path, crates: &[&str]) -> bool {
+        let in_crates = |segments: &[String]| {
+            segments
+                .first()
+                .is_some_and(|root| crates.contains(&root.as_str()))
+        };
+
+        if let Some(resolved) = self.resolve(path) {
+            return in_crates(&resolved);
+        }
+
+        if path.segments.len() > 1 {
+            let first = path.segments[0].ident.to_string();
+            return crates.contains(&first.as_str());
+        }
+
+        self.globs.iter().any(|glob| in_crates(glob))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn imports(code: &str) -> ImportMap {
+        ImportMap::from_file(&syn::parse_file(code).unwrap())
+    }
+
+    fn path(code: &str) -> syn::Path {
+        syn::parse_str(code).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_imports() {
+        let map = imports(
+            r#"
+            use axum::{extract::{self, Path}, Json};
+            use actix_web::web;
+            use validator_json::Json as ValidJson;
+        "#,
+        );
+
+        assert_eq!(map.resolve(&path("Json")).unwrap(), vec!["axum", "Json"]);
+        assert_eq!(
+            map.resolve(&path("extract::Query")).unwrap(),
+            vec!["axum", "extract", "Query"]
+        );
+        assert_eq!(
+            map.resolve(&path("web::Json")).unwrap(),
+            vec!["actix_web", "web", "Json"]
+        );
+        assert_eq!(
+            map.resolve(&path("ValidJson")).unwrap(),
+            vec!["validator_json", "Json"]
+        );
+        assert!(map.resolve(&path("Form")).is_none());
+    }
+
+    #[test]
+    fn test_is_from_crate() {
+        let map = imports(
+            r#"
+            use crate::wrappers::Json;
+            use salvo::prelude::*;
+        "#,
+        );
+
+        assert!(!map.is_from_crate(&path("Json"), &["axum"]));
+        assert!(map.is_from_crate(&path("axum::Json"), &["axum"]));
+        assert!(map.is_from_crate(&path("JsonBody"), &["salvo"]));
+        assert!(!map.is_from_crate(&path("JsonBody"), &["axum"]));
+    }
+}
@@ -8,6 +8,8 @@
 //!
 //! - **Axum**: See [`axum::AxumExtractor`]
 //! - **Actix-Web**: See [`actix::ActixExtractor`]
+//! - **Salvo**: See [`salvo::SalvoExtractor`]
+//! - **Tide**: See [`tide::TideExtractor`]
 //!
 //! # Example
 //!
@@ -24,8 +26,18 @@
 
 pub mod axum;
 pub mod actix;
+pub mod salvo;
+pub mod tide;
+mod aliases;
+mod functions;
+mod imports;
 
-use crate::parser::ParsedFile;
+use crate::diagnostics::{DiagnosticKind, Diagnostics};
+use crate::parser::{extract_doc_comment, ParsedFile};
+use imports::ImportMap;
+use log::warn;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 /// Trait for extracting route information from parsed Rust files.
 ///
@@ -42,8 +54,53 @@ pub trait RouteExtractor {
     ///
     /// Returns a vector of `RouteInfo` structs, one for each discovered route across all files.
     fn extract_routes(&self, parsed_files: &[ParsedFile]) -> Vec<RouteInfo>;
+
+    /// Extracts all route information, recording any problems in `diagnostics`.
+    ///
+    /// Equivalent to [`RouteExtractor::extract_routes_with_options`] with default options.
+    fn extract_routes_with_diagnostics(
+        &self,
+        parsed_files: &[ParsedFile],
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<RouteInfo> {
+        self.extract_routes_with_options(parsed_files, &ExtractOptions::default(), diagnostics)
+    }
+
+    /// Extracts all route information using `options`, recording any problems in `diagnostics`.
+    ///
+    /// The default implementation delegates to [`RouteExtractor::extract_routes`], ignoring
+    /// the options and recording nothing. Extractors that support options or can detect
+    /// incomplete results (such as unresolved handlers) override this method.
+    fn extract_routes_with_options(
+        &self,
+        parsed_files: &[ParsedFile],
+        options: &ExtractOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<RouteInfo> {
+        let _ = (options, diagnostics);
+        self.extract_routes(parsed_files)
+    }
 }
 
+/// Options controlling how routes are extracted.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    /// Only recognize extractors such as `Json`, `Path` and `Query` when the file imports them
+    /// from the framework crate (or names them fully qualified), instead of matching any type
+    /// with that name. This avoids misclassifying custom wrappers like `validator::Json`.
+    pub strict_imports: bool,
+    /// Document the handler of unmatched requests (Axum `.fallback(handler)`, Actix
+    /// `.default_service(...)`) as a catch-all GET operation under this path, e.g. `/{*path}`.
+    /// Fallbacks are ignored when unset.
+    pub fallback_path: Option<String>,
+    /// Document the error responses the framework sends when a handler's extractors reject a
+    /// request, such as Axum's `400` for a malformed JSON body (Axum only)
+    pub document_rejections: bool,
+}
+
+/// Summary of operations documenting a fallback handler
+pub(crate) const FALLBACK_SUMMARY: &str = "Fallback for unmatched requests";
+
 /// Complete information about a single API endpoint.
 ///
 /// This structure contains all the metadata needed to generate an OpenAPI operation,
@@ -56,12 +113,60 @@ pub struct RouteInfo {
     pub method: HttpMethod,
     /// The name of the handler function
     pub handler_name: String,
+    /// Path of the handler function: as written at the route registration
+    /// (`users::get_user`) until the extractor resolves it to the module-qualified path of the
+    /// function it found (`handlers::users::get_user`)
+    pub handler_path: String,
     /// List of parameters extracted from the handler signature
     pub parameters: Vec<Parameter>,
     /// Type information for the request body, if present
     pub request_body: Option<TypeInfo>,
+    /// Whether the request body is required (false for `Option<Json<T>>` extractors)
+    pub request_body_required: bool,
     /// Type information for the response, if it can be determined
     pub response_type: Option<TypeInfo>,
+    /// Status code of the successful response (e.g. 201 for handlers returning
+    /// `(StatusCode::CREATED, Json(..))`); `None` means 200
+    pub response_status: Option<u16>,
+    /// Explicit operation ID from `#[openapi(operation_id = "...")]`
+    pub operation_id: Option<String>,
+    /// Explicit summary from `#[openapi(summary = "...")]`
+    pub summary: Option<String>,
+    /// Tags from `#[openapi(tag = "...")]`, in declaration order
+    pub tags: Vec<String>,
+    /// Request body media types from `#[openapi(consumes = "...")]`, in declaration order, each
+    /// documented with the same schema; empty means JSON
+    pub consumes: Vec<String>,
+    /// Response media type from `#[openapi(produces = "...")]` or the handler's response
+    /// wrapper (Axum's `Html<T>`, `Form<T>`, `Json<T>`); `None` means JSON
+    pub produces: Option<String>,
+    /// JSON file with an example request body, from
+    /// `#[openapi(request_example_file = "...")]`, relative to the project directory
+    pub request_example_file: Option<String>,
+    /// Names of shared examples of the request body, from
+    /// `#[openapi(request_example = "...")]`
+    pub request_examples: Vec<String>,
+    /// Vendor extensions of the operation, from `#[openapi(extension("x-name" = "value"))]`
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Where the handler function's signature is written, when the handler was resolved
+    pub handler_location: Option<SourceLocation>,
+    /// Whether the handler function is declared `pub`; `true` when visibility is unknown or
+    /// not meaningful for the framework
+    pub is_public: bool,
+    /// Whether the route serves static files through a service (`get_service(ServeDir::new(..))`,
+    /// `nest_service`) instead of calling a handler function
+    pub serves_files: bool,
+    /// Whether the extractor found the handler function, and with it the handler's extractors
+    /// and return type
+    pub handler_resolved: bool,
+    /// Doc comment of the handler function
+    pub doc: Option<String>,
+    /// Responses known beyond what static analysis infers (e.g. `401`, `429`), registered by
+    /// library users; they replace inferred responses with the same status
+    pub additional_responses: Vec<AdditionalResponse>,
+    /// Responses the framework sends when the handler's extractors reject a request (Axum's
+    /// `JsonRejection`, `PathRejection`, ...); statuses the route documents otherwise win
+    pub rejection_responses: Vec<AdditionalResponse>,
 }
 
 /// HTTP methods supported by route extractors.
@@ -83,6 +188,10 @@ pub enum HttpMethod {
     Options,
     /// HTTP HEAD method
     Head,
+    /// HTTP TRACE method
+    Trace,
+    /// HTTP CONNECT method (not an OpenAPI operation, so never documented)
+    Connect,
 }
 
 /// Information about a single parameter in a route handler.
@@ -101,6 +210,87 @@ pub struct Parameter {
     pub required: bool,
 }
 
+/// A response documented for a route in addition to its inferred success response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdditionalResponse {
+    /// HTTP status code the response is documented under
+    pub status: u16,
+    /// Description of the response
+    pub description: String,
+    /// Type of the response body, if it has one
+    pub body: Option<TypeInfo>,
+}
+
+/// An event the API sends to its subscribers, documented under the top-level `x-webhooks`
+/// extension of the OpenAPI 3.0 document.
+///
+/// Declared with `#[openapi(webhook(name = "user.created", body = "User"))]` on a marker
+/// function or a module; the marker's doc comment describes the event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookInfo {
+    /// Event name the webhook is listed under
+    pub name: String,
+    /// Type of the event payload
+    pub body: TypeInfo,
+    /// Doc comment of the marker item
+    pub doc: Option<String>,
+}
+
+/// Where a handler function's signature is written in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Source file, as scanned
+    pub file: PathBuf,
+    /// Line the signature starts on, 1-based
+    pub start_line: usize,
+    /// Column the signature starts at, 0-based and counted in characters
+    pub start_column: usize,
+    /// Line the signature ends on, 1-based
+    pub end_line: usize,
+    /// Column just past the end of the signature, 0-based and counted in characters
+    pub end_column: usize,
+}
+
+impl SourceLocation {
+    /// The text of this location in `source` (the content of [`Self::file`]); continuation
+    /// lines lose the indentation of the first line
+    pub fn snippet(&self, source: &str) -> String {
+        let lines: Vec<&str> = source
+            .lines()
+            .skip(self.start_line.saturating_sub(1))
+            .take((self.end_line + 1).saturating_sub(self.start_line.max(1)))
+            .collect();
+        let Some(first) = lines.first() else {
+            return String::new();
+        };
+        let indent = first.len() - first.trim_start().len();
+        let last = lines.len() - 1;
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line = if i == last {
+                    let end = line
+                        .char_indices()
+                        .nth(self.end_column)
+                        .map_or(line.len(), |(at, _)| at);
+                    &line[..end]
+                } else {
+                    line
+                };
+                let start = if i == 0 {
+                    line.char_indices().nth(self.start_column).map_or(line.len(), |(at, _)| at)
+                } else {
+                    let whitespace = line.len() - line.trim_start().len();
+                    whitespace.min(indent)
+                };
+                line[start..].trim_end()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 /// The location where a parameter value is extracted from in an HTTP request.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParameterLocation {
@@ -128,6 +318,15 @@ pub struct TypeInfo {
     pub is_option: bool,
     /// Whether this type is a `Vec<T>` (array type)
     pub is_vec: bool,
+    /// Whether this type is a `Result<T, E>`; `generic_args` holds the `Ok` and `Err` arms
+    pub is_result: bool,
+    /// Whether this type is an `impl Trait`; `name` holds the trait name
+    pub is_impl_trait: bool,
+    /// Whether this type is a trait object (`dyn Trait`); `name` holds the trait name
+    pub is_trait_object: bool,
+    /// Whether this type is a map such as `HashMap<K, V>`; `generic_args` holds the key and
+    /// value types
+    pub is_map: bool,
 }
 
 impl TypeInfo {
@@ -139,6 +338,10 @@ impl TypeInfo {
             generic_args: Vec::new(),
             is_option: false,
             is_vec: false,
+            is_result: false,
+            is_impl_trait: false,
+            is_trait_object: false,
+            is_map: false,
         }
     }
 
@@ -150,6 +353,10 @@ impl TypeInfo {
             generic_args: vec![inner],
             is_option: true,
             is_vec: false,
+            is_result: false,
+            is_impl_trait: false,
+            is_trait_object: false,
+            is_map: false,
         }
     }
 
@@ -161,6 +368,72 @@ impl TypeInfo {
             generic_args: vec![inner],
             is_option: false,
             is_vec: true,
+            is_result: false,
+            is_impl_trait: false,
+            is_trait_object: false,
+            is_map: false,
+        }
+    }
+
+    /// Create a TypeInfo for a `Result<T, E>` type.
+    ///
+    /// `err` is `None` for single-argument aliases such as `anyhow::Result<T>`.
+    pub fn result(ok: TypeInfo, err: Option<TypeInfo>) -> Self {
+        let mut generic_args = vec![ok];
+        generic_args.extend(err);
+        Self {
+            name: generic_args[0].name.clone(),
+            is_generic: false,
+            generic_args,
+            is_option: false,
+            is_vec: false,
+            is_result: true,
+            is_impl_trait: false,
+            is_trait_object: false,
+            is_map: false,
+        }
+    }
+
+    /// Create a TypeInfo for a map type such as `HashMap<K, V>` or `BTreeMap<K, V>`
+    pub fn map(name: String, key: TypeInfo, value: TypeInfo) -> Self {
+        Self {
+            is_map: true,
+            generic_args: vec![key, value],
+            ..Self::new(name)
+        }
+    }
+
+    /// Create a TypeInfo for an `impl Trait` type
+    pub fn impl_trait(trait_name: String) -> Self {
+        Self {
+            is_impl_trait: true,
+            ..Self::new(trait_name)
+        }
+    }
+
+    /// Create a TypeInfo for a trait object (`dyn Trait`) type
+    pub fn trait_object(trait_name: String) -> Self {
+        Self {
+            is_trait_object: true,
+            ..Self::new(trait_name)
+        }
+    }
+
+    /// The `Ok` arm of a `Result<T, E>` type
+    pub fn ok_type(&self) -> Option<&TypeInfo> {
+        if self.is_result {
+            self.generic_args.first()
+        } else {
+            None
+        }
+    }
+
+    /// The `Err` arm of a `Result<T, E>` type, if it was spelled out
+    pub fn err_type(&self) -> Option<&TypeInfo> {
+        if self.is_result {
+            self.generic_args.get(1)
+        } else {
+            None
         }
     }
 }
@@ -171,10 +444,38 @@ impl RouteInfo {
         Self {
             path,
             method,
+            handler_path: handler_name.clone(),
             handler_name,
             parameters: Vec::new(),
             request_body: None,
+            request_body_required: true,
             response_type: None,
+            response_status: None,
+            operation_id: None,
+            summary: None,
+            tags: Vec::new(),
+            consumes: Vec::new(),
+            produces: None,
+            request_example_file: None,
+            request_examples: Vec::new(),
+            extensions: BTreeMap::new(),
+            handler_location: None,
+            is_public: true,
+            serves_files: false,
+            handler_resolved: false,
+            doc: None,
+            additional_responses: Vec::new(),
+            rejection_responses: Vec::new(),
+        }
+    }
+
+    /// Create a new RouteInfo for the handler at `handler_path` as written at the route
+    /// registration (`get_user` or `users::get_user`)
+    pub fn for_handler(path: String, method: HttpMethod, handler_path: String) -> Self {
+        let handler_name = handler_path.rsplit("::").next().unwrap_or_default().to_string();
+        Self {
+            handler_path,
+            ..Self::new(path, method, handler_name)
         }
     }
 }
@@ -190,3 +491,799 @@ impl Parameter {
         }
     }
 }
+
+/// Extractors that consume raw request data without a typed schema.
+///
+/// These are recognized explicitly so they are never mistaken for a request body or a typed
+/// parameter, even if a later change makes extractor matching more permissive:
+///
+/// - `RawQuery`: the unparsed query string; any query parameters are accepted
+/// - `RawForm`: the unparsed form body
+/// - `OriginalUri`: the request URI before any nesting was stripped
+pub(crate) const RAW_EXTRACTORS: &[&str] = &["RawQuery", "RawForm", "OriginalUri"];
+
+/// Return the name of the raw extractor used by `ty`, if it is one of [`RAW_EXTRACTORS`].
+pub(crate) fn raw_extractor_name(ty: &syn::Type) -> Option<&'static str> {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return RAW_EXTRACTORS
+                .iter()
+                .copied()
+                .find(|name| segment.ident == name);
+        }
+    }
+    None
+}
+
+/// Peel an `Option<...>` wrapper off an extractor type.
+///
+/// Returns the inner type and `true` if `ty` was `Option<T>`, or `ty` itself and `false`
+/// otherwise. Used to recognize optional extractors such as `Option<Json<T>>`.
+pub(crate) fn peel_option(ty: &syn::Type) -> (&syn::Type, bool) {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
+                        return (inner_ty, true);
+                    }
+                }
+            }
+        }
+    }
+    (ty, false)
+}
+
+/// Whether an extractor type may be recognized under the given options.
+///
+/// Without [`ExtractOptions::strict_imports`] every type with a matching name is accepted.
+/// In strict mode the type must come from one of the framework `crates` according to the
+/// imports of the file declaring the handler.
+pub(crate) fn extractor_allowed(
+    ty: &syn::Type,
+    imports: Option<&ImportMap>,
+    options: &ExtractOptions,
+    crates: &[&str],
+) -> bool {
+    if !options.strict_imports {
+        return true;
+    }
+    match (ty, imports) {
+        (syn::Type::Path(type_path), Some(imports)) => {
+            imports.is_from_crate(&type_path.path, crates)
+        }
+        _ => false,
+    }
+}
+
+/// Name of the parameter recording a handler's `Path<T>` extractor; `T` is its type
+pub(crate) const PATH_EXTRACTOR_PARAM: &str = "path_params";
+
+/// Map types from `std::collections` and common crates, matched by their last path segment
+pub(crate) const MAP_TYPES: &[&str] = &["HashMap", "BTreeMap", "IndexMap"];
+
+/// Smart pointers that serialize exactly like the type they wrap (`Arc<str>` is a string)
+pub(crate) const TRANSPARENT_WRAPPERS: &[&str] = &["Box", "Arc", "Rc", "Cow"];
+
+/// Return the type arguments of a generic type path segment (`T` and `E` in `Result<T, E>`)
+fn type_arguments(segment: &syn::PathSegment) -> Vec<&syn::Type> {
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Extract TypeInfo from a syn::Type.
+///
+/// Shared by all extractors so that `Option`, `Vec`, `Result` and `impl Trait` are decomposed
+/// the same way everywhere. References and smart pointers are looked through (`&str` and
+/// `Arc<str>` are `str`).
+pub(crate) fn extract_type_info(ty: &syn::Type) -> TypeInfo {
+    match ty {
+        syn::Type::Path(type_path) => {
+            let Some(segment) = type_path.path.segments.last() else {
+                return TypeInfo::new("unknown".to_string());
+            };
+            let type_name = segment.ident.to_string();
+            let args = type_arguments(segment);
+
+            match (type_name.as_str(), args.as_slice()) {
+                ("Option", [inner, ..]) => TypeInfo::option(extract_type_info(inner)),
+                ("Vec", [inner, ..]) => TypeInfo::vec(extract_type_info(inner)),
+                (name, [inner, ..]) if TRANSPARENT_WRAPPERS.contains(&name) => {
+                    extract_type_info(inner)
+                }
+                (name, [key, value, ..]) if MAP_TYPES.contains(&name) => TypeInfo::map(
+                    type_name.clone(),
+                    extract_type_info(key),
+                    extract_type_info(value),
+                ),
+                ("Result", [ok, rest @ ..]) => TypeInfo::result(
+                    extract_type_info(ok),
+                    rest.first().map(|err| extract_type_info(err)),
+                ),
+                (_, []) => TypeInfo::new(type_name),
+                // `ApiResponse<User>`, instantiated by the schema generator
+                _ => TypeInfo {
+                    is_generic: true,
+                    generic_args: args.iter().map(|arg| extract_type_info(arg)).collect(),
+                    ..TypeInfo::new(type_name)
+                },
+            }
+        }
+        syn::Type::Reference(type_ref) => extract_type_info(&type_ref.elem),
+        syn::Type::Paren(paren) => extract_type_info(&paren.elem),
+        syn::Type::ImplTrait(impl_trait) => {
+            TypeInfo::impl_trait(bound_trait_name(&impl_trait.bounds))
+        }
+        // `Box<dyn Serialize>`: the concrete type is only known at run time
+        syn::Type::TraitObject(trait_object) => {
+            TypeInfo::trait_object(bound_trait_name(&trait_object.bounds))
+        }
+        _ => TypeInfo::new("unknown".to_string()),
+    }
+}
+
+/// Name of the first trait of the bounds of an `impl Trait` or `dyn Trait` type (`Serialize`
+/// in `dyn Serialize + Send`)
+pub(crate) fn bound_trait_name<P>(
+    bounds: &syn::punctuated::Punctuated<syn::TypeParamBound, P>,
+) -> String {
+    bounds
+        .iter()
+        .find_map(|bound| match bound {
+            syn::TypeParamBound::Trait(trait_bound) => {
+                trait_bound.path.segments.last().map(|s| s.ident.to_string())
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Split a `Result<T, E>` type into its `Ok` and `Err` arms.
+///
+/// Returns `None` if `ty` is not a `Result`. The `Err` arm is `None` for single-argument
+/// aliases such as `anyhow::Result<T>`.
+pub(crate) fn result_arms(ty: &syn::Type) -> Option<(&syn::Type, Option<&syn::Type>)> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let args = type_arguments(segment);
+    let ok = *args.first()?;
+    Some((ok, args.get(1).copied()))
+}
+
+/// Record that the handler of `route` declares body extractors besides the documented `body`.
+///
+/// A request body can only be consumed once, so the first body extractor is the one the
+/// handler actually receives; the others are listed in the diagnostic.
+pub(crate) fn report_ignored_bodies(
+    route: &RouteInfo,
+    body: &TypeInfo,
+    ignored: &[TypeInfo],
+    diagnostics: &mut Diagnostics,
+) {
+    if ignored.is_empty() {
+        return;
+    }
+    let ignored: Vec<&str> = ignored.iter().map(|ty| ty.name.as_str()).collect();
+    let message = format!(
+        "Handler {} ({} {}) declares several request bodies; documenting {}, ignoring {}",
+        route.handler_path,
+        route.method_str(),
+        route.path,
+        body.name,
+        ignored.join(", ")
+    );
+    warn!("{}", message);
+    diagnostics.push(DiagnosticKind::MultipleRequestBodies, message);
+}
+
+/// The statics declared in a `lazy_static! { static ref NAME: Type = expr; ... }` item, as
+/// name and initializer pairs.
+///
+/// Macro bodies are opaque to `syn`, so routers built in such initializers are only found
+/// through this function. Other macros, and bodies that fail to parse, yield nothing.
+pub(crate) fn lazy_statics(mac: &syn::Macro) -> Vec<(syn::Ident, syn::Expr)> {
+    if mac.path.segments.last().is_none_or(|s| s.ident != "lazy_static") {
+        return Vec::new();
+    }
+    let parser = |input: syn::parse::ParseStream| {
+        let mut statics = Vec::new();
+        while !input.is_empty() {
+            input.call(syn::Attribute::parse_outer)?;
+            input.parse::<syn::Visibility>()?;
+            input.parse::<syn::Token![static]>()?;
+            input.parse::<syn::Token![ref]>()?;
+            let ident: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![:]>()?;
+            input.parse::<syn::Type>()?;
+            input.parse::<syn::Token![=]>()?;
+            let expr: syn::Expr = input.parse()?;
+            input.parse::<syn::Token![;]>()?;
+            statics.push((ident, expr));
+        }
+        Ok(statics)
+    };
+    mac.parse_body_with(parser).unwrap_or_else(|e| {
+        warn!("Ignoring unparsable lazy_static! item: {}", e);
+        Vec::new()
+    })
+}
+
+/// Operation metadata declared with `#[openapi(...)]` on a handler function.
+///
+/// ```ignore
+/// #[openapi(operation_id = "listUsers", summary = "List all users", tag = "users")]
+/// async fn list_users() -> Json<Vec<User>> { ... }
+/// ```
+///
+/// `tag` may be repeated to assign several tags. `response = "Vec<User>"` documents the
+/// success response of handlers whose return type hides it (`impl IntoResponse`),
+/// `request_body = "CreateUser"` the body of handlers whose extractors do not reveal it, and
+/// `consumes`/`produces` replace the `application/json` media type of the request body and
+/// response; `consumes` may be repeated for bodies accepted in several encodings.
+/// `request_example_file = "examples/create_user.json"` documents the request body
+/// example kept in that file, and `request_example = "new_user"` (repeatable) references a
+/// shared example registered under `components.examples`.
+///
+/// On fields of `Query<T>`/`Path<T>` structs, `param_description = "..."` describes the
+/// parameter instead of the field's doc comment.
+///
+/// On marker functions and modules, `webhook(name = "user.created", body = "User")`
+/// (repeatable) declares a webhook; see [`extract_webhooks`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OpenApiAttributes {
+    /// Operation ID override
+    pub operation_id: Option<String>,
+    /// Summary override
+    pub summary: Option<String>,
+    /// Operation tags
+    pub tags: Vec<String>,
+    /// Response type override
+    pub response: Option<TypeInfo>,
+    /// Request body type override
+    pub request_body: Option<TypeInfo>,
+    /// Request body media type overrides
+    pub consumes: Vec<String>,
+    /// Response media type override
+    pub produces: Option<String>,
+    /// Request body example file
+    pub request_example_file: Option<String>,
+    /// Names of shared request body examples
+    pub request_examples: Vec<String>,
+    /// Vendor extensions
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Parameter description override (on extractor struct fields)
+    pub param_description: Option<String>,
+    /// Webhooks declared by a marker item
+    pub webhooks: Vec<WebhookInfo>,
+}
+
+impl OpenApiAttributes {
+    /// Whether no override was declared
+    pub fn is_empty(&self) -> bool {
+        self.operation_id.is_none()
+            && self.summary.is_none()
+            && self.tags.is_empty()
+            && self.response.is_none()
+            && self.request_body.is_none()
+            && self.consumes.is_empty()
+            && self.produces.is_none()
+            && self.request_example_file.is_none()
+            && self.request_examples.is_empty()
+            && self.extensions.is_empty()
+            && self.param_description.is_none()
+            && self.webhooks.is_empty()
+    }
+
+    /// Copy the declared overrides onto a route
+    pub fn apply_to(&self, route: &mut RouteInfo) {
+        if self.operation_id.is_some() {
+            route.operation_id = self.operation_id.clone();
+        }
+        if self.summary.is_some() {
+            route.summary = self.summary.clone();
+        }
+        route.tags.extend(self.tags.iter().cloned());
+        if self.response.is_some() {
+            route.response_type = self.response.clone();
+        }
+        if self.request_body.is_some() {
+            route.request_body = self.request_body.clone();
+            route.request_body_required = true;
+        }
+        if !self.consumes.is_empty() {
+            route.consumes = self.consumes.clone();
+        }
+        if self.produces.is_some() {
+            route.produces = self.produces.clone();
+        }
+        if self.request_example_file.is_some() {
+            route.request_example_file = self.request_example_file.clone();
+        }
+        route.request_examples.extend(self.request_examples.iter().cloned());
+        route.extensions.extend(self.extensions.clone());
+    }
+}
+
+/// Parse all `#[openapi(...)]` attributes in `attrs`.
+///
+/// Unknown keys and malformed attributes are logged and ignored so that a typo never aborts
+/// generation.
+pub(crate) fn parse_openapi_attributes(attrs: &[syn::Attribute]) -> OpenApiAttributes {
+    let mut result = OpenApiAttributes::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("openapi")) {
+        let parsed = attr.parse_nested_meta(|meta| {
+            let key = meta
+                .path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+            match key.as_str() {
+                "operation_id" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.operation_id = Some(value.value());
+                }
+                "summary" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.summary = Some(value.value());
+                }
+                "tag" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.tags.push(value.value());
+                }
+                "response" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    let ty: syn::Type = value.parse()?;
+                    result.response = Some(extract_type_info(&ty));
+                }
+                "request_body" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    let ty: syn::Type = value.parse()?;
+                    result.request_body = Some(extract_type_info(&ty));
+                }
+                "consumes" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.consumes.push(value.value());
+                }
+                "produces" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.produces = Some(value.value());
+                }
+                "request_example_file" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.request_example_file = Some(value.value());
+                }
+                "request_example" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.request_examples.push(value.value());
+                }
+                "param_description" => {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    result.param_description = Some(value.value());
+                }
+                "extension" => {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    while !content.is_empty() {
+                        let name: syn::LitStr = content.parse()?;
+                        content.parse::<syn::Token![=]>()?;
+                        let value: syn::LitStr = content.parse()?;
+                        if name.value().starts_with("x-") {
+                            result.extensions.insert(name.value(), extension_value(&value.value()));
+                        } else {
+                            warn!(
+                                "Ignoring extension `{}`: names must start with `x-`",
+                                name.value()
+                            );
+                        }
+                        if !content.is_empty() {
+                            content.parse::<syn::Token![,]>()?;
+                        }
+                    }
+                }
+                "webhook" => {
+                    let mut name = None;
+                    let mut body = None;
+                    meta.parse_nested_meta(|nested| {
+                        let value: syn::LitStr = nested.value()?.parse()?;
+                        if nested.path.is_ident("name") {
+                            name = Some(value.value());
+                        } else if nested.path.is_ident("body") {
+                            body = Some(extract_type_info(&value.parse::<syn::Type>()?));
+                        } else {
+                            return Err(nested.error("expected `name` or `body`"));
+                        }
+                        Ok(())
+                    })?;
+                    match (name, body) {
+                        (Some(name), Some(body)) => {
+                            result.webhooks.push(WebhookInfo { name, body, doc: None })
+                        }
+                        _ => return Err(meta.error("webhook needs both `name` and `body`")),
+                    }
+                }
+                _ => {
+                    warn!("Ignoring unknown #[openapi(...)] key `{}`", key);
+                    skip_meta_value(&meta)?;
+                }
+            }
+            Ok(())
+        });
+
+        if let Err(e) = parsed {
+            warn!("Ignoring part of #[openapi(...)] attribute: {}", e);
+        }
+    }
+
+    result
+}
+
+/// Skip the value of a nested meta item (`= ...` or `(...)`) so parsing can continue with its
+/// siblings
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let _content;
+        syn::parenthesized!(_content in meta.input);
+    }
+    Ok(())
+}
+
+/// Value of an `#[openapi(extension(...))]` entry: the JSON it spells (`"100"` is the number
+/// 100, `"true"` a boolean, `"[\"a\"]"` an array), or else the string itself
+fn extension_value(value: &str) -> serde_json::Value {
+    serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
+}
+
+/// Collect the webhooks declared on functions and modules of the parsed files, and by inner
+/// `#![openapi(webhook(...))]` attributes of the files themselves.
+///
+/// Webhooks do not depend on a web framework, so they are found independently of the route
+/// extractors. Each webhook takes the doc comment of the item declaring it.
+pub fn extract_webhooks(parsed_files: &[ParsedFile]) -> Vec<WebhookInfo> {
+    fn declared(attrs: &[syn::Attribute], webhooks: &mut Vec<WebhookInfo>) {
+        let doc = extract_doc_comment(attrs);
+        webhooks.extend(parse_openapi_attributes(attrs).webhooks.into_iter().map(|webhook| {
+            WebhookInfo {
+                doc: doc.clone(),
+                ..webhook
+            }
+        }));
+    }
+
+    fn visit(items: &[syn::Item], webhooks: &mut Vec<WebhookInfo>) {
+        for item in items {
+            match item {
+                syn::Item::Fn(item_fn) => declared(&item_fn.attrs, webhooks),
+                syn::Item::Mod(item_mod) => {
+                    declared(&item_mod.attrs, webhooks);
+                    if let Some((_, items)) = &item_mod.content {
+                        visit(items, webhooks);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut webhooks = Vec::new();
+    for parsed in parsed_files {
+        declared(&parsed.syntax_tree.attrs, &mut webhooks);
+        visit(&parsed.syntax_tree.items, &mut webhooks);
+    }
+    webhooks
+}
+
+/// Tags declared for a whole file or inline module.
+///
+/// Files declare them with an inner `#![openapi(tag = "...")]` attribute or a `//! @tag name`
+/// doc line, inline modules with `#[openapi(tag = "...")]` or `/// @tag name`. Every handler
+/// defined inside inherits the tags unless it declares its own.
+#[derive(Debug, Default)]
+pub(crate) struct ModuleTags {
+    /// Tags of the enclosing file and modules, outermost first
+    scopes: Vec<Vec<String>>,
+    /// Inherited tags of each handler function defined in a tagged scope
+    handlers: HashMap<String, Vec<String>>,
+}
+
+impl ModuleTags {
+    /// Enter a file or module with the given attributes
+    pub fn enter(&mut self, attrs: &[syn::Attribute]) {
+        let mut tags = parse_openapi_attributes(attrs).tags;
+        if let Some(doc) = extract_doc_comment(attrs) {
+            tags.extend(
+                doc.lines()
+                    .filter_map(|line| line.trim().strip_prefix("@tag "))
+                    .map(|tag| tag.trim().to_string()),
+            );
+        }
+        self.scopes.push(tags);
+    }
+
+    /// Leave the innermost file or module
+    pub fn exit(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Record the tags in scope for a handler function defined at the current position, keyed
+    /// by its [`functions::FunctionIndex`] key
+    pub fn record(&mut self, fn_key: &str) {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.scopes.iter().flatten() {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        if !tags.is_empty() {
+            self.handlers.insert(fn_key.to_string(), tags);
+        }
+    }
+
+    /// Give a route without handler-level tags the tags of its handler's module
+    pub fn apply_to(&self, fn_key: &str, route: &mut RouteInfo) {
+        if route.tags.is_empty() {
+            if let Some(tags) = self.handlers.get(fn_key) {
+                route.tags = tags.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_attrs(code: &str) -> Vec<syn::Attribute> {
+        syn::parse_str::<syn::ItemFn>(code).unwrap().attrs
+    }
+
+    fn parse_type(code: &str) -> syn::Type {
+        syn::parse_str(code).unwrap()
+    }
+
+    #[test]
+    fn test_lazy_statics() {
+        let item: syn::ItemMacro = syn::parse_str(
+            r#"
+            lazy_static::lazy_static! {
+                /// The API router
+                pub static ref API: Router = Router::with_path("api");
+                static ref EMPTY: Vec<u8> = vec![];
+            }
+        "#,
+        )
+        .unwrap();
+
+        let statics = lazy_statics(&item.mac);
+        let names: Vec<String> = statics.iter().map(|(ident, _)| ident.to_string()).collect();
+        assert_eq!(names, vec!["API", "EMPTY"]);
+        assert!(matches!(statics[0].1, syn::Expr::Call(_)));
+
+        let other: syn::ItemMacro = syn::parse_str("thread_local! { static X: u8 = 0; }").unwrap();
+        assert!(lazy_statics(&other.mac).is_empty());
+    }
+
+    #[test]
+    fn test_extract_result_type_info() {
+        let type_info = extract_type_info(&parse_type("Result<Vec<User>, AppError>"));
+
+        assert!(type_info.is_result);
+        let ok = type_info.ok_type().unwrap();
+        assert!(ok.is_vec);
+        assert_eq!(ok.generic_args[0].name, "User");
+        assert_eq!(type_info.err_type().unwrap().name, "AppError");
+
+        // Single-argument aliases like anyhow::Result<T> have no Err arm
+        let type_info = extract_type_info(&parse_type("anyhow::Result<User>"));
+        assert_eq!(type_info.ok_type().unwrap().name, "User");
+        assert!(type_info.err_type().is_none());
+
+        assert!(TypeInfo::new("User".to_string()).ok_type().is_none());
+    }
+
+    #[test]
+    fn test_extract_impl_trait_type_info() {
+        let type_info = extract_type_info(&parse_type("impl IntoResponse + Send"));
+
+        assert!(type_info.is_impl_trait);
+        assert_eq!(type_info.name, "IntoResponse");
+    }
+
+    #[test]
+    fn test_extract_trait_object_type_info() {
+        let type_info = extract_type_info(&parse_type("Box<dyn erased_serde::Serialize + Send>"));
+
+        assert!(type_info.is_trait_object);
+        assert!(!type_info.is_impl_trait);
+        assert_eq!(type_info.name, "Serialize");
+    }
+
+    #[test]
+    fn test_extract_qualified_type_info() {
+        let type_info = extract_type_info(&parse_type("std::vec::Vec<std::string::String>"));
+        assert!(type_info.is_vec);
+        assert_eq!(type_info.generic_args[0].name, "String");
+
+        let type_info = extract_type_info(&parse_type(
+            "std::collections::HashMap<String, core::primitive::u32>",
+        ));
+        assert!(type_info.is_map);
+        assert_eq!(type_info.name, "HashMap");
+        assert_eq!(type_info.generic_args[1].name, "u32");
+    }
+
+    #[test]
+    fn test_result_arms() {
+        let ty = parse_type("Result<Json<User>, StatusCode>");
+        let (ok, err) = result_arms(&ty).unwrap();
+
+        assert_eq!(extract_type_info(ok).name, "Json");
+        assert_eq!(extract_type_info(err.unwrap()).name, "StatusCode");
+        assert!(result_arms(&parse_type("Json<User>")).is_none());
+    }
+
+    #[test]
+    fn test_parse_openapi_attributes() {
+        let attrs = parse_attrs(
+            r#"
+            #[openapi(operation_id = "listUsers", summary = "List all users", tag = "users")]
+            #[openapi(tag = "admin")]
+            async fn list_users() {}
+        "#,
+        );
+
+        let parsed = parse_openapi_attributes(&attrs);
+        assert_eq!(parsed.operation_id, Some("listUsers".to_string()));
+        assert_eq!(parsed.summary, Some("List all users".to_string()));
+        assert_eq!(parsed.tags, vec!["users", "admin"]);
+    }
+
+    #[test]
+    fn test_parse_openapi_response_attribute() {
+        let attrs = parse_attrs(
+            r#"
+            #[openapi(response = "Vec<User>")]
+            async fn list_users() -> impl IntoResponse {}
+        "#,
+        );
+
+        let response = parse_openapi_attributes(&attrs).response.unwrap();
+        assert!(response.is_vec);
+        assert_eq!(response.generic_args[0].name, "User");
+    }
+
+    #[test]
+    fn test_parse_openapi_media_type_attributes() {
+        let attrs = parse_attrs(
+            r#"
+            #[openapi(consumes = "text/csv", produces = "application/octet-stream")]
+            #[openapi(request_example_file = "examples/users.csv")]
+            #[openapi(request_example = "admins", request_example = "guests")]
+            async fn import_users(body: String) -> Vec<u8> {}
+        "#,
+        );
+
+        let parsed = parse_openapi_attributes(&attrs);
+        assert_eq!(parsed.consumes, vec!["text/csv"]);
+        assert_eq!(parsed.produces, Some("application/octet-stream".to_string()));
+        assert_eq!(parsed.request_example_file, Some("examples/users.csv".to_string()));
+        assert_eq!(parsed.request_examples, vec!["admins", "guests"]);
+
+        let mut route =
+            RouteInfo::new("/import".to_string(), HttpMethod::Post, "import_users".to_string());
+        parsed.apply_to(&mut route);
+        assert_eq!(route.consumes, parsed.consumes);
+        assert_eq!(route.produces, parsed.produces);
+
+        // `consumes` is repeatable
+        let attrs = parse_attrs(
+            r#"
+            #[openapi(consumes = "application/json")]
+            #[openapi(consumes = "application/x-www-form-urlencoded")]
+            async fn create_user(body: JsonOrForm<CreateUser>) {}
+        "#,
+        );
+        assert_eq!(
+            parse_openapi_attributes(&attrs).consumes,
+            vec!["application/json", "application/x-www-form-urlencoded"]
+        );
+    }
+
+    #[test]
+    fn test_parse_openapi_extension_attributes() {
+        let attrs = parse_attrs(
+            r#"
+            #[openapi(extension("x-rate-limit" = "100", "x-owner" = "billing"))]
+            #[openapi(extension("x-internal" = "true"), summary = "Refund")]
+            #[openapi(extension("rate-limit" = "5", "x-team" = "payments"), tag = "billing")]
+            async fn refund() {}
+        "#,
+        );
+
+        let parsed = parse_openapi_attributes(&attrs);
+        assert_eq!(parsed.extensions["x-rate-limit"], serde_json::json!(100));
+        assert_eq!(parsed.extensions["x-owner"], "billing");
+        assert_eq!(parsed.extensions["x-internal"], true);
+        assert_eq!(parsed.summary, Some("Refund".to_string()));
+        // Names must start with `x-`; only the offending extension is skipped
+        assert_eq!(parsed.extensions["x-team"], "payments");
+        assert_eq!(parsed.tags, vec!["billing"]);
+        assert_eq!(parsed.extensions.len(), 4);
+    }
+
+    #[test]
+    fn test_extract_webhooks() {
+        let code = r#"
+            /// A user signed up
+            #[openapi(webhook(name = "user.created", body = "User"))]
+            fn user_created() {}
+
+            #[openapi(webhook(name = "order.paid", body = "Vec<Order>"))]
+            mod events {
+                #[openapi(webhook(name = "order.shipped", body = "Order"))]
+                #[openapi(webhook(name = "incomplete"))]
+                fn order_shipped() {}
+            }
+        "#;
+        let parsed = ParsedFile {
+            path: PathBuf::from("src/events.rs"),
+            syntax_tree: syn::parse_file(code).unwrap(),
+        };
+
+        let webhooks = extract_webhooks(&[parsed]);
+        let names: Vec<&str> = webhooks.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["user.created", "order.paid", "order.shipped"]);
+        assert_eq!(webhooks[0].body.name, "User");
+        assert_eq!(webhooks[0].doc.as_deref(), Some("A user signed up"));
+        assert!(webhooks[1].body.is_vec);
+        assert_eq!(webhooks[1].doc, None);
+    }
+
+    #[test]
+    fn test_source_location_snippet() {
+        let source = "mod api {\n    pub async fn health() -> &'static str { \"ok\" }\n}\n";
+        let location = SourceLocation {
+            file: PathBuf::from("src/api.rs"),
+            start_line: 2,
+            start_column: 8,
+            end_line: 2,
+            end_column: 41,
+        };
+        assert_eq!(location.snippet(source), "async fn health() -> &'static str");
+    }
+
+    #[test]
+    fn test_parse_openapi_attributes_ignores_unknown_keys() {
+        let attrs = parse_attrs(
+            r#"
+            #[openapi(summary = "Health check")]
+            #[openapi(colour = "blue")]
+            #[openapi(typo = "x", tag = "health", retries(3), deprecated, operation_id = "ping")]
+            #[get("/health")]
+            async fn health() {}
+        "#,
+        );
+
+        let parsed = parse_openapi_attributes(&attrs);
+        assert_eq!(parsed.summary, Some("Health check".to_string()));
+        // Keys after an unknown one are still read
+        assert_eq!(parsed.tags, vec!["health"]);
+        assert_eq!(parsed.operation_id, Some("ping".to_string()));
+    }
+}
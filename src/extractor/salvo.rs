@@ -0,0 +1,815 @@
+use crate::diagnostics::Diagnostics;
+use crate::extractor::aliases::TypeAliases;
+use crate::extractor::functions::{is_entry_point, FunctionIndex};
+use crate::extractor::imports::ImportMap;
+use crate::extractor::{
+    extract_type_info, extractor_allowed, lazy_statics, parse_openapi_attributes, peel_option,
+    report_ignored_bodies, result_arms, ExtractOptions, HttpMethod, ModuleTags,
+    OpenApiAttributes, Parameter, ParameterLocation, RouteExtractor, RouteInfo, TypeInfo,
+};
+use crate::parser::{extract_doc_comment, ParsedFile};
+use log::{debug, warn};
+use std::collections::HashSet;
+use syn::{visit::Visit, Expr, ExprMethodCall, Lit};
+
+/// Salvo route extractor
+///
+/// Salvo routers form a tree built with `Router::new()`/`Router::with_path("...")`, where
+/// `.path("...")` extends the router's path, `.get(handler)` and friends register handlers,
+/// and `.push(child)` nests a child router below the parent's path. The child may be built
+/// inline or come from another function or static (`.push(users::router())`, `.push(API)`).
+pub struct SalvoExtractor;
+
+/// Crates whose extractors are recognized in strict import mode
+const SALVO_CRATES: &[&str] = &["salvo"];
+
+impl RouteExtractor for SalvoExtractor {
+    fn extract_routes(&self, parsed_files: &[ParsedFile]) -> Vec<RouteInfo> {
+        self.extract_routes_with_diagnostics(parsed_files, &mut Diagnostics::new())
+    }
+
+    fn extract_routes_with_options(
+        &self,
+        parsed_files: &[ParsedFile],
+        options: &ExtractOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<RouteInfo> {
+        let mut visitor = SalvoVisitor::new(options.clone());
+
+        // First pass: collect all routers and function signatures from all files
+        for parsed_file in parsed_files {
+            visitor.imports.push(ImportMap::from_file(&parsed_file.syntax_tree));
+            visitor.function_index.enter_file(&parsed_file.path);
+            visitor.module_tags.enter(&parsed_file.syntax_tree.attrs);
+            visitor.visit_file(&parsed_file.syntax_tree);
+            visitor.module_tags.exit();
+        }
+
+        // Routers pushed by name are placed below the path they are pushed at
+        visitor.resolve_router_mounts();
+
+        // After collecting routes and functions from all files, analyze handlers
+        visitor.analyze_handlers(diagnostics);
+
+        visitor.routes
+    }
+}
+
+/// A router returned by a function or held by a static, pushed into another router
+#[derive(Debug, Clone)]
+struct RouterMount {
+    /// Owner of the router the child is pushed into (`None` outside any function or static)
+    parent: Option<String>,
+    /// Owner of the pushed router, as written at the `.push()` until resolved
+    child: String,
+    /// Full path of the parent router at the `.push()`
+    prefix: String,
+}
+
+/// Visitor for traversing the AST and finding Salvo routers
+struct SalvoVisitor {
+    routes: Vec<RouteInfo>,
+    /// Router owner of each entry in `routes`, see [`SalvoVisitor::current_owner`]
+    route_owners: Vec<Option<String>>,
+    /// Routers pushed by name into other routers
+    mounts: Vec<RouterMount>,
+    /// Router the visited routes belong to: the module-qualified key of the enclosing function,
+    /// or the name of the enclosing static
+    current_owner: Option<String>,
+    functions: std::collections::HashMap<String, syn::Signature>,
+    /// `#[openapi(...)]` overrides declared on handler functions
+    openapi_attrs: std::collections::HashMap<String, OpenApiAttributes>,
+    /// Doc comments of the visited functions
+    handler_docs: std::collections::HashMap<String, String>,
+    /// Imports of each visited file, in visiting order
+    imports: Vec<ImportMap>,
+    /// Index into `imports` of the file declaring each function
+    function_files: std::collections::HashMap<String, usize>,
+    /// Functions declared `pub`
+    public_functions: std::collections::HashSet<String>,
+    /// Tags inherited from enclosing files and modules
+    module_tags: ModuleTags,
+    /// Module-qualified keys of the visited functions
+    function_index: FunctionIndex,
+    /// `type` aliases, expanded in handler return types
+    type_aliases: TypeAliases,
+    options: ExtractOptions,
+}
+
+impl SalvoVisitor {
+    fn new(options: ExtractOptions) -> Self {
+        Self {
+            routes: Vec::new(),
+            route_owners: Vec::new(),
+            mounts: Vec::new(),
+            current_owner: None,
+            functions: std::collections::HashMap::new(),
+            openapi_attrs: std::collections::HashMap::new(),
+            handler_docs: std::collections::HashMap::new(),
+            imports: Vec::new(),
+            function_files: std::collections::HashMap::new(),
+            public_functions: std::collections::HashSet::new(),
+            module_tags: ModuleTags::default(),
+            function_index: FunctionIndex::default(),
+            type_aliases: TypeAliases::default(),
+            options,
+        }
+    }
+
+    /// Analyze routes with handler information
+    fn analyze_handlers(&mut self, diagnostics: &mut Diagnostics) {
+        for idx in 0..self.routes.len() {
+            let handler_path = self.routes[idx].handler_path.clone();
+            // Resolve the handler as written at the registration to a module-qualified key
+            let handler_key = self
+                .function_index
+                .resolve(&handler_path)
+                .map(str::to_string)
+                .unwrap_or(handler_path);
+
+            if let Some(attrs) = self.openapi_attrs.get(&handler_key) {
+                attrs.apply_to(&mut self.routes[idx]);
+            }
+            self.routes[idx].doc = self.handler_docs.get(&handler_key).cloned();
+            self.module_tags.apply_to(&handler_key, &mut self.routes[idx]);
+
+            if let Some(fn_sig) = self.functions.get(&handler_key) {
+                debug!("Found handler function: {}", handler_key);
+                self.routes[idx].handler_path = handler_key.clone();
+                self.routes[idx].handler_resolved = true;
+                self.routes[idx].handler_location =
+                    self.function_index.location(&handler_key, fn_sig);
+                let imports = self
+                    .function_files
+                    .get(&handler_key)
+                    .and_then(|&idx| self.imports.get(idx));
+                let (params, request_body, request_body_required, ignored_bodies) =
+                    self.parse_extractors(fn_sig, imports);
+                let response_type = self.parse_response_type(fn_sig);
+
+                let route = &mut self.routes[idx];
+                for param in params {
+                    // Typed path parameters refine the String placeholders from the URL
+                    if let Some(existing) = route
+                        .parameters
+                        .iter_mut()
+                        .find(|p| p.location == ParameterLocation::Path && p.name == param.name)
+                    {
+                        existing.type_info = param.type_info;
+                    } else {
+                        route.parameters.push(param);
+                    }
+                }
+                if let Some(body) = &request_body {
+                    report_ignored_bodies(route, body, &ignored_bodies, diagnostics);
+                }
+                // An `#[openapi(request_body = "...")]` override takes precedence
+                if route.request_body.is_none() {
+                    route.request_body = request_body;
+                    route.request_body_required = request_body_required;
+                }
+                // An `#[openapi(response = "...")]` override takes precedence
+                if route.response_type.is_none() {
+                    route.response_type = response_type;
+                }
+                route.is_public = self.public_functions.contains(&handler_key);
+            } else {
+                self.function_index
+                    .report_unresolved(&self.routes[idx], &handler_key, diagnostics);
+            }
+        }
+    }
+
+    /// Parse a router expression and all routers pushed into it
+    fn parse_router(&mut self, expr: &Expr, prefix: &str) {
+        let Some((base_path, calls)) = self.decompose_router_chain(expr) else {
+            return;
+        };
+
+        // .path() applies to the whole router regardless of where it appears in the chain
+        let mut path = self.combine_paths(prefix, &base_path.unwrap_or_default());
+        for call in &calls {
+            if call.method == "path" {
+                if let Some(segment) = call
+                    .args
+                    .first()
+                    .and_then(|a| self.extract_string_literal(a))
+                {
+                    path = self.combine_paths(&path, &segment);
+                }
+            }
+        }
+
+        for call in &calls {
+            let method_name = call.method.to_string();
+            match method_name.as_str() {
+                "push" => match call.args.first() {
+                    Some(child) if self.decompose_router_chain(child).is_some() => {
+                        self.parse_router(child, &path);
+                    }
+                    Some(child) => {
+                        if let Some(name) = router_name(child) {
+                            self.mounts.push(RouterMount {
+                                parent: self.current_owner.clone(),
+                                child: name,
+                                prefix: path.clone(),
+                            });
+                        }
+                    }
+                    None => {}
+                },
+                _ => {
+                    if let Some(method) = self.parse_http_method(&method_name) {
+                        if let Some(handler) = call.args.first() {
+                            let handler_path = self.extract_handler_path(handler);
+                            let full_path = if path.is_empty() {
+                                "/".to_string()
+                            } else {
+                                path.clone()
+                            };
+                            let mut route =
+                                RouteInfo::for_handler(full_path.clone(), method, handler_path);
+                            route.parameters = self.extract_path_parameters(&full_path);
+                            self.routes.push(route);
+                            self.route_owners.push(self.current_owner.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Re-root routes of routers pushed by name below the path they are pushed at.
+    ///
+    /// Routes of a pushed router are emitted once per `.push()`. Routers that are never pushed
+    /// keep their paths.
+    fn resolve_router_mounts(&mut self) {
+        let mut known: HashSet<String> = self.route_owners.iter().flatten().cloned().collect();
+        known.extend(self.mounts.iter().filter_map(|m| m.parent.clone()));
+        // Ignore pushes of routers we know nothing about (e.g. defined in another crate)
+        let mounts: Vec<RouterMount> = self
+            .mounts
+            .iter()
+            .map(|m| RouterMount {
+                child: self.function_index.resolve(&m.child).unwrap_or(&m.child).to_string(),
+                ..m.clone()
+            })
+            .filter(|m| known.contains(&m.child))
+            .collect();
+        if mounts.is_empty() {
+            return;
+        }
+
+        let mounted: HashSet<&String> = mounts.iter().map(|m| &m.child).collect();
+        let mut roots: Vec<Option<&String>> = vec![None];
+        let mut unmounted: Vec<&String> =
+            known.iter().filter(|owner| !mounted.contains(owner)).collect();
+        unmounted.sort();
+        roots.extend(unmounted.into_iter().map(Some));
+
+        let mut resolved = Vec::new();
+        for root in roots {
+            self.emit_router_routes(root, "", &mounts, &mut Vec::new(), &mut resolved);
+        }
+
+        self.route_owners = vec![None; resolved.len()];
+        self.routes = resolved;
+    }
+
+    /// Emit all routes of `owner` and the routers pushed into it under `prefix`
+    fn emit_router_routes(
+        &self,
+        owner: Option<&String>,
+        prefix: &str,
+        mounts: &[RouterMount],
+        stack: &mut Vec<String>,
+        out: &mut Vec<RouteInfo>,
+    ) {
+        for (route, route_owner) in self.routes.iter().zip(&self.route_owners) {
+            if route_owner.as_ref() == owner {
+                let mut route = route.clone();
+                let path = self.combine_paths(prefix, &route.path);
+                route.path = if path.is_empty() { "/".to_string() } else { path };
+                route.parameters = self.extract_path_parameters(&route.path);
+                out.push(route);
+            }
+        }
+
+        for mount in mounts.iter().filter(|m| m.parent.as_ref() == owner) {
+            if stack.contains(&mount.child) {
+                warn!("Ignoring recursive router push of {}", mount.child);
+                continue;
+            }
+            stack.push(mount.child.clone());
+            let child_prefix = self.combine_paths(prefix, &mount.prefix);
+            self.emit_router_routes(Some(&mount.child), &child_prefix, mounts, stack, out);
+            stack.pop();
+        }
+    }
+
+    /// Split a router chain into its base path and method calls in source order.
+    ///
+    /// Returns `None` if the chain does not start with `Router::new()` or
+    /// `Router::with_path(...)`. The base path is `None` for `Router::new()`.
+    fn decompose_router_chain<'a>(
+        &self,
+        expr: &'a Expr,
+    ) -> Option<(Option<String>, Vec<&'a ExprMethodCall>)> {
+        let mut calls = Vec::new();
+        let mut current = expr;
+        while let Expr::MethodCall(method_call) = current {
+            calls.push(method_call);
+            current = &method_call.receiver;
+        }
+        calls.reverse();
+
+        let Expr::Call(call_expr) = current else {
+            return None;
+        };
+        let Expr::Path(func) = &*call_expr.func else {
+            return None;
+        };
+        let segments = &func.path.segments;
+        if segments.len() < 2 || segments[segments.len() - 2].ident != "Router" {
+            return None;
+        }
+
+        match segments.last()?.ident.to_string().as_str() {
+            "new" => Some((None, calls)),
+            "with_path" => {
+                let path = call_expr
+                    .args
+                    .first()
+                    .and_then(|a| self.extract_string_literal(a))?;
+                Some((Some(path), calls))
+            }
+            _ => None,
+        }
+    }
+
+    /// Combine a prefix and a Salvo path segment, converting `<id>` to `{id}`
+    fn combine_paths(&self, prefix: &str, path: &str) -> String {
+        let path = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(Self::convert_segment)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let prefix = prefix.trim_end_matches('/');
+        if path.is_empty() {
+            prefix.to_string()
+        } else {
+            format!("{}/{}", prefix, path)
+        }
+    }
+
+    /// Convert a Salvo path segment (`<id>`, `<id:num>`, `<**rest>`, `{id:num}`) to `{id}`
+    fn convert_segment(segment: &str) -> String {
+        let inner = segment
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .or_else(|| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')));
+
+        match inner {
+            Some(inner) => {
+                let name = inner
+                    .split(':')
+                    .next()
+                    .unwrap_or(inner)
+                    .trim_start_matches('*');
+                format!("{{{}}}", name)
+            }
+            None => segment.to_string(),
+        }
+    }
+
+    /// Parse HTTP method from a router method name
+    fn parse_http_method(&self, method: &str) -> Option<HttpMethod> {
+        match method {
+            "get" => Some(HttpMethod::Get),
+            "post" => Some(HttpMethod::Post),
+            "put" => Some(HttpMethod::Put),
+            "delete" => Some(HttpMethod::Delete),
+            "patch" => Some(HttpMethod::Patch),
+            "head" => Some(HttpMethod::Head),
+            "options" => Some(HttpMethod::Options),
+            _ => None,
+        }
+    }
+
+    /// Extract the handler path as written (`get_user`, `users::get_user`) from an expression
+    fn extract_handler_path(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Path(path_expr) if !path_expr.path.segments.is_empty() => path_expr
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::"),
+            _ => "unknown".to_string(),
+        }
+    }
+
+    /// Extract a string literal from an expression
+    fn extract_string_literal(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Lit(expr_lit) => {
+                if let Lit::Str(lit_str) = &expr_lit.lit {
+                    Some(lit_str.value())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Extract path parameters from a route path (e.g., "/users/{id}" -> Parameter{name: "id"})
+    fn extract_path_parameters(&self, path: &str) -> Vec<Parameter> {
+        path.split('/')
+            .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+            .map(|name| {
+                Parameter::new(
+                    name.to_string(),
+                    ParameterLocation::Path,
+                    TypeInfo::new("String".to_string()),
+                    true,
+                )
+            })
+            .collect()
+    }
+
+    /// Parse extractors from a handler signature
+    ///
+    /// Returns the extracted parameters, the request body type, whether the body is required,
+    /// and the types of any further body extractors, which are ignored in favour of the first.
+    fn parse_extractors(
+        &self,
+        fn_sig: &syn::Signature,
+        imports: Option<&ImportMap>,
+    ) -> (Vec<Parameter>, Option<TypeInfo>, bool, Vec<TypeInfo>) {
+        let mut parameters = Vec::new();
+        let mut request_body = None;
+        let mut request_body_required = true;
+        let mut ignored_bodies = Vec::new();
+
+        for input in &fn_sig.inputs {
+            let syn::FnArg::Typed(pat_type) = input else {
+                continue;
+            };
+            let (extractor_ty, is_optional) = peel_option(&pat_type.ty);
+            let syn::Type::Path(type_path) = extractor_ty else {
+                continue;
+            };
+            let Some(segment) = type_path.path.segments.last() else {
+                continue;
+            };
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                continue;
+            };
+            let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() else {
+                continue;
+            };
+            let arg_name = match &*pat_type.pat {
+                syn::Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                _ => continue,
+            };
+            if !extractor_allowed(extractor_ty, imports, &self.options, SALVO_CRATES) {
+                debug!("Skipping {} not imported from salvo", segment.ident);
+                continue;
+            }
+
+            match segment.ident.to_string().as_str() {
+                "JsonBody" => {
+                    if request_body.is_none() {
+                        request_body = Some(extract_type_info(inner_ty));
+                        request_body_required = !is_optional;
+                    } else {
+                        ignored_bodies.push(extract_type_info(inner_ty));
+                    }
+                }
+                "PathParam" => {
+                    parameters.push(Parameter::new(
+                        arg_name,
+                        ParameterLocation::Path,
+                        extract_type_info(inner_ty),
+                        true,
+                    ));
+                }
+                "QueryParam" => {
+                    // QueryParam<T, REQUIRED> defaults to required
+                    let required = !matches!(
+                        args.args.iter().nth(1),
+                        Some(syn::GenericArgument::Const(Expr::Lit(syn::ExprLit {
+                            lit: Lit::Bool(lit_bool),
+                            ..
+                        }))) if !lit_bool.value
+                    );
+                    parameters.push(Parameter::new(
+                        arg_name,
+                        ParameterLocation::Query,
+                        extract_type_info(inner_ty),
+                        required && !is_optional,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        (parameters, request_body, request_body_required, ignored_bodies)
+    }
+
+    /// Parse the response type from a handler signature
+    fn parse_response_type(&self, fn_sig: &syn::Signature) -> Option<TypeInfo> {
+        match &fn_sig.output {
+            syn::ReturnType::Type(_, ty) => self.parse_return_type(ty),
+            syn::ReturnType::Default => None,
+        }
+    }
+
+    /// Parse a return type of the form `Json<T>` or `Result<Json<T>, E>`
+    fn parse_return_type(&self, ty: &syn::Type) -> Option<TypeInfo> {
+        // `ApiResult<User>` -> `Result<Json<User>, ApiError>`
+        let ty = &*self.type_aliases.expand(ty);
+
+        if let Some((ok_ty, _)) = result_arms(ty) {
+            return self.parse_return_type(ok_ty);
+        }
+
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != "Json" {
+            return None;
+        }
+        match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(syn::GenericArgument::Type(inner_ty)) => Some(extract_type_info(inner_ty)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for SalvoVisitor {
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        // The outermost router chain is parsed as a whole, including pushed children
+        let expr = Expr::MethodCall(node.clone());
+        if self.decompose_router_chain(&expr).is_some() {
+            self.parse_router(&expr, "");
+            return;
+        }
+
+        // Continue visiting child nodes
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        self.module_tags.enter(&node.attrs);
+        self.function_index.enter_module(&node.ident);
+        syn::visit::visit_item_mod(self, node);
+        self.function_index.exit_module();
+        self.module_tags.exit();
+    }
+
+    fn visit_item_type(&mut self, node: &'ast syn::ItemType) {
+        self.type_aliases.insert(node);
+    }
+
+    fn visit_item_static(&mut self, node: &'ast syn::ItemStatic) {
+        // `static API: Lazy<Router> = Lazy::new(|| Router::new()...)` defines the router `API`
+        let saved_owner = self.current_owner.replace(node.ident.to_string());
+        syn::visit::visit_item_static(self, node);
+        self.current_owner = saved_owner;
+    }
+
+    fn visit_item_const(&mut self, node: &'ast syn::ItemConst) {
+        let saved_owner = self.current_owner.replace(node.ident.to_string());
+        syn::visit::visit_item_const(self, node);
+        self.current_owner = saved_owner;
+    }
+
+    fn visit_item_macro(&mut self, node: &'ast syn::ItemMacro) {
+        // Routers built in `lazy_static! { static ref ROUTER: Router = ...; }`
+        for (ident, init) in lazy_statics(&node.mac) {
+            let saved_owner = self.current_owner.replace(ident.to_string());
+            self.visit_expr(&init);
+            self.current_owner = saved_owner;
+        }
+        syn::visit::visit_item_macro(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        // Store function signatures for later analysis
+        debug!("Found function: {}", node.sig.ident);
+        // Routers built in the function belong to the router it returns
+        let mut owner = node.sig.ident.to_string();
+        // Entry points only build the router; they can never be handlers
+        if !is_entry_point(node) {
+            let key = self.function_index.insert(&node.sig);
+            owner = key.clone();
+            let openapi_attrs = parse_openapi_attributes(&node.attrs);
+            if !openapi_attrs.is_empty() {
+                self.openapi_attrs.insert(key.clone(), openapi_attrs);
+            }
+            if let Some(doc) = extract_doc_comment(&node.attrs) {
+                self.handler_docs.insert(key.clone(), doc);
+            }
+            if let Some(file_idx) = self.imports.len().checked_sub(1) {
+                self.function_files.insert(key.clone(), file_idx);
+            }
+            if matches!(node.vis, syn::Visibility::Public(_)) {
+                self.public_functions.insert(key.clone());
+            }
+            self.module_tags.record(&key);
+            self.functions.insert(key, node.sig.clone());
+        }
+
+        let saved_owner = self.current_owner.replace(owner);
+        syn::visit::visit_item_fn(self, node);
+        self.current_owner = saved_owner;
+    }
+}
+
+/// Name of the router pushed by `expr`, as written: the function of `users::router()`, or
+/// the static of `API` and `API.clone()`
+fn router_name(expr: &Expr) -> Option<String> {
+    let path_name = |path: &syn::Path| {
+        let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        (!segments.is_empty()).then(|| segments.join("::"))
+    };
+    match expr {
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(func) => path_name(&func.path),
+            _ => None,
+        },
+        // Statics are known by their own name
+        Expr::Path(path_expr) => Some(path_expr.path.segments.last()?.ident.to_string()),
+        Expr::MethodCall(method_call) if method_call.method == "clone" => {
+            router_name(&method_call.receiver)
+        }
+        Expr::Reference(reference) => router_name(&reference.expr),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn parse_code(code: &str) -> ParsedFile {
+        ParsedFile {
+            path: PathBuf::from("test.rs"),
+            syntax_tree: syn::parse_file(code).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_nested_routers() {
+        let code = r#"
+            use salvo::prelude::*;
+
+            #[handler]
+            async fn list_users() {}
+
+            #[handler]
+            async fn create_user() {}
+
+            #[handler]
+            async fn get_user() {}
+
+            fn router() -> Router {
+                Router::with_path("api").push(
+                    Router::with_path("users")
+                        .get(list_users)
+                        .post(create_user)
+                        .push(Router::with_path("<id>").get(get_user)),
+                )
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = SalvoExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 3);
+        let list = routes
+            .iter()
+            .find(|r| r.handler_name == "list_users")
+            .unwrap();
+        assert_eq!(list.path, "/api/users");
+        assert_eq!(list.method, HttpMethod::Get);
+
+        let create = routes
+            .iter()
+            .find(|r| r.handler_name == "create_user")
+            .unwrap();
+        assert_eq!(create.path, "/api/users");
+        assert_eq!(create.method, HttpMethod::Post);
+
+        let get = routes
+            .iter()
+            .find(|r| r.handler_name == "get_user")
+            .unwrap();
+        assert_eq!(get.path, "/api/users/{id}");
+        assert_eq!(get.parameters.len(), 1);
+        assert_eq!(get.parameters[0].name, "id");
+    }
+
+    #[test]
+    fn test_routers_pushed_by_name() {
+        let code = r#"
+            use salvo::prelude::*;
+
+            #[handler]
+            async fn list_users() {}
+
+            #[handler]
+            async fn get_user() {}
+
+            #[handler]
+            async fn stats() {}
+
+            #[handler]
+            async fn health() {}
+
+            mod users {
+                pub fn router() -> Router {
+                    Router::with_path("users")
+                        .get(list_users)
+                        .push(Router::with_path("<id>").get(get_user))
+                }
+            }
+
+            static ADMIN: Lazy<Router> = Lazy::new(|| Router::with_path("admin").get(stats));
+
+            fn router() -> Router {
+                Router::with_path("api")
+                    .push(users::router())
+                    .push(ADMIN.clone())
+                    .push(Router::with_path("health").get(health))
+            }
+        "#;
+
+        let routes = SalvoExtractor.extract_routes(&[parse_code(code)]);
+        let path = |handler: &str| {
+            routes.iter().find(|r| r.handler_name == handler).unwrap().path.as_str()
+        };
+
+        assert_eq!(routes.len(), 4);
+        assert_eq!(path("list_users"), "/api/users");
+        assert_eq!(path("get_user"), "/api/users/{id}");
+        assert_eq!(path("stats"), "/api/admin");
+        assert_eq!(path("health"), "/api/health");
+        let get_user = routes.iter().find(|r| r.handler_name == "get_user").unwrap();
+        assert_eq!(get_user.parameters[0].name, "id");
+    }
+
+    #[test]
+    fn test_path_method_and_typed_params() {
+        let code = r#"
+            use salvo::prelude::*;
+
+            #[handler]
+            async fn update_user(id: PathParam<i64>, body: JsonBody<UpdateUser>) -> Json<User> {
+                unimplemented!()
+            }
+
+            #[handler]
+            async fn search(q: QueryParam<String, false>) -> Result<Json<Vec<User>>, StatusError> {
+                unimplemented!()
+            }
+
+            fn router() -> Router {
+                Router::new()
+                    .push(Router::new().path("users/<id:num>").put(update_user))
+                    .push(Router::with_path("search").get(search))
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = SalvoExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        let update = routes
+            .iter()
+            .find(|r| r.handler_name == "update_user")
+            .unwrap();
+        assert_eq!(update.path, "/users/{id}");
+        assert_eq!(update.parameters.len(), 1);
+        assert_eq!(update.parameters[0].type_info.name, "i64");
+        assert_eq!(update.request_body.as_ref().unwrap().name, "UpdateUser");
+        assert_eq!(update.response_type.as_ref().unwrap().name, "User");
+
+        let search = routes.iter().find(|r| r.handler_name == "search").unwrap();
+        assert_eq!(search.parameters.len(), 1);
+        assert_eq!(search.parameters[0].location, ParameterLocation::Query);
+        assert!(!search.parameters[0].required);
+        assert!(search.response_type.as_ref().unwrap().is_vec);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::diagnostics::Diagnostics;
+use crate::extractor::functions::{is_entry_point, FunctionIndex};
+use crate::extractor::{
+    parse_openapi_attributes, ExtractOptions, HttpMethod, ModuleTags, OpenApiAttributes,
+    Parameter, ParameterLocation, RouteExtractor, RouteInfo, TypeInfo,
+};
+use crate::parser::{extract_doc_comment, ParsedFile};
+use log::debug;
+use syn::{visit::Visit, Expr, ExprMethodCall, Lit};
+
+/// Tide route extractor
+///
+/// Tide registers routes on a server with `app.at("/users").get(list_users)`, where `.at()`
+/// selects (and may extend) the route path and each method call registers a handler for it.
+/// Tide handlers take the whole `Request<State>`, so only the path and method are extracted;
+/// responses can be declared with `#[openapi(response = "...")]`.
+pub struct TideExtractor;
+
+impl RouteExtractor for TideExtractor {
+    fn extract_routes(&self, parsed_files: &[ParsedFile]) -> Vec<RouteInfo> {
+        self.extract_routes_with_diagnostics(parsed_files, &mut Diagnostics::new())
+    }
+
+    fn extract_routes_with_options(
+        &self,
+        parsed_files: &[ParsedFile],
+        _options: &ExtractOptions,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<RouteInfo> {
+        let mut visitor = TideVisitor::new();
+
+        // First pass: collect all route chains and function signatures from all files
+        for parsed_file in parsed_files {
+            visitor.function_index.enter_file(&parsed_file.path);
+            visitor.module_tags.enter(&parsed_file.syntax_tree.attrs);
+            visitor.visit_file(&parsed_file.syntax_tree);
+            visitor.module_tags.exit();
+        }
+
+        // After collecting routes and functions from all files, analyze handlers
+        visitor.analyze_handlers(diagnostics);
+
+        visitor.routes
+    }
+}
+
+/// Visitor for traversing the AST and finding Tide route registrations
+struct TideVisitor {
+    routes: Vec<RouteInfo>,
+    functions: std::collections::HashMap<String, syn::Signature>,
+    /// `#[openapi(...)]` overrides declared on handler functions
+    openapi_attrs: std::collections::HashMap<String, OpenApiAttributes>,
+    /// Doc comments of the visited functions
+    handler_docs: std::collections::HashMap<String, String>,
+    /// Functions declared `pub`
+    public_functions: std::collections::HashSet<String>,
+    /// Tags inherited from enclosing files and modules
+    module_tags: ModuleTags,
+    /// Module-qualified keys of the visited functions
+    function_index: FunctionIndex,
+}
+
+impl TideVisitor {
+    fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            functions: std::collections::HashMap::new(),
+            openapi_attrs: std::collections::HashMap::new(),
+            handler_docs: std::collections::HashMap::new(),
+            public_functions: std::collections::HashSet::new(),
+            module_tags: ModuleTags::default(),
+            function_index: FunctionIndex::default(),
+        }
+    }
+
+    /// Analyze routes with handler information
+    fn analyze_handlers(&mut self, diagnostics: &mut Diagnostics) {
+        for idx in 0..self.routes.len() {
+            let handler_path = self.routes[idx].handler_path.clone();
+            // Resolve the handler as written at the registration to a module-qualified key
+            let handler_key = self
+                .function_index
+                .resolve(&handler_path)
+                .map(str::to_string)
+                .unwrap_or(handler_path);
+
+            if let Some(attrs) = self.openapi_attrs.get(&handler_key) {
+                attrs.apply_to(&mut self.routes[idx]);
+            }
+            self.routes[idx].doc = self.handler_docs.get(&handler_key).cloned();
+            self.module_tags.apply_to(&handler_key, &mut self.routes[idx]);
+
+            if let Some(fn_sig) = self.functions.get(&handler_key) {
+                debug!("Found handler function: {}", handler_key);
+                let route = &mut self.routes[idx];
+                route.handler_path = handler_key.clone();
+                route.handler_resolved = true;
+                route.handler_location = self.function_index.location(&handler_key, fn_sig);
+                route.is_public = self.public_functions.contains(&handler_key);
+            } else {
+                self.function_index
+                    .report_unresolved(&self.routes[idx], &handler_key, diagnostics);
+            }
+        }
+    }
+
+    /// Parse a route chain such as `app.at("/users").get(list_users).post(create_user)`
+    fn parse_route_chain(&mut self, calls: &[&ExprMethodCall]) {
+        let mut path = String::new();
+        for call in calls {
+            let method_name = call.method.to_string();
+            if method_name == "at" {
+                if let Some(segment) = call
+                    .args
+                    .first()
+                    .and_then(|a| self.extract_string_literal(a))
+                {
+                    path = self.combine_paths(&path, &segment);
+                }
+                continue;
+            }
+
+            let Some(method) = self.parse_http_method(&method_name) else {
+                debug!("Skipping Tide route method .{}() on {}", method_name, path);
+                continue;
+            };
+            if let Some(handler) = call.args.first() {
+                let handler_path = self.extract_handler_path(handler);
+                let full_path = if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    path.clone()
+                };
+                let mut route = RouteInfo::for_handler(full_path.clone(), method, handler_path);
+                route.parameters = self.extract_path_parameters(&full_path);
+                self.routes.push(route);
+            }
+        }
+    }
+
+    /// Split a method call chain into its calls in source order, starting at the first
+    /// `.at("...")`.
+    ///
+    /// Returns `None` if the chain contains no `.at()` with a string literal path.
+    fn decompose_route_chain<'a>(
+        &self,
+        node: &'a ExprMethodCall,
+    ) -> Option<Vec<&'a ExprMethodCall>> {
+        let mut calls = vec![node];
+        let mut current = &*node.receiver;
+        while let Expr::MethodCall(method_call) = current {
+            calls.push(method_call);
+            current = &method_call.receiver;
+        }
+        calls.reverse();
+
+        let start = calls.iter().position(|call| {
+            call.method == "at"
+                && call
+                    .args
+                    .first()
+                    .and_then(|a| self.extract_string_literal(a))
+                    .is_some()
+        })?;
+        Some(calls.split_off(start))
+    }
+
+    /// Combine a prefix and a Tide path, converting `:id` and `*rest` segments to `{id}` and
+    /// `{rest}`
+    fn combine_paths(&self, prefix: &str, path: &str) -> String {
+        let path = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| match segment.strip_prefix([':', '*']) {
+                Some(name) if !name.is_empty() => format!("{{{}}}", name),
+                _ => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let prefix = prefix.trim_end_matches('/');
+        if path.is_empty() {
+            prefix.to_string()
+        } else {
+            format!("{}/{}", prefix, path)
+        }
+    }
+
+    /// Parse HTTP method from a route method name
+    fn parse_http_method(&self, method: &str) -> Option<HttpMethod> {
+        match method {
+            "get" => Some(HttpMethod::Get),
+            "post" => Some(HttpMethod::Post),
+            "put" => Some(HttpMethod::Put),
+            "delete" => Some(HttpMethod::Delete),
+            "patch" => Some(HttpMethod::Patch),
+            "head" => Some(HttpMethod::Head),
+            "options" => Some(HttpMethod::Options),
+            "trace" => Some(HttpMethod::Trace),
+            "connect" => Some(HttpMethod::Connect),
+            _ => None,
+        }
+    }
+
+    /// Extract the handler path as written (`get_user`, `users::get_user`) from an expression
+    fn extract_handler_path(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Path(path_expr) if !path_expr.path.segments.is_empty() => path_expr
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::"),
+            _ => "unknown".to_string(),
+        }
+    }
+
+    /// Extract a string literal from an expression
+    fn extract_string_literal(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Lit(expr_lit) => {
+                if let Lit::Str(lit_str) = &expr_lit.lit {
+                    Some(lit_str.value())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Extract path parameters from a route path (e.g., "/users/{id}" -> Parameter{name: "id"})
+    fn extract_path_parameters(&self, path: &str) -> Vec<Parameter> {
+        path.split('/')
+            .filter_map(|segment| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+            .map(|name| {
+                Parameter::new(
+                    name.to_string(),
+                    ParameterLocation::Path,
+                    TypeInfo::new("String".to_string()),
+                    true,
+                )
+            })
+            .collect()
+    }
+}
+
+impl<'ast> Visit<'ast> for TideVisitor {
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        // The outermost call of a `.at(...)` chain is parsed as a whole
+        if let Some(calls) = self.decompose_route_chain(node) {
+            self.parse_route_chain(&calls);
+            return;
+        }
+
+        // Continue visiting child nodes
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        self.module_tags.enter(&node.attrs);
+        self.function_index.enter_module(&node.ident);
+        syn::visit::visit_item_mod(self, node);
+        self.function_index.exit_module();
+        self.module_tags.exit();
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        // Store function signatures for later analysis
+        debug!("Found function: {}", node.sig.ident);
+        // Entry points only build the server; they can never be handlers
+        if !is_entry_point(node) {
+            let key = self.function_index.insert(&node.sig);
+            let openapi_attrs = parse_openapi_attributes(&node.attrs);
+            if !openapi_attrs.is_empty() {
+                self.openapi_attrs.insert(key.clone(), openapi_attrs);
+            }
+            if let Some(doc) = extract_doc_comment(&node.attrs) {
+                self.handler_docs.insert(key.clone(), doc);
+            }
+            if matches!(node.vis, syn::Visibility::Public(_)) {
+                self.public_functions.insert(key.clone());
+            }
+            self.module_tags.record(&key);
+            self.functions.insert(key, node.sig.clone());
+        }
+
+        // Continue visiting child nodes
+        syn::visit::visit_item_fn(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn parse_code(code: &str) -> ParsedFile {
+        ParsedFile {
+            path: PathBuf::from("test.rs"),
+            syntax_tree: syn::parse_file(code).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_at_chains() {
+        let code = r#"
+            use tide::Request;
+
+            pub async fn list_users(_req: Request<()>) -> tide::Result { unimplemented!() }
+            pub async fn create_user(_req: Request<()>) -> tide::Result { unimplemented!() }
+            pub async fn get_user(_req: Request<()>) -> tide::Result { unimplemented!() }
+            pub async fn download(_req: Request<()>) -> tide::Result { unimplemented!() }
+
+            #[async_std::main]
+            async fn main() -> tide::Result<()> {
+                let mut app = tide::new();
+                app.at("/users").get(list_users).post(create_user);
+                app.at("/users").at(":id").get(get_user);
+                app.at("/files/*path").with(Logger).get(download);
+                app.listen("127.0.0.1:8080").await?;
+                Ok(())
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let extractor = TideExtractor;
+        let routes = extractor.extract_routes(&[parsed]);
+
+        assert_eq!(routes.len(), 4);
+        let list = routes
+            .iter()
+            .find(|r| r.handler_name == "list_users")
+            .unwrap();
+        assert_eq!(list.path, "/users");
+        assert_eq!(list.method, HttpMethod::Get);
+        assert!(list.is_public);
+
+        let create = routes
+            .iter()
+            .find(|r| r.handler_name == "create_user")
+            .unwrap();
+        assert_eq!(create.path, "/users");
+        assert_eq!(create.method, HttpMethod::Post);
+
+        let get = routes
+            .iter()
+            .find(|r| r.handler_name == "get_user")
+            .unwrap();
+        assert_eq!(get.path, "/users/{id}");
+        assert_eq!(get.parameters.len(), 1);
+        assert_eq!(get.parameters[0].name, "id");
+
+        let download = routes
+            .iter()
+            .find(|r| r.handler_name == "download")
+            .unwrap();
+        assert_eq!(download.path, "/files/{path}");
+    }
+
+    #[test]
+    fn test_unknown_handler_diagnostic() {
+        let code = r#"
+            use tide::Request;
+
+            async fn health(_req: Request<()>) -> tide::Result { unimplemented!() }
+
+            fn routes(app: &mut tide::Server<()>) {
+                app.at("/health").get(health);
+                app.at("/").get(|_| async { Ok("Hello") });
+            }
+        "#;
+
+        let parsed = parse_code(code);
+        let mut diagnostics = Diagnostics::new();
+        let routes = TideExtractor.extract_routes_with_diagnostics(&[parsed], &mut diagnostics);
+
+        assert_eq!(routes.len(), 2);
+        let health = routes.iter().find(|r| r.path == "/health").unwrap();
+        assert_eq!(health.handler_path, "test::health");
+        assert!(!health.is_public);
+        assert_eq!(diagnostics.len(), 1);
+    }
+}
@@ -8,6 +8,8 @@
 //!
 //! - **Axum**: Extracts routes from `Router` definitions and method chains
 //! - **Actix-Web**: Extracts routes from route macros like `#[get]`, `#[post]`, etc.
+//! - **Salvo**: Extracts routes from `Router` trees built with `with_path` and `push`
+//! - **Tide**: Extracts routes from `app.at("...")` chains such as `.get()` and `.post()`
 //!
 //! # Architecture
 //!
@@ -80,3 +82,9 @@ pub mod schema_generator;
 pub mod openapi_builder;
 pub mod serializer;
 pub mod error;
+pub mod diagnostics;
+pub mod diff;
+pub mod validation;
+pub mod annotate;
+pub mod merge;
+pub mod report;
@@ -27,20 +27,10 @@
 //! openapi-from-source ./my-api-project -v
 //! ```
 
-mod cli;
-mod scanner;
-mod parser;
-mod detector;
-mod extractor;
-mod type_resolver;
-mod schema_generator;
-mod openapi_builder;
-mod serializer;
-mod error;
-
 use anyhow::Result;
 use clap::Parser;
 use log::info;
+use openapi_from_source::cli;
 
 fn main() -> Result<()> {
     // We need to parse args twice: once to get verbose flag, then again after logger init
@@ -54,8 +44,15 @@ fn main() -> Result<()> {
         log::LevelFilter::Info
     };
     
+    let write_style = match args_for_verbose.color {
+        cli::ColorChoice::Auto => env_logger::WriteStyle::Auto,
+        cli::ColorChoice::Always => env_logger::WriteStyle::Always,
+        cli::ColorChoice::Never => env_logger::WriteStyle::Never,
+    };
+    
     env_logger::Builder::from_default_env()
         .filter_level(log_level)
+        .write_style(write_style)
         .init();
 
     info!("Rust OpenAPI Generator starting...");
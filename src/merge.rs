@@ -0,0 +1,505 @@
+//! Merging a generated document into a hand-maintained base spec.
+//!
+//! Teams often keep the parts of a spec that cannot be inferred from code (API title and
+//! description, servers, security requirements) in a base document and let the generator
+//! contribute paths and schemas. [`merge`] combines the two section by section, with a
+//! [`MergeStrategy`] deciding which side wins when both define the same entry.
+
+use crate::openapi_builder::{Components, OpenApiDocument, PathItem, Tag};
+use std::collections::HashMap;
+use std::mem::take;
+
+/// Which document wins when both define the same entry of a section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precedence {
+    /// Keep the base document's entry
+    Base,
+    /// Replace it with the generated document's entry
+    Generated,
+}
+
+/// Per-section precedence used by [`merge`].
+///
+/// The default keeps the base document's metadata and lets generated paths and schemas
+/// replace stale base entries of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeStrategy {
+    /// Precedence for `openapi`, `info`, `servers` and `security`
+    pub info: Precedence,
+    /// Precedence for operations sharing the same URL and method, and webhook operations
+    /// sharing the same name and method
+    pub paths: Precedence,
+    /// Precedence for `components.schemas`, `components.responses` and `components.examples`
+    /// entries sharing the same name
+    pub schemas: Precedence,
+}
+
+impl Default for MergeStrategy {
+    fn default() -> Self {
+        Self {
+            info: Precedence::Base,
+            paths: Precedence::Generated,
+            schemas: Precedence::Generated,
+        }
+    }
+}
+
+/// Merge a generated document into a base document.
+///
+/// Paths and component entries are combined; entries defined by both documents are taken
+/// from the side the strategy gives precedence to. Paths are combined operation by operation,
+/// so a generated `GET /users` leaves a hand-written `POST /users` in place. With
+/// [`Precedence::Generated`] for `info`, the base's `servers` and `security` are only replaced
+/// if the generated document defines them.
+///
+/// Fields the generator never produces (`externalDocs`, `securitySchemes`, operation
+/// `callbacks`, `x-` extensions) survive the merge: a generated operation replacing a base
+/// one keeps the base operation's unmodeled fields and the extensions it does not set itself.
+pub fn merge(
+    base: OpenApiDocument,
+    mut generated: OpenApiDocument,
+    strategy: &MergeStrategy,
+) -> OpenApiDocument {
+    let (openapi, info, servers, security) = match strategy.info {
+        Precedence::Base => (base.openapi, base.info, base.servers, base.security),
+        Precedence::Generated => (
+            generated.openapi,
+            generated.info,
+            generated.servers.or(base.servers),
+            generated.security.or(base.security),
+        ),
+    };
+
+    if strategy.paths == Precedence::Generated {
+        for (path, item) in generated.paths.iter_mut() {
+            let Some(base_item) = base.paths.get(path) else {
+                continue;
+            };
+            for (method, base_operation) in base_item.operations() {
+                if let Some(operation) = item.operation_mut(method) {
+                    for (key, value) in &base_operation.extensions {
+                        operation.extensions.entry(key.clone()).or_insert_with(|| value.clone());
+                    }
+                    for (key, value) in &base_operation.extra {
+                        operation.extra.entry(key.clone()).or_insert_with(|| value.clone());
+                    }
+                }
+            }
+        }
+    }
+    let paths = merge_paths(base.paths, generated.paths, strategy.paths);
+    let webhooks = merge_paths(base.webhooks, generated.webhooks, strategy.paths);
+    let x_webhooks = merge_paths(base.x_webhooks, generated.x_webhooks, strategy.paths);
+    let tags = merge_tags(base.tags, generated.tags);
+
+    let base_components = base.components.unwrap_or_default();
+    let generated_components = generated.components.unwrap_or_default();
+    let components = Components {
+        schemas: merge_maps(
+            base_components.schemas,
+            generated_components.schemas,
+            strategy.schemas,
+        ),
+        responses: merge_maps(
+            base_components.responses,
+            generated_components.responses,
+            strategy.schemas,
+        ),
+        examples: merge_maps(
+            base_components.examples,
+            generated_components.examples,
+            strategy.schemas,
+        ),
+        extensions: merge_fields(
+            base_components.extensions,
+            generated_components.extensions,
+            strategy.schemas,
+        ),
+        extra: merge_fields(
+            base_components.extra,
+            generated_components.extra,
+            strategy.schemas,
+        ),
+    };
+    let components = if components.is_empty() {
+        None
+    } else {
+        Some(components)
+    };
+
+    OpenApiDocument {
+        openapi,
+        info,
+        servers,
+        paths,
+        webhooks,
+        x_webhooks,
+        components,
+        security,
+        tags,
+        extensions: merge_fields(base.extensions, generated.extensions, strategy.info),
+        extra: merge_fields(base.extra, generated.extra, strategy.info),
+    }
+}
+
+/// Combine two tag lists: the base tags keep their order and descriptions, generated tags the
+/// base does not list follow
+fn merge_tags(base: Option<Vec<Tag>>, generated: Option<Vec<Tag>>) -> Option<Vec<Tag>> {
+    let (mut base, generated) = match (base, generated) {
+        (Some(base), Some(generated)) => (base, generated),
+        (base, generated) => return base.or(generated),
+    };
+    for tag in generated {
+        if !base.iter().any(|t| t.name == tag.name) {
+            base.push(tag);
+        }
+    }
+    Some(base)
+}
+
+/// Combine the unmodeled fields or vendor extensions of two objects, resolving shared keys by
+/// precedence
+fn merge_fields<M>(base: M, generated: M, precedence: Precedence) -> M
+where
+    M: IntoIterator<Item = (String, serde_json::Value)> + Extend<(String, serde_json::Value)>,
+{
+    // Entries of the winning side are inserted last and replace those of the other side
+    let (mut fields, winner) = match precedence {
+        Precedence::Base => (generated, base),
+        Precedence::Generated => (base, generated),
+    };
+    fields.extend(winner);
+    fields
+}
+
+/// Combine two path maps operation by operation, resolving operations with the same method by
+/// precedence
+fn merge_paths(
+    base: HashMap<String, PathItem>,
+    generated: HashMap<String, PathItem>,
+    precedence: Precedence,
+) -> HashMap<String, PathItem> {
+    let mut paths = base;
+    for (path, mut item) in generated {
+        let item = match paths.remove(&path) {
+            Some(mut base_item) => {
+                let extra =
+                    merge_fields(take(&mut base_item.extra), take(&mut item.extra), precedence);
+                let (winner, loser) = match precedence {
+                    Precedence::Base => (base_item, item),
+                    Precedence::Generated => (item, base_item),
+                };
+                PathItem {
+                    get: winner.get.or(loser.get),
+                    post: winner.post.or(loser.post),
+                    put: winner.put.or(loser.put),
+                    patch: winner.patch.or(loser.patch),
+                    delete: winner.delete.or(loser.delete),
+                    options: winner.options.or(loser.options),
+                    head: winner.head.or(loser.head),
+                    trace: winner.trace.or(loser.trace),
+                    extra,
+                }
+            }
+            None => item,
+        };
+        paths.insert(path, item);
+    }
+    paths
+}
+
+/// Combine two optional maps, resolving shared keys by precedence
+fn merge_maps<V>(
+    base: Option<HashMap<String, V>>,
+    generated: Option<HashMap<String, V>>,
+    precedence: Precedence,
+) -> Option<HashMap<String, V>> {
+    match (base, generated) {
+        (None, None) => None,
+        (Some(map), None) | (None, Some(map)) => Some(map),
+        (Some(base), Some(generated)) => {
+            let (mut winner, loser) = match precedence {
+                Precedence::Base => (base, generated),
+                Precedence::Generated => (generated, base),
+            };
+            for (key, value) in loser {
+                winner.entry(key).or_insert(value);
+            }
+            Some(winner)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(yaml: &str) -> OpenApiDocument {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    const BASE: &str = r#"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 2.1.0
+servers:
+  - url: https://api.example.com
+  - url: https://{env}.example.com
+    variables:
+      env:
+        default: prod
+        enum: [prod, staging]
+paths:
+  /health:
+    get:
+      responses:
+        '200':
+          description: OK
+  /users:
+    get:
+      summary: Hand-written
+      responses:
+        '200':
+          description: OK
+security:
+  - bearer: []
+tags:
+  - name: users
+    description: User accounts
+"#;
+
+    const GENERATED: &str = r#"
+openapi: 3.0.0
+info:
+  title: Generated API
+  version: 1.0.0
+paths:
+  /users:
+    get:
+      summary: GET /users
+      responses:
+        '200':
+          description: Successful response
+components:
+  schemas:
+    User:
+      type: object
+tags:
+  - name: admin
+  - name: users
+"#;
+
+    #[test]
+    fn test_merge_keeps_base_metadata_by_default() {
+        let merged = merge(document(BASE), document(GENERATED), &MergeStrategy::default());
+
+        assert_eq!(merged.openapi, "3.0.3");
+        assert_eq!(merged.info.title, "Petstore");
+        let servers = merged.servers.unwrap();
+        assert_eq!(servers[0].url, "https://api.example.com");
+        let env = &servers[1].variables.as_ref().unwrap()["env"];
+        assert_eq!(env.default, "prod");
+        assert_eq!(
+            env.enum_values,
+            Some(vec!["prod".to_string(), "staging".to_string()])
+        );
+        assert!(merged.security.is_some());
+
+        assert_eq!(merged.paths.len(), 2);
+        let users = merged.paths["/users"].get.as_ref().unwrap();
+        assert_eq!(users.summary.as_deref(), Some("GET /users"));
+        assert!(merged.components.unwrap().schemas.unwrap().contains_key("User"));
+
+        // Hand-written tags keep their descriptions; new generated tags follow
+        let tags = merged.tags.unwrap();
+        let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["users", "admin"]);
+        assert_eq!(tags[0].description.as_deref(), Some("User accounts"));
+    }
+
+    #[test]
+    fn test_merge_with_generated_info_and_base_paths() {
+        let strategy = MergeStrategy {
+            info: Precedence::Generated,
+            paths: Precedence::Base,
+            ..Default::default()
+        };
+        let merged = merge(document(BASE), document(GENERATED), &strategy);
+
+        assert_eq!(merged.info.title, "Generated API");
+        // The generated document has no servers, so the base's are kept
+        assert!(merged.servers.is_some());
+
+        let users = merged.paths["/users"].get.as_ref().unwrap();
+        assert_eq!(users.summary.as_deref(), Some("Hand-written"));
+    }
+
+    #[test]
+    fn test_merge_combines_operations_of_a_path() {
+        let base = document(
+            r#"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 2.1.0
+paths:
+  /users:
+    get:
+      summary: Hand-written list
+      responses:
+        '200':
+          description: OK
+    post:
+      summary: Hand-written create
+      responses:
+        '201':
+          description: Created
+"#,
+        );
+
+        let merged = merge(base.clone(), document(GENERATED), &MergeStrategy::default());
+        let users = &merged.paths["/users"];
+        assert_eq!(users.get.as_ref().unwrap().summary.as_deref(), Some("GET /users"));
+        // Methods only the base defines are kept
+        assert_eq!(
+            users.post.as_ref().unwrap().summary.as_deref(),
+            Some("Hand-written create")
+        );
+
+        let strategy = MergeStrategy {
+            paths: Precedence::Base,
+            ..Default::default()
+        };
+        let merged = merge(base, document(GENERATED), &strategy);
+        let users = &merged.paths["/users"];
+        assert_eq!(
+            users.get.as_ref().unwrap().summary.as_deref(),
+            Some("Hand-written list")
+        );
+        assert!(users.post.is_some());
+    }
+
+    #[test]
+    fn test_merge_preserves_unmodeled_fields() {
+        let base = document(
+            r#"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 2.1.0
+x-logo: https://example.com/logo.png
+paths:
+  /users:
+    post:
+      summary: Hand-written
+      callbacks:
+        created:
+          '{$request.body#/callback}':
+            post:
+              responses:
+                200:
+                  description: OK
+      x-rate-limit: 10
+      responses:
+        201:
+          description: Created
+components:
+  securitySchemes:
+    bearer:
+      type: http
+      scheme: bearer
+"#,
+        );
+        let generated = document(
+            r#"
+openapi: 3.0.0
+info:
+  title: Generated API
+  version: 1.0.0
+paths:
+  /users:
+    post:
+      summary: POST /users
+      responses:
+        '201':
+          description: Created
+"#,
+        );
+        let merged = merge(base, generated, &MergeStrategy::default());
+
+        assert_eq!(merged.extensions["x-logo"], "https://example.com/logo.png");
+        let operation = merged.paths["/users"].post.as_ref().unwrap();
+        assert_eq!(operation.summary.as_deref(), Some("POST /users"));
+        assert_eq!(operation.extensions["x-rate-limit"], 10);
+        assert!(operation.extra.contains_key("callbacks"));
+        let components = merged.components.as_ref().unwrap();
+        assert_eq!(components.extra["securitySchemes"]["bearer"]["scheme"], "bearer");
+
+        let yaml = serde_yaml::to_string(&merged).unwrap();
+        assert!(yaml.contains("securitySchemes:"));
+        assert!(yaml.contains("x-rate-limit: 10"));
+    }
+
+    #[test]
+    fn test_merge_round_trips_fields_of_nested_objects() {
+        let base = document(
+            r#"
+openapi: 3.0.3
+info:
+  title: Petstore
+  version: 2.1.0
+  contact:
+    email: api@example.com
+  license:
+    name: MIT
+paths:
+  /pets/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: string
+        example: rex
+    get:
+      responses:
+        '200':
+          description: OK
+          headers:
+            X-Rate-Limit:
+              schema:
+                type: integer
+          links:
+            owner:
+              operationId: getOwner
+components:
+  schemas:
+    Code:
+      type: string
+      pattern: '^[A-Z]{3}$'
+    Pet:
+      type: object
+      properties:
+        code:
+          type: string
+          pattern: '^[A-Z]{3}$'
+"#,
+        );
+        let merged = merge(base, document(GENERATED), &MergeStrategy::default());
+        let yaml = serde_yaml::to_string(&merged).unwrap();
+        let written: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(written["info"]["contact"]["email"], "api@example.com");
+        assert_eq!(written["info"]["license"]["name"], "MIT");
+        let pet = &written["paths"]["/pets/{id}"];
+        assert_eq!(pet["parameters"][0]["name"], "id");
+        assert_eq!(pet["parameters"][0]["example"], "rex");
+        let response = &pet["get"]["responses"]["200"];
+        assert_eq!(response["headers"]["X-Rate-Limit"]["schema"]["type"], "integer");
+        assert_eq!(response["links"]["owner"]["operationId"], "getOwner");
+        let schemas = &written["components"]["schemas"];
+        assert_eq!(schemas["Code"]["pattern"], "^[A-Z]{3}$");
+        assert_eq!(schemas["Pet"]["properties"]["code"]["pattern"], "^[A-Z]{3}$");
+    }
+}
@@ -1,8 +1,9 @@
-use crate::extractor::{HttpMethod, RouteInfo};
-use crate::schema_generator::{Schema, SchemaGenerator};
-use log::debug;
+use crate::extractor::{HttpMethod, RouteInfo, TypeInfo, WebhookInfo, PATH_EXTRACTOR_PARAM};
+use crate::schema_generator::{Property, Schema, SchemaGenerator};
+use log::{debug, warn};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
 
 /// OpenAPI document builder
 pub struct OpenApiBuilder {
@@ -10,8 +11,42 @@ pub struct OpenApiBuilder {
     info: Info,
     /// Paths collection (URL path -> PathItem)
     paths: HashMap<String, PathItem>,
+    /// Webhooks (event name -> PathItem with a POST operation)
+    webhooks: HashMap<String, PathItem>,
     /// Components section (schemas, etc.)
     components: Components,
+    /// Shared error responses registered with `with_error_response`
+    error_responses: Vec<ErrorResponse>,
+    /// Directory `#[openapi(request_example_file = "...")]` paths are relative to
+    examples_root: PathBuf,
+    /// Reference example files with `externalValue` instead of inlining their content
+    external_examples: bool,
+    /// Success status overrides for routes without an explicit status, by method
+    default_statuses: HashMap<HttpMethod, u16>,
+    /// Success response description overrides, by status
+    response_descriptions: HashMap<u16, String>,
+    /// Keep component schemas no operation references when building
+    keep_unused_schemas: bool,
+    /// Responses documented on every operation: status -> description
+    global_responses: Vec<(u16, String)>,
+    /// Parameters documented on every operation, such as headers required by middleware
+    global_parameters: Vec<Parameter>,
+    /// List the top-level tags alphabetically instead of in order of first use
+    sort_tags: bool,
+    /// Servers listed at the top level
+    servers: Vec<Server>,
+    /// Document a HEAD operation mirroring every GET operation
+    auto_head: bool,
+}
+
+/// A reusable error response emitted under `components.responses`
+struct ErrorResponse {
+    /// HTTP status code the response is documented under
+    status: u16,
+    /// Component name (e.g. "NotFound")
+    name: String,
+    /// Type of the response body
+    type_info: TypeInfo,
 }
 
 /// OpenAPI Info object
@@ -24,9 +59,54 @@ pub struct Info {
     /// API description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Vendor extensions (`x-internal`, `x-rate-limit`)
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Fields this crate does not model (`contact`, `license`, `termsOfService`), kept so
+    /// that a document read from disk is written back without losing them
+    #[serde(flatten, deserialize_with = "deserialize_extra")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// OpenAPI Server object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Server {
+    /// Server URL
+    pub url: String,
+    /// Server description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Values substituted for the `{name}` placeholders of the URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<BTreeMap<String, ServerVariable>>,
+}
+
+/// OpenAPI Server Variable object: the values of a `{name}` placeholder in a server URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerVariable {
+    /// Allowed values
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<String>>,
+    /// Value used when the client does not pick one
+    pub default: String,
+    /// Variable description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// OpenAPI Tag object, listed at the top level to order and describe operation groups
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+    /// Tag name, as used by operations
+    pub name: String,
+    /// Tag description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
-/// OpenAPI PathItem object - represents all operations for a single path
+/// OpenAPI PathItem object - represents all operations for a single path.
+///
+/// Operations serialize in the conventional reading order GET, POST, PUT, PATCH, DELETE.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathItem {
     /// GET operation
@@ -38,18 +118,25 @@ pub struct PathItem {
     /// PUT operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub put: Option<Operation>,
-    /// DELETE operation
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub delete: Option<Operation>,
     /// PATCH operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub patch: Option<Operation>,
+    /// DELETE operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
     /// OPTIONS operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<Operation>,
     /// HEAD operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub head: Option<Operation>,
+    /// TRACE operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Operation>,
+    /// Fields this crate does not model (path-level `parameters`, `summary`, `servers`,
+    /// vendor extensions), kept when a document is read from disk and written back
+    #[serde(flatten, deserialize_with = "deserialize_unmodeled")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// OpenAPI Operation object - represents a single API operation
@@ -64,6 +151,9 @@ pub struct Operation {
     /// Operation ID
     #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
     pub operation_id: Option<String>,
+    /// Tags for grouping operations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
     /// Parameters (path, query, header)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<Parameter>>,
@@ -72,6 +162,13 @@ pub struct Operation {
     pub request_body: Option<RequestBody>,
     /// Responses
     pub responses: HashMap<String, Response>,
+    /// Vendor extensions, from `#[openapi(extension("x-rate-limit" = "100"))]`
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Fields this crate does not model (`callbacks`, `security`, ...), kept so that a
+    /// document read from disk is written back without losing them
+    #[serde(flatten, deserialize_with = "deserialize_extra")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// OpenAPI Parameter object
@@ -89,6 +186,19 @@ pub struct Parameter {
     /// Parameter description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Whether the parameter is deprecated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// Serialization style, `form` for multi-value query parameters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// Whether array values are repeated, one `name=value` pair each (`?tags=a&tags=b`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+    /// Fields this crate does not model (`example`, `allowEmptyValue`, vendor extensions),
+    /// kept when a document is read from disk and written back
+    #[serde(flatten, deserialize_with = "deserialize_unmodeled")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// OpenAPI RequestBody object
@@ -108,24 +218,80 @@ pub struct RequestBody {
 pub struct MediaType {
     /// Schema for this media type
     pub schema: Schema,
+    /// Example payload, inlined from an example file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    /// Named examples: example files, or references to `components.examples`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<HashMap<String, Example>>,
+}
+
+/// OpenAPI Example object: an inline value, an external example file, or a reference to a
+/// shared example in `components.examples`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Example {
+    /// Reference to a shared example in `components.examples`
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Short description of the example
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Example payload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    /// URL or relative path of the example file
+    #[serde(rename = "externalValue", skip_serializing_if = "Option::is_none")]
+    pub external_value: Option<String>,
 }
 
 /// OpenAPI Response object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Response {
-    /// Response description
+    /// Reference to a shared response in `components.responses`
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Response description, required by OpenAPI; the status reason phrase unless known
+    #[serde(default)]
     pub description: String,
     /// Response content
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<HashMap<String, MediaType>>,
+    /// Fields this crate does not model (`headers`, `links`, vendor extensions), kept when a
+    /// document is read from disk and written back
+    #[serde(flatten, deserialize_with = "deserialize_unmodeled")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// OpenAPI Components object
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Components {
     /// Schema definitions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schemas: Option<HashMap<String, Schema>>,
+    /// Shared response definitions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<HashMap<String, Response>>,
+    /// Shared, named example payloads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<HashMap<String, Example>>,
+    /// Vendor extensions (`x-` fields)
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Sections this crate does not model (`securitySchemes`, `parameters`, ...), kept so
+    /// that a document read from disk is written back without losing them
+    #[serde(flatten, deserialize_with = "deserialize_extra")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Components {
+    /// Whether no section is present
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_none()
+            && self.responses.is_none()
+            && self.examples.is_none()
+            && self.extensions.is_empty()
+            && self.extra.is_empty()
+    }
 }
 
 /// Complete OpenAPI document
@@ -135,11 +301,118 @@ pub struct OpenApiDocument {
     pub openapi: String,
     /// API info
     pub info: Info,
-    /// API paths
+    /// Servers hosting the API, added with `with_server` (`--server`, `--base-url-from-env`)
+    /// or taken from a base spec
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+    /// API paths, serialized in alphabetical order
+    #[serde(serialize_with = "serialize_sorted")]
     pub paths: HashMap<String, PathItem>,
+    /// Events the API sends to subscribers (OpenAPI 3.1), serialized in alphabetical order;
+    /// only read from existing specs
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub webhooks: HashMap<String, PathItem>,
+    /// Generated webhooks, in an `x-webhooks` extension as OpenAPI 3.0 has no `webhooks`
+    /// section, serialized in alphabetical order
+    #[serde(
+        rename = "x-webhooks",
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
+    pub x_webhooks: HashMap<String, PathItem>,
     /// Components (schemas, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
+    /// Security requirements (never generated, preserved when merging with a base spec)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<HashMap<String, Vec<String>>>>,
+    /// Tags used by the operations, in presentation order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+    /// Vendor extensions (`x-` fields)
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Fields this crate does not model (`externalDocs`, `jsonSchemaDialect`, ...), kept so
+    /// that a document read from disk is written back without losing them
+    #[serde(flatten, deserialize_with = "deserialize_extra")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Deserialize unmodeled fields other than vendor extensions, accepting the non-string keys
+/// YAML allows (`200:` in the responses of a callback) by turning them into strings as JSON
+/// requires
+pub(crate) fn deserialize_extra<'de, D>(
+    deserializer: D,
+) -> Result<serde_json::Map<String, serde_json::Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    unmodeled_fields(deserializer, |key| !key.starts_with("x-"))
+}
+
+/// Deserialize every unmodeled field of an object that has no separate map for its vendor
+/// extensions
+pub(crate) fn deserialize_unmodeled<'de, D>(
+    deserializer: D,
+) -> Result<serde_json::Map<String, serde_json::Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    unmodeled_fields(deserializer, |_| true)
+}
+
+/// Collect the unmodeled fields whose key passes `keep` as JSON values
+fn unmodeled_fields<'de, D>(
+    deserializer: D,
+    keep: fn(&str) -> bool,
+) -> Result<serde_json::Map<String, serde_json::Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let fields = BTreeMap::<String, serde_yaml::Value>::deserialize(deserializer)?;
+    fields
+        .into_iter()
+        .filter(|(key, _)| keep(key))
+        .map(|(key, value)| {
+            serde_json::to_value(value)
+                .map(|value| (key, value))
+                .map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
+
+/// Deserialize the vendor extensions (`x-` fields) of an object; sibling `#[serde(flatten)]`
+/// maps each see every unmodeled field, and only this one keeps the `x-` ones
+pub(crate) fn deserialize_extensions<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, serde_json::Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let fields = BTreeMap::<String, serde_yaml::Value>::deserialize(deserializer)?;
+    fields
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("x-"))
+        .map(|(key, value)| {
+            serde_json::to_value(value)
+                .map(|value| (key, value))
+                .map_err(serde::de::Error::custom)
+        })
+        .collect()
+}
+
+/// Serialize a map with its keys in alphabetical order
+fn serialize_sorted<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
 }
 
 impl OpenApiBuilder {
@@ -151,9 +424,23 @@ impl OpenApiBuilder {
                 title: "Generated API".to_string(),
                 version: "1.0.0".to_string(),
                 description: Some("API documentation generated from Rust code".to_string()),
+                extensions: BTreeMap::new(),
+                extra: Default::default(),
             },
             paths: HashMap::new(),
-            components: Components { schemas: None },
+            webhooks: HashMap::new(),
+            components: Components::default(),
+            error_responses: Vec::new(),
+            examples_root: PathBuf::new(),
+            external_examples: false,
+            default_statuses: HashMap::new(),
+            response_descriptions: HashMap::new(),
+            keep_unused_schemas: false,
+            global_responses: Vec::new(),
+            global_parameters: Vec::new(),
+            sort_tags: false,
+            servers: Vec::new(),
+            auto_head: false,
         }
     }
 
@@ -163,7 +450,126 @@ impl OpenApiBuilder {
             title,
             version,
             description,
+            extensions: BTreeMap::new(),
+            extra: Default::default(),
+        };
+        self
+    }
+
+    /// Set the API title, keeping the rest of the info section
+    pub fn with_title(mut self, title: String) -> Self {
+        self.info.title = title;
+        self
+    }
+
+    /// Set the API version, keeping the rest of the info section
+    pub fn with_version(mut self, version: String) -> Self {
+        self.info.version = version;
+        self
+    }
+
+    /// Set the API description, keeping the rest of the info section
+    pub fn with_description(mut self, description: String) -> Self {
+        self.info.description = Some(description);
+        self
+    }
+
+    /// Resolve `#[openapi(request_example_file = "...")]` paths relative to `root` (the
+    /// project directory). With `external`, request bodies reference the file as an
+    /// `externalValue` example instead of inlining its JSON content.
+    pub fn with_example_files(mut self, root: PathBuf, external: bool) -> Self {
+        self.examples_root = root;
+        self.external_examples = external;
+        self
+    }
+
+    /// Document the success response of `method` routes under `status` when the handler does
+    /// not declare one.
+    ///
+    /// Without an override, POST routes use `201`, DELETE routes `204` (`200` if they return
+    /// a body) and all others `200`.
+    pub fn with_default_status(mut self, method: HttpMethod, status: u16) -> Self {
+        self.default_statuses.insert(method, status);
+        self
+    }
+
+    /// Describe success responses documented under `status` with `description` instead of
+    /// the default ("Successful response", "Created" for `201`, "No content" for `204`)
+    pub fn with_response_description(mut self, status: u16, description: String) -> Self {
+        self.response_descriptions.insert(status, description);
+        self
+    }
+
+    /// Keep component schemas that no operation references, directly or through other
+    /// schemas, instead of dropping them with [`prune_unreferenced`] when building
+    pub fn with_unused_schemas(mut self, keep: bool) -> Self {
+        self.keep_unused_schemas = keep;
+        self
+    }
+
+    /// Document a response without body under `status` on every operation added afterwards,
+    /// e.g. a standard `401 Unauthorized`. Responses the route documents itself under the same
+    /// status take precedence.
+    pub fn with_global_response(mut self, status: u16, description: String) -> Self {
+        self.global_responses.push((status, description));
+        self
+    }
+
+    /// Document `parameter` on every operation, e.g. an `X-API-Version` header a middleware
+    /// layer requires of every request, which no handler declares. Parameters an operation
+    /// documents itself under the same name and location take precedence.
+    pub fn with_global_parameter(mut self, parameter: Parameter) -> Self {
+        self.global_parameters.push(parameter);
+        self
+    }
+
+    /// Register a shared error response under `components.responses`.
+    ///
+    /// Operations whose handlers return `Result<_, E>` with a matching error type reference
+    /// the response as `$ref: "#/components/responses/{name}"` under `status`.
+    pub fn with_error_response(mut self, status: u16, name: String, type_info: TypeInfo) -> Self {
+        self.error_responses.push(ErrorResponse {
+            status,
+            name,
+            type_info,
+        });
+        self
+    }
+
+    /// Register a named example payload under `components.examples`.
+    ///
+    /// Routes naming it with `#[openapi(request_example = "...")]` reference it from their
+    /// request body as `$ref: "#/components/examples/{name}"`.
+    pub fn with_example(mut self, name: String, value: serde_json::Value) -> Self {
+        let example = Example {
+            value: Some(value),
+            ..Example::default()
         };
+        self.components
+            .examples
+            .get_or_insert_with(HashMap::new)
+            .insert(name, example);
+        self
+    }
+
+    /// List the top-level tags alphabetically. By default they are listed in order of first use,
+    /// walking the paths alphabetically.
+    pub fn with_sorted_tags(mut self, sort: bool) -> Self {
+        self.sort_tags = sort;
+        self
+    }
+
+    /// Document a HEAD operation for every path with a GET operation and no HEAD route of its
+    /// own: the GET operation with `_head` appended to its operation ID and without response
+    /// bodies, as servers answer HEAD with the headers of the corresponding GET
+    pub fn with_auto_head(mut self, enabled: bool) -> Self {
+        self.auto_head = enabled;
+        self
+    }
+
+    /// List `server` under the top-level `servers`, after the servers added before
+    pub fn with_server(mut self, server: Server) -> Self {
+        self.servers.push(server);
         self
     }
 
@@ -171,6 +577,15 @@ impl OpenApiBuilder {
     pub fn add_route(&mut self, route: &RouteInfo, schema_gen: &mut SchemaGenerator) {
         debug!("Adding route: {} {}", route.method_str(), route.path);
 
+        // OpenAPI path items have no slot for CONNECT
+        if route.method == HttpMethod::Connect {
+            warn!(
+                "Skipping CONNECT {} ({}): CONNECT is not an OpenAPI operation",
+                route.path, route.handler_name
+            );
+            return;
+        }
+
         // Convert path parameters from :param to {param} format
         let openapi_path = Self::convert_path_format(&route.path);
 
@@ -178,73 +593,260 @@ impl OpenApiBuilder {
         let parameters = if route.parameters.is_empty() {
             None
         } else {
-            let params: Vec<Parameter> = route
+            let mut params: Vec<Parameter> = Vec::new();
+            for param_schema in route
                 .parameters
                 .iter()
-                .map(|p| {
-                    let param_schema = schema_gen.generate_parameter_schema(p);
-                    Parameter {
-                        name: param_schema.name,
-                        location: param_schema.location,
-                        required: param_schema.required,
-                        schema: param_schema.schema,
-                        description: None,
-                    }
-                })
-                .collect();
+                .flat_map(|p| schema_gen.generate_parameter_schemas(p))
+            {
+                let parameter = Parameter {
+                    name: param_schema.name,
+                    location: param_schema.location,
+                    required: param_schema.required,
+                    schema: param_schema.schema,
+                    description: param_schema.description,
+                    deprecated: param_schema.deprecated,
+                    style: param_schema.style,
+                    explode: param_schema.explode,
+                    extra: Default::default(),
+                };
+                // Fields of a `Path<T>` struct describe the URL parameters of the same name
+                match params
+                    .iter_mut()
+                    .find(|p| p.name == parameter.name && p.location == parameter.location)
+                {
+                    Some(existing) => *existing = parameter,
+                    None => params.push(parameter),
+                }
+            }
+            // A `Path<T>` extractor of a single value (`Path<u64>`) is not a parameter of its
+            // own; it types the URL's parameter when there is exactly one
+            if let Some(index) = params
+                .iter()
+                .position(|p| p.name == PATH_EXTRACTOR_PARAM && p.location == "path")
+            {
+                let extracted = params.remove(index);
+                let mut url_params = params.iter_mut().filter(|p| p.location == "path");
+                if let (Some(only), None) = (url_params.next(), url_params.next()) {
+                    only.schema = extracted.schema;
+                }
+            }
             Some(params)
         };
 
         // Generate request body if present
         let request_body = route.request_body.as_ref().map(|type_info| {
             let schema = schema_gen.generate_schema(type_info);
+            let (mut example, mut examples) = match &route.request_example_file {
+                Some(file) => self.request_example(file),
+                None => (None, None),
+            };
+            if !route.request_examples.is_empty() {
+                let examples = examples.get_or_insert_with(HashMap::new);
+                // `example` and `examples` are mutually exclusive; an inlined example file
+                // joins the named examples
+                if let Some(value) = example.take() {
+                    let name = route.request_example_file.as_deref().map_or("example", |file| {
+                        std::path::Path::new(file)
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .unwrap_or("example")
+                    });
+                    examples.insert(
+                        name.to_string(),
+                        Example {
+                            value: Some(value),
+                            ..Example::default()
+                        },
+                    );
+                }
+                examples.extend(self.example_refs(route));
+            }
             RequestBody {
                 description: Some("Request body".to_string()),
-                required: true,
-                content: {
-                    let mut content = HashMap::new();
-                    content.insert(
-                        "application/json".to_string(),
-                        MediaType { schema },
-                    );
-                    content
-                },
+                required: route.request_body_required,
+                // Every declared media type shares the body schema
+                content: media_types(&route.consumes)
+                    .map(|media_type| {
+                        let media = MediaType {
+                            schema: raw_body_schema(schema.clone(), media_type),
+                            example: example.clone(),
+                            examples: examples.clone(),
+                        };
+                        (media_type.to_string(), media)
+                    })
+                    .collect(),
             }
         });
 
         // Generate response
+        let status = self.success_status(route);
+        // Rustdoc `# Returns` and `# Errors` sections of the handler describe its responses
+        let doc_returns = route.doc.as_deref().and_then(|doc| doc_section(doc, "Returns"));
+        let doc_errors = route.doc.as_deref().and_then(|doc| doc_section(doc, "Errors"));
         let response = if let Some(response_type) = &route.response_type {
             let schema = schema_gen.generate_schema(response_type);
             Response {
-                description: "Successful response".to_string(),
+                reference: None,
+                description: doc_returns.unwrap_or_else(|| self.response_description(status)),
+                content: Some({
+                    let mut content = HashMap::new();
+                    content.insert(
+                        route.produces.as_deref().unwrap_or("application/json").to_string(),
+                        MediaType {
+                            schema,
+                            example: None,
+                            examples: None,
+                        },
+                    );
+                    content
+                }),
+                extra: Default::default(),
+            }
+        } else if route.serves_files {
+            // Static files served by a service such as `ServeDir`
+            let schema = Schema {
+                schema_type: Some("string".to_string()),
+                format: Some("binary".to_string()),
+                ..Default::default()
+            };
+            Response {
+                reference: None,
+                description: "Static file".to_string(),
                 content: Some({
                     let mut content = HashMap::new();
                     content.insert(
-                        "application/json".to_string(),
-                        MediaType { schema },
+                        route
+                            .produces
+                            .as_deref()
+                            .unwrap_or("application/octet-stream")
+                            .to_string(),
+                        MediaType {
+                            schema,
+                            example: None,
+                            examples: None,
+                        },
                     );
                     content
                 }),
+                extra: Default::default(),
             }
         } else {
             // Default response when type is unknown
             Response {
-                description: "Successful response".to_string(),
+                reference: None,
+                description: doc_returns.unwrap_or_else(|| self.response_description(status)),
                 content: None,
+                extra: Default::default(),
             }
         };
 
         let mut responses = HashMap::new();
-        responses.insert("200".to_string(), response);
+        responses.insert(status.to_string(), response);
+
+        // Reference shared error responses matching the handler's error type
+        if let Some(error_type) = route.response_type.as_ref().and_then(|t| t.err_type()) {
+            for error in self
+                .error_responses
+                .iter()
+                .filter(|e| e.type_info.name == error_type.name)
+            {
+                responses.insert(
+                    error.status.to_string(),
+                    Response {
+                        reference: Some(format!("#/components/responses/{}", error.name)),
+                        description: reason_phrase(&error.status.to_string()).to_string(),
+                        content: None,
+                        extra: Default::default(),
+                    },
+                );
+            }
+        }
+
+        // Responses registered programmatically replace inferred ones
+        for additional in &route.additional_responses {
+            let content = additional.body.as_ref().map(|body| {
+                let mut content = HashMap::new();
+                content.insert(
+                    "application/json".to_string(),
+                    MediaType {
+                        schema: schema_gen.generate_schema(body),
+                        example: None,
+                        examples: None,
+                    },
+                );
+                content
+            });
+            responses.insert(
+                additional.status.to_string(),
+                Response {
+                    reference: None,
+                    description: additional.description.clone(),
+                    content,
+                    extra: Default::default(),
+                },
+            );
+        }
+        for rejection in &route.rejection_responses {
+            responses.entry(rejection.status.to_string()).or_insert_with(|| Response {
+                reference: None,
+                description: rejection.description.clone(),
+                content: None,
+                extra: Default::default(),
+            });
+        }
+        for (status, description) in &self.global_responses {
+            responses.entry(status.to_string()).or_insert_with(|| Response {
+                reference: None,
+                description: description.clone(),
+                content: None,
+                extra: Default::default(),
+            });
+        }
+        if let Some(errors) = doc_errors {
+            responses.entry("default".to_string()).or_insert(Response {
+                reference: None,
+                description: errors,
+                content: None,
+                extra: Default::default(),
+            });
+        }
+
+        for (status, response) in &mut responses {
+            if response.description.trim().is_empty() {
+                response.description = reason_phrase(status).to_string();
+            }
+        }
+
+        // The handler's doc comment describes the operation unless `#[openapi(summary)]` does
+        let (doc_summary, description) = route.doc.as_deref().map(doc_overview).unwrap_or_default();
 
         // Create the operation
         let operation = Operation {
-            summary: Some(format!("{} {}", route.method_str(), route.path)),
-            description: None,
-            operation_id: Some(route.handler_name.clone()),
+            summary: Some(
+                route
+                    .summary
+                    .clone()
+                    .or(doc_summary)
+                    .unwrap_or_else(|| format!("{} {}", route.method_str(), route.path)),
+            ),
+            description,
+            operation_id: Some(
+                route
+                    .operation_id
+                    .clone()
+                    .unwrap_or_else(|| route.handler_name.clone()),
+            ),
+            tags: if route.tags.is_empty() {
+                None
+            } else {
+                Some(route.tags.clone())
+            },
             parameters,
             request_body,
             responses,
+            extensions: route.extensions.clone(),
+            extra: serde_json::Map::new(),
         };
 
         // Add operation to the appropriate path and method
@@ -256,6 +858,8 @@ impl OpenApiBuilder {
             patch: None,
             options: None,
             head: None,
+            trace: None,
+            extra: Default::default(),
         });
 
         match route.method {
@@ -266,19 +870,85 @@ impl OpenApiBuilder {
             HttpMethod::Patch => path_item.patch = Some(operation),
             HttpMethod::Options => path_item.options = Some(operation),
             HttpMethod::Head => path_item.head = Some(operation),
+            HttpMethod::Trace => path_item.trace = Some(operation),
+            HttpMethod::Connect => unreachable!("CONNECT routes are skipped above"),
         }
     }
 
+    /// Add a webhook to the OpenAPI document, as a POST operation whose request body is the
+    /// event payload.
+    ///
+    /// The first paragraph of the webhook's doc comment is its summary, the rest its description.
+    pub fn add_webhook(&mut self, webhook: &WebhookInfo, schema_gen: &mut SchemaGenerator) {
+        debug!("Adding webhook: {}", webhook.name);
+
+        let mut content = HashMap::new();
+        content.insert(
+            "application/json".to_string(),
+            MediaType {
+                schema: schema_gen.generate_schema(&webhook.body),
+                example: None,
+                examples: None,
+            },
+        );
+        let mut responses = HashMap::new();
+        responses.insert(
+            "200".to_string(),
+            Response {
+                reference: None,
+                description: "Webhook received".to_string(),
+                content: None,
+                extra: Default::default(),
+            },
+        );
+
+        let (summary, description) = webhook.doc.as_deref().map(doc_overview).unwrap_or_default();
+        let operation = Operation {
+            summary: Some(summary.unwrap_or_else(|| format!("Webhook {}", webhook.name))),
+            description,
+            operation_id: None,
+            tags: None,
+            parameters: None,
+            request_body: Some(RequestBody {
+                description: Some("Event payload".to_string()),
+                required: true,
+                content,
+            }),
+            responses,
+            extensions: BTreeMap::new(),
+            extra: serde_json::Map::new(),
+        };
+
+        self.webhooks.insert(
+            webhook.name.clone(),
+            PathItem {
+                get: None,
+                post: Some(operation),
+                put: None,
+                delete: None,
+                patch: None,
+                options: None,
+                head: None,
+                trace: None,
+                extra: Default::default(),
+            },
+        );
+    }
+
     /// Convert path format from :param or {param} to OpenAPI {param} format
     fn convert_path_format(path: &str) -> String {
         // Handle both Axum style (:param) and Actix style ({param})
         // Convert :param to {param}
+        let path = Self::normalize_path(path);
         let parts: Vec<&str> = path.split('/').collect();
         let converted_parts: Vec<String> = parts
             .iter()
             .map(|part| {
-                if part.starts_with(':') {
-                    format!("{{{}}}", &part[1..])
+                // Wildcards (`*rest`, `{*rest}`) are documented as ordinary parameters
+                if let Some(name) = part.strip_prefix([':', '*']).filter(|n| !n.is_empty()) {
+                    format!("{{{}}}", name)
+                } else if let Some(name) = part.strip_prefix("{*") {
+                    format!("{{{}", name)
                 } else {
                     part.to_string()
                 }
@@ -288,70 +958,721 @@ impl OpenApiBuilder {
         converted_parts.join("/")
     }
 
+    /// Normalize a path so equivalent spellings map to the same `paths` entry.
+    ///
+    /// Repeated slashes are collapsed, trailing slashes are removed (except for the root `/`),
+    /// and a leading slash is added if missing.
+    fn normalize_path(path: &str) -> String {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        format!("/{}", segments.join("/"))
+    }
+
+    /// Status the success response of a route is documented under: its declared status, else
+    /// the default for its method
+    fn success_status(&self, route: &RouteInfo) -> u16 {
+        if let Some(status) = route.response_status {
+            return status;
+        }
+        if let Some(&status) = self.default_statuses.get(&route.method) {
+            return status;
+        }
+        match route.method {
+            HttpMethod::Post => 201,
+            // A `204 No Content` response cannot carry the handler's response body
+            HttpMethod::Delete if route.response_type.is_none() => 204,
+            _ => 200,
+        }
+    }
+
+    /// Description of a success response documented under `status`
+    fn response_description(&self, status: u16) -> String {
+        if let Some(description) = self.response_descriptions.get(&status) {
+            return description.clone();
+        }
+        match status {
+            201 => "Created",
+            204 => "No content",
+            _ => "Successful response",
+        }
+        .to_string()
+    }
+
+    /// The request body example declared with `#[openapi(request_example_file = "...")]`:
+    /// either the file's JSON content, or an `externalValue` reference to it. Unreadable or
+    /// invalid files are logged and documented without an example.
+    fn request_example(
+        &self,
+        file: &str,
+    ) -> (Option<serde_json::Value>, Option<HashMap<String, Example>>) {
+        if self.external_examples {
+            let name = std::path::Path::new(file)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "example".to_string());
+            let example = Example {
+                external_value: Some(file.to_string()),
+                ..Example::default()
+            };
+            return (None, Some(HashMap::from([(name, example)])));
+        }
+
+        let path = self.examples_root.join(file);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Could not read example file {}: {}", path.display(), e);
+                return (None, None);
+            }
+        };
+        match serde_json::from_str(&content) {
+            Ok(example) => (Some(example), None),
+            Err(e) => {
+                warn!("Example file {} is not valid JSON: {}", path.display(), e);
+                (None, None)
+            }
+        }
+    }
+
+    /// References to the shared examples a route names with
+    /// `#[openapi(request_example = "...")]`. Names never registered with
+    /// [`with_example`](Self::with_example) are logged and skipped.
+    fn example_refs(&self, route: &RouteInfo) -> Vec<(String, Example)> {
+        let registered = self.components.examples.as_ref();
+        route
+            .request_examples
+            .iter()
+            .filter(|name| {
+                let known = registered.is_some_and(|examples| examples.contains_key(*name));
+                if !known {
+                    warn!(
+                        "Unknown example `{}` referenced by {} {}",
+                        name,
+                        route.method_str(),
+                        route.path
+                    );
+                }
+                known
+            })
+            .map(|name| {
+                let example = Example {
+                    reference: Some(format!("#/components/examples/{}", name)),
+                    ..Example::default()
+                };
+                (name.clone(), example)
+            })
+            .collect()
+    }
+
+    /// The top-level tags: every tag used by an operation, once
+    fn tags(&self) -> Option<Vec<Tag>> {
+        let mut paths: Vec<(&String, &PathItem)> = self.paths.iter().collect();
+        paths.sort_by_key(|(path, _)| *path);
+
+        let mut names: Vec<&String> = Vec::new();
+        for (_, path_item) in paths {
+            for (_, operation) in path_item.operations() {
+                for tag in operation.tags.iter().flatten() {
+                    if !names.contains(&tag) {
+                        names.push(tag);
+                    }
+                }
+            }
+        }
+        if self.sort_tags {
+            names.sort();
+        }
+
+        if names.is_empty() {
+            return None;
+        }
+        let tags = names
+            .into_iter()
+            .map(|name| Tag {
+                name: name.clone(),
+                description: None,
+            })
+            .collect();
+        Some(tags)
+    }
+
     /// Build the final OpenAPI document
-    pub fn build(self, schema_gen: SchemaGenerator) -> OpenApiDocument {
+    pub fn build(mut self, mut schema_gen: SchemaGenerator) -> OpenApiDocument {
         debug!("Building final OpenAPI document");
 
+        // Generate shared error responses first so their schemas are collected below
+        if !self.error_responses.is_empty() {
+            let mut responses = HashMap::new();
+            for error in &self.error_responses {
+                let schema = schema_gen.generate_schema(&error.type_info);
+                let mut content = HashMap::new();
+                content.insert(
+                    "application/json".to_string(),
+                    MediaType {
+                        schema,
+                        example: None,
+                        examples: None,
+                    },
+                );
+                responses.insert(
+                    error.name.clone(),
+                    Response {
+                        reference: None,
+                        description: format!("{} error response", error.name),
+                        content: Some(content),
+                        extra: Default::default(),
+                    },
+                );
+            }
+            self.components.responses = Some(responses);
+        }
+
+        if self.auto_head {
+            for path_item in self.paths.values_mut() {
+                if path_item.head.is_none() {
+                    path_item.head = path_item.get.as_ref().map(head_operation);
+                }
+            }
+        }
+
+        let tags = self.tags();
+
+        if !self.global_parameters.is_empty() {
+            for path_item in self.paths.values_mut() {
+                for (_, operation) in path_item.operations_mut() {
+                    let parameters = operation.parameters.get_or_insert_with(Vec::new);
+                    for global in &self.global_parameters {
+                        // Header names are case-insensitive
+                        let same = |p: &&Parameter| {
+                            p.location == global.location
+                                && (p.name == global.name
+                                    || p.location == "header"
+                                        && p.name.eq_ignore_ascii_case(&global.name))
+                        };
+                        if !parameters.iter().any(|p| same(&p)) {
+                            parameters.push(global.clone());
+                        }
+                    }
+                }
+            }
+        }
+
         // Collect all schemas from the schema generator
         let schemas = schema_gen.get_schemas();
-        let components = if !schemas.is_empty() {
-            Some(Components {
-                schemas: Some(schemas.clone()),
-            })
-        } else {
+        if !schemas.is_empty() {
+            self.components.schemas = Some(schemas.clone());
+        }
+
+        let components = if self.components.is_empty() {
             None
+        } else {
+            Some(self.components)
         };
 
-        OpenApiDocument {
+        // Schemas follow OpenAPI 3.0 (`nullable`, `allOf` around nullable `$ref`s), so the
+        // webhooks go to `x-webhooks` rather than the 3.1 `webhooks` section
+        let mut document = OpenApiDocument {
             openapi: "3.0.0".to_string(),
             info: self.info,
+            servers: (!self.servers.is_empty()).then_some(self.servers),
             paths: self.paths,
+            webhooks: HashMap::new(),
+            x_webhooks: self.webhooks,
             components,
+            security: None,
+            tags,
+            extensions: BTreeMap::new(),
+            extra: serde_json::Map::new(),
+        };
+        if !self.keep_unused_schemas {
+            prune_unreferenced(&mut document);
         }
+        drop_one_way_properties(&mut document);
+        match schema_section(schema_gen.ref_prefix()) {
+            Ok(Some(section)) => move_schemas(&mut document, section),
+            Ok(None) => {}
+            Err(e) => warn!("Component schemas stay under components.schemas: {}", e),
+        }
+        document
     }
 }
 
-impl Default for OpenApiBuilder {
-    fn default() -> Self {
-        Self::new()
+/// Top-level section the component schemas are listed in for `$ref`s starting with `prefix`,
+/// so that the references resolve: `None` for `#/components/schemas/`, `Some("definitions")`
+/// for `#/definitions/`. Prefixes pointing anywhere else, or at a section the document
+/// already models, are rejected.
+pub fn schema_section(prefix: &str) -> Result<Option<String>, String> {
+    const MODELED: &[&str] = &[
+        "openapi", "info", "servers", "paths", "webhooks", "components", "security", "tags",
+    ];
+
+    if prefix == crate::schema_generator::SCHEMA_REF_PREFIX {
+        return Ok(None);
+    }
+    let section = prefix
+        .strip_prefix("#/")
+        .and_then(|rest| rest.strip_suffix('/'))
+        .filter(|section| !section.is_empty() && !section.contains(['/', '~']))
+        .ok_or_else(|| {
+            format!(
+                "`{}` is neither `#/components/schemas/` nor a top-level `#/section/`",
+                prefix
+            )
+        })?;
+    if MODELED.contains(&section) || section.starts_with("x-") {
+        return Err(format!("`{}` cannot hold the component schemas", section));
     }
+    Ok(Some(section.to_string()))
 }
 
-impl RouteInfo {
-    /// Get the HTTP method as a string
-    fn method_str(&self) -> &str {
-        match self.method {
-            HttpMethod::Get => "GET",
-            HttpMethod::Post => "POST",
-            HttpMethod::Put => "PUT",
-            HttpMethod::Delete => "DELETE",
-            HttpMethod::Patch => "PATCH",
-            HttpMethod::Options => "OPTIONS",
-            HttpMethod::Head => "HEAD",
+/// Move the component schemas of a document to a top-level section, see [`schema_section`]
+fn move_schemas(document: &mut OpenApiDocument, section: String) {
+    let Some(components) = document.components.as_mut() else {
+        return;
+    };
+    let Some(schemas) = components.schemas.take() else {
+        return;
+    };
+    let schemas: BTreeMap<_, _> = schemas.into_iter().collect();
+    match serde_json::to_value(schemas) {
+        Ok(schemas) => {
+            document.extra.insert(section, schemas);
         }
+        Err(e) => warn!("Failed to move the component schemas to {}: {}", section, e),
+    }
+    if components.is_empty() {
+        document.components = None;
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::extractor::{HttpMethod, Parameter, ParameterLocation, RouteInfo, TypeInfo};
-    use crate::parser::AstParser;
-    use crate::type_resolver::TypeResolver;
-    use std::fs;
-    use std::io::Write;
-    use tempfile::TempDir;
+impl PathItem {
+    /// List all operations defined on this path, paired with their lowercase method name
+    pub fn operations(&self) -> Vec<(&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("post", &self.post),
+            ("put", &self.put),
+            ("patch", &self.patch),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("trace", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|op| (method, op)))
+        .collect()
+    }
 
-    /// Helper function to create a temporary file with content
-    fn create_temp_file(dir: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
-        let file_path = dir.path().join(name);
-        let mut file = fs::File::create(&file_path).unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-        file_path
+    /// List all operations defined on this path for modification, paired with their lowercase
+    /// method name
+    pub fn operations_mut(&mut self) -> Vec<(&'static str, &mut Operation)> {
+        [
+            ("get", &mut self.get),
+            ("post", &mut self.post),
+            ("put", &mut self.put),
+            ("patch", &mut self.patch),
+            ("delete", &mut self.delete),
+            ("options", &mut self.options),
+            ("head", &mut self.head),
+            ("trace", &mut self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_mut().map(|op| (method, op)))
+        .collect()
     }
 
-    /// Helper function to create a SchemaGenerator from code
-    fn create_generator_from_code(code: &str) -> SchemaGenerator {
+    /// The operation for a lowercase method name, if defined on this path
+    pub fn operation_mut(&mut self, method: &str) -> Option<&mut Operation> {
+        match method {
+            "get" => self.get.as_mut(),
+            "post" => self.post.as_mut(),
+            "put" => self.put.as_mut(),
+            "patch" => self.patch.as_mut(),
+            "delete" => self.delete.as_mut(),
+            "options" => self.options.as_mut(),
+            "head" => self.head.as_mut(),
+            "trace" => self.trace.as_mut(),
+            _ => None,
+        }
+    }
+}
+
+impl Default for OpenApiBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where a component schema is used by the operations of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Usage {
+    /// Only by request bodies and parameters
+    Request,
+    /// Only by responses
+    Response,
+    /// By both requests and responses
+    Both,
+    /// By no operation
+    Unused,
+}
+
+/// Classify every component schema by whether operations use it in requests, responses or
+/// both, following `$ref`s through other schemas.
+///
+/// Schemas used only in responses are candidates for `readOnly` fields, schemas used in both
+/// directions for separate request and response types.
+pub fn schema_usage(document: &OpenApiDocument) -> HashMap<String, Usage> {
+    let schemas = document
+        .components
+        .as_ref()
+        .and_then(|c| c.schemas.as_ref());
+
+    let mut request_roots = Vec::new();
+    let mut response_roots = Vec::new();
+    let webhooks = document.webhooks.values().chain(document.x_webhooks.values());
+    for path_item in document.paths.values().chain(webhooks) {
+        for (_, operation) in path_item.operations() {
+            for parameter in operation.parameters.iter().flatten() {
+                schema_refs(&parameter.schema, &mut request_roots);
+            }
+            if let Some(request_body) = &operation.request_body {
+                for media in request_body.content.values() {
+                    schema_refs(&media.schema, &mut request_roots);
+                }
+            }
+            for response in operation.responses.values() {
+                for media in response.content.iter().flat_map(|c| c.values()) {
+                    schema_refs(&media.schema, &mut response_roots);
+                }
+            }
+        }
+    }
+    // Shared error responses are referenced by operations by name
+    let shared_responses = document
+        .components
+        .as_ref()
+        .and_then(|c| c.responses.as_ref());
+    for response in shared_responses.iter().flat_map(|r| r.values()) {
+        for media in response.content.iter().flat_map(|c| c.values()) {
+            schema_refs(&media.schema, &mut response_roots);
+        }
+    }
+
+    let reachable = |roots: Vec<String>| {
+        let mut seen = HashSet::new();
+        let mut pending = roots;
+        while let Some(name) = pending.pop() {
+            if seen.insert(name.clone()) {
+                if let Some(schema) = schemas.and_then(|s| s.get(&name)) {
+                    schema_refs(schema, &mut pending);
+                }
+            }
+        }
+        seen
+    };
+    let in_requests = reachable(request_roots);
+    let in_responses = reachable(response_roots);
+
+    schemas
+        .into_iter()
+        .flat_map(|s| s.keys())
+        .map(|name| {
+            let usage = match (in_requests.contains(name), in_responses.contains(name)) {
+                (true, true) => Usage::Both,
+                (true, false) => Usage::Request,
+                (false, true) => Usage::Response,
+                (false, false) => Usage::Unused,
+            };
+            (name.clone(), usage)
+        })
+        .collect()
+}
+
+/// Remove the properties of component schemas used in one direction only that serde never
+/// sends in that direction: `readOnly` properties (`#[serde(skip_deserializing)]`) of schemas
+/// used only by requests and `writeOnly` ones (`#[serde(skip_serializing)]`) of schemas used
+/// only by responses. Schemas used in both directions keep them, marked.
+fn drop_one_way_properties(document: &mut OpenApiDocument) {
+    fn retain(schema: &mut Schema, drop: fn(&Property) -> bool) {
+        if let Some(properties) = schema.properties.as_mut() {
+            let dropped: Vec<String> = properties
+                .iter()
+                .filter(|(_, property)| drop(property))
+                .map(|(name, _)| name.clone())
+                .collect();
+            for name in &dropped {
+                debug!("Removing one-way property: {}", name);
+                properties.remove(name);
+            }
+            if let Some(required) = schema.required.as_mut() {
+                required.retain(|name| !dropped.contains(name));
+            }
+        }
+        if schema.properties.as_ref().is_some_and(HashMap::is_empty) {
+            schema.properties = None;
+        }
+        if schema.required.as_ref().is_some_and(Vec::is_empty) {
+            schema.required = None;
+        }
+        for member in schema.all_of.iter_mut().flatten() {
+            retain(member, drop);
+        }
+    }
+
+    let usage = schema_usage(document);
+    let Some(schemas) = document.components.as_mut().and_then(|c| c.schemas.as_mut()) else {
+        return;
+    };
+    for (name, schema) in schemas.iter_mut() {
+        let drop: fn(&Property) -> bool = match usage.get(name) {
+            Some(Usage::Request) => |property| property.read_only == Some(true),
+            Some(Usage::Response) => |property| property.write_only == Some(true),
+            _ => continue,
+        };
+        retain(schema, drop);
+    }
+}
+
+/// Remove the component schemas that no operation or shared response references, directly or
+/// through other schemas.
+///
+/// Schema generation also collects the schemas of nested types, types of routes that were
+/// filtered out, and placeholders; none of them is useful to consumers of the document.
+pub fn prune_unreferenced(document: &mut OpenApiDocument) {
+    let unused: Vec<String> = schema_usage(document)
+        .into_iter()
+        .filter(|(_, usage)| *usage == Usage::Unused)
+        .map(|(name, _)| name)
+        .collect();
+    let Some(components) = document.components.as_mut() else {
+        return;
+    };
+    if let Some(schemas) = components.schemas.as_mut() {
+        for name in &unused {
+            debug!("Removing unreferenced schema: {}", name);
+            schemas.remove(name);
+        }
+        if schemas.is_empty() {
+            components.schemas = None;
+        }
+    }
+    if components.is_empty() {
+        document.components = None;
+    }
+}
+
+/// Media types of a request body declared with `consumes`; none means JSON
+fn media_types(consumes: &[String]) -> impl Iterator<Item = &str> {
+    let default = consumes.is_empty().then_some("application/json");
+    consumes.iter().map(String::as_str).chain(default)
+}
+
+/// Reason phrase of a response status key (`404`, `4XX`, `default`)
+fn reason_phrase(status: &str) -> &'static str {
+    match status {
+        "200" => "OK",
+        "201" => "Created",
+        "202" => "Accepted",
+        "204" => "No Content",
+        "301" => "Moved Permanently",
+        "302" => "Found",
+        "304" => "Not Modified",
+        "400" => "Bad Request",
+        "401" => "Unauthorized",
+        "403" => "Forbidden",
+        "404" => "Not Found",
+        "405" => "Method Not Allowed",
+        "409" => "Conflict",
+        "410" => "Gone",
+        "413" => "Payload Too Large",
+        "415" => "Unsupported Media Type",
+        "422" => "Unprocessable Entity",
+        "429" => "Too Many Requests",
+        "500" => "Internal Server Error",
+        "501" => "Not Implemented",
+        "502" => "Bad Gateway",
+        "503" => "Service Unavailable",
+        "504" => "Gateway Timeout",
+        "default" => "Unexpected response",
+        _ => match status.as_bytes().first() {
+            Some(b'1') => "Informational",
+            Some(b'2') => "Success",
+            Some(b'3') => "Redirection",
+            Some(b'4') => "Client error",
+            Some(b'5') => "Server error",
+            _ => "Response",
+        },
+    }
+}
+
+/// The schema of a body sent as `media_type`: byte buffers, base64 encoded (`format: byte`)
+/// inside JSON, are sent as-is (`format: binary`) in an `application/octet-stream` body
+fn raw_body_schema(mut schema: Schema, media_type: &str) -> Schema {
+    if media_type == "application/octet-stream" && schema.format.as_deref() == Some("byte") {
+        schema.format = Some("binary".to_string());
+    }
+    schema
+}
+
+/// Names of the component schemas a schema references directly
+fn schema_refs(schema: &Schema, refs: &mut Vec<String>) {
+    push_ref(schema.reference.as_deref(), refs);
+    for property in schema.properties.iter().flat_map(|p| p.values()) {
+        property_refs(property, refs);
+    }
+    if let Some(items) = &schema.items {
+        schema_refs(items, refs);
+    }
+    if let Some(values) = &schema.additional_properties {
+        schema_refs(values, refs);
+    }
+    for variant in schema.one_of.iter().chain(&schema.all_of).flatten() {
+        schema_refs(variant, refs);
+    }
+}
+
+/// Names of the component schemas a property references directly
+fn property_refs(property: &Property, refs: &mut Vec<String>) {
+    push_ref(property.reference.as_deref(), refs);
+    if let Some(items) = &property.items {
+        schema_refs(items, refs);
+    }
+    if let Some(values) = &property.additional_properties {
+        schema_refs(values, refs);
+    }
+    for wrapped in property.all_of.iter().flatten() {
+        schema_refs(wrapped, refs);
+    }
+    for nested in property.properties.iter().flat_map(|p| p.values()) {
+        property_refs(nested, refs);
+    }
+}
+
+/// Record the schema name of a component schema reference, its last path segment whatever
+/// prefix the schema generator references components with
+fn push_ref(reference: Option<&str>, refs: &mut Vec<String>) {
+    if let Some(name) = reference.and_then(|r| r.rsplit('/').next()) {
+        refs.push(name.to_string());
+    }
+}
+
+impl RouteInfo {
+    /// Get the HTTP method as a string
+    pub(crate) fn method_str(&self) -> &str {
+        match self.method {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Connect => "CONNECT",
+        }
+    }
+}
+
+/// HEAD operation mirroring a GET operation: same parameters and responses, without bodies
+fn head_operation(get: &Operation) -> Operation {
+    let mut head = get.clone();
+    head.operation_id = get.operation_id.as_ref().map(|id| format!("{}_head", id));
+    if let Some(path) = get.summary.as_deref().and_then(|s| s.strip_prefix("GET ")) {
+        head.summary = Some(format!("HEAD {}", path));
+    }
+    head.request_body = None;
+    for response in head.responses.values_mut() {
+        response.content = None;
+    }
+    head
+}
+
+/// Whether a line of Markdown, stripped of leading whitespace, is a `# heading`
+fn is_heading(trimmed: &str) -> bool {
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    level > 0 && trimmed[level..].starts_with(' ')
+}
+
+/// Summary (the first paragraph, its lines joined) and description (the text after it, up to
+/// the first heading such as `# Returns`) of an operation documented by a Markdown doc comment
+fn doc_overview(doc: &str) -> (Option<String>, Option<String>) {
+    let mut lines = doc.lines().peekable();
+    let mut summary = Vec::new();
+    while let Some(line) = lines.next_if(|line| {
+        let trimmed = line.trim();
+        !trimmed.is_empty() && !is_heading(trimmed)
+    }) {
+        summary.push(line.trim());
+    }
+    if summary.is_empty() {
+        return (None, None);
+    }
+
+    let mut in_fence = false;
+    let body: Vec<&str> = lines
+        .take_while(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") {
+                in_fence = !in_fence;
+            }
+            in_fence || !is_heading(trimmed)
+        })
+        .collect();
+    let body = body.join("\n").trim().to_string();
+    (Some(summary.join(" ")), (!body.is_empty()).then_some(body))
+}
+
+/// Text of the `# heading` section of a Markdown doc comment, up to the next heading of the
+/// same or a higher level. Headings are matched case-insensitively; lines inside code fences
+/// are never taken for headings.
+fn doc_section(doc: &str, heading: &str) -> Option<String> {
+    let mut in_fence = false;
+    let mut section: Option<(usize, Vec<&str>)> = None;
+
+    for line in doc.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        let title = trimmed[level..].trim();
+        let is_heading = !in_fence && is_heading(trimmed);
+
+        match &mut section {
+            Some((section_level, _)) if is_heading && level <= *section_level => break,
+            Some((_, lines)) => lines.push(line),
+            None if is_heading && title.eq_ignore_ascii_case(heading) => {
+                section = Some((level, Vec::new()))
+            }
+            None => {}
+        }
+    }
+
+    let text = section?.1.join("\n").trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::{
+        AdditionalResponse, HttpMethod, Parameter, ParameterLocation, RouteInfo, TypeInfo,
+    };
+    use crate::parser::AstParser;
+    use crate::type_resolver::TypeResolver;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    /// Helper function to create a temporary file with content
+    fn create_temp_file(dir: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let file_path = dir.path().join(name);
+        let mut file = fs::File::create(&file_path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file_path
+    }
+
+    /// Helper function to create a SchemaGenerator from code
+    fn create_generator_from_code(code: &str) -> SchemaGenerator {
         let temp_dir = TempDir::new().unwrap();
         let file_path = create_temp_file(&temp_dir, "test.rs", code);
         let parsed = AstParser::parse_file(&file_path).unwrap();
@@ -442,6 +1763,157 @@ mod tests {
         assert!(request_body.content.contains_key("application/json"));
     }
 
+    #[test]
+    fn test_add_route_with_optional_request_body() {
+        let code = r#"
+            pub struct SettingsPatch {
+                pub theme: Option<String>,
+            }
+        "#;
+        
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code(code);
+        
+        let mut route = RouteInfo::new(
+            "/settings".to_string(),
+            HttpMethod::Patch,
+            "merge_settings".to_string(),
+        );
+        route.request_body = Some(TypeInfo::new("SettingsPatch".to_string()));
+        route.request_body_required = false;
+        
+        builder.add_route(&route, &mut schema_gen);
+        
+        let operation = builder.paths["/settings"].patch.as_ref().unwrap();
+        let request_body = operation.request_body.as_ref().unwrap();
+        assert!(!request_body.required);
+    }
+
+    #[test]
+    fn test_add_route_with_operation_overrides() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+        
+        let mut route = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Get,
+            "list_users".to_string(),
+        );
+        route.operation_id = Some("listUsers".to_string());
+        route.summary = Some("List all users".to_string());
+        route.tags = vec!["users".to_string()];
+        
+        builder.add_route(&route, &mut schema_gen);
+        
+        let operation = builder.paths["/users"].get.as_ref().unwrap();
+        assert_eq!(operation.operation_id, Some("listUsers".to_string()));
+        assert_eq!(operation.summary, Some("List all users".to_string()));
+        assert_eq!(operation.tags, Some(vec!["users".to_string()]));
+    }
+
+    #[test]
+    fn test_vendor_extensions() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+        let mut route =
+            RouteInfo::new("/refunds".to_string(), HttpMethod::Post, "refund".to_string());
+        route.extensions.insert("x-rate-limit".to_string(), serde_json::json!(100));
+        builder.add_route(&route, &mut schema_gen);
+
+        let json = serde_json::to_value(builder.build(schema_gen)).unwrap();
+        assert_eq!(json["paths"]["/refunds"]["post"]["x-rate-limit"], 100);
+
+        // Extensions of documents read from disk survive a round trip
+        let document: OpenApiDocument = serde_yaml::from_str(
+            r#"
+openapi: 3.0.0
+info:
+  title: Payments
+  version: 1.0.0
+  x-audience: internal
+paths:
+  /refunds:
+    post:
+      x-rate-limit: 100
+      responses:
+        '200':
+          description: OK
+components:
+  schemas:
+    Refund:
+      type: object
+      x-internal: true
+"#,
+        )
+        .unwrap();
+        assert_eq!(document.info.extensions["x-audience"], "internal");
+        let operation = document.paths["/refunds"].post.as_ref().unwrap();
+        assert_eq!(operation.extensions["x-rate-limit"], 100);
+        assert!(operation.extra.is_empty());
+        let schemas = document.components.as_ref().unwrap().schemas.as_ref().unwrap();
+        assert_eq!(schemas["Refund"].extensions["x-internal"], true);
+
+        let yaml = serde_yaml::to_string(&document).unwrap();
+        assert_eq!(yaml.matches("x-rate-limit: 100").count(), 1);
+        assert!(yaml.contains("x-audience: internal"));
+        assert!(yaml.contains("x-internal: true"));
+    }
+
+    fn tagged_builder(sort_tags: bool) -> OpenApiBuilder {
+        let mut builder = OpenApiBuilder::new().with_sorted_tags(sort_tags);
+        let mut schema_gen = create_generator_from_code("");
+        for (path, method, tags) in [
+            ("/users", HttpMethod::Delete, vec!["users", "admin"]),
+            ("/accounts", HttpMethod::Get, vec!["billing"]),
+            ("/users", HttpMethod::Get, vec!["users"]),
+            ("/health", HttpMethod::Get, vec![]),
+        ] {
+            let mut route = RouteInfo::new(path.to_string(), method, "handler".to_string());
+            route.tags = tags.into_iter().map(str::to_string).collect();
+            builder.add_route(&route, &mut schema_gen);
+        }
+        builder
+    }
+
+    #[test]
+    fn test_top_level_tags() {
+        let tag_names = |document: &OpenApiDocument| -> Vec<String> {
+            document.tags.iter().flatten().map(|t| t.name.clone()).collect()
+        };
+
+        // First use, walking paths alphabetically and methods in reading order
+        let document = tagged_builder(false).build(create_generator_from_code(""));
+        assert_eq!(tag_names(&document), vec!["billing", "users", "admin"]);
+
+        let document = tagged_builder(true).build(create_generator_from_code(""));
+        assert_eq!(tag_names(&document), vec!["admin", "billing", "users"]);
+
+        let document = OpenApiBuilder::new().build(create_generator_from_code(""));
+        assert!(document.tags.is_none());
+    }
+
+    #[test]
+    fn test_paths_and_methods_serialize_in_reading_order() {
+        let mut builder = tagged_builder(false);
+        let mut schema_gen = create_generator_from_code("");
+        for method in [HttpMethod::Patch, HttpMethod::Post, HttpMethod::Put] {
+            let route = RouteInfo::new("/users".to_string(), method, "handler".to_string());
+            builder.add_route(&route, &mut schema_gen);
+        }
+        let document = builder.build(schema_gen);
+
+        let json = serde_json::to_string(&document).unwrap();
+        let position = |needle: &str| json.find(needle).unwrap();
+        assert!(position("\"/accounts\"") < position("\"/health\""));
+        assert!(position("\"/health\"") < position("\"/users\""));
+        let users = &json[position("\"/users\"")..];
+        let methods: Vec<usize> = ["\"get\"", "\"post\"", "\"put\"", "\"patch\"", "\"delete\""]
+            .iter()
+            .map(|method| users.find(method).unwrap())
+            .collect();
+        assert!(methods.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
     #[test]
     fn test_add_route_with_path_parameter() {
         let mut builder = OpenApiBuilder::new();
@@ -475,6 +1947,85 @@ mod tests {
         assert!(parameters[0].required);
     }
 
+    #[test]
+    fn test_path_struct_fields_describe_url_parameters() {
+        let code = r#"
+            pub struct UserPath {
+                /// Identifier of the user
+                pub id: u64,
+            }
+        "#;
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code(code);
+
+        let mut route = RouteInfo::new(
+            "/users/:id".to_string(),
+            HttpMethod::Get,
+            "get_user".to_string(),
+        );
+        route.parameters.push(Parameter::new(
+            "id".to_string(),
+            ParameterLocation::Path,
+            TypeInfo::new("String".to_string()),
+            true,
+        ));
+        route.parameters.push(Parameter::new(
+            crate::extractor::PATH_EXTRACTOR_PARAM.to_string(),
+            ParameterLocation::Path,
+            TypeInfo::new("UserPath".to_string()),
+            true,
+        ));
+
+        builder.add_route(&route, &mut schema_gen);
+
+        let operation = builder.paths["/users/{id}"].get.as_ref().unwrap();
+        let parameters = operation.parameters.as_ref().unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0].name, "id");
+        assert_eq!(parameters[0].description, Some("Identifier of the user".to_string()));
+        assert_eq!(parameters[0].schema.format, Some("int64".to_string()));
+    }
+
+    #[test]
+    fn test_single_value_path_extractor_types_url_parameter() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+
+        for (path, names) in [
+            ("/users/:id", vec!["id"]),
+            ("/users/:id/posts/:post_id", vec!["id", "post_id"]),
+        ] {
+            let mut route = RouteInfo::new(path.to_string(), HttpMethod::Get, "get".to_string());
+            for name in names {
+                route.parameters.push(Parameter::new(
+                    name.to_string(),
+                    ParameterLocation::Path,
+                    TypeInfo::new("String".to_string()),
+                    true,
+                ));
+            }
+            route.parameters.push(Parameter::new(
+                PATH_EXTRACTOR_PARAM.to_string(),
+                ParameterLocation::Path,
+                TypeInfo::new("u64".to_string()),
+                true,
+            ));
+            builder.add_route(&route, &mut schema_gen);
+        }
+
+        let operation = builder.paths["/users/{id}"].get.as_ref().unwrap();
+        let parameters = operation.parameters.as_ref().unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0].name, "id");
+        assert_eq!(parameters[0].schema.format.as_deref(), Some("int64"));
+
+        // With several URL parameters the single value cannot be attributed
+        let operation = builder.paths["/users/{id}/posts/{post_id}"].get.as_ref().unwrap();
+        let names: Vec<&str> =
+            operation.parameters.iter().flatten().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "post_id"]);
+    }
+
     #[test]
     fn test_add_route_with_query_parameter() {
         let mut builder = OpenApiBuilder::new();
@@ -619,28 +2170,314 @@ mod tests {
     }
 
     #[test]
-    fn test_convert_path_format_no_params() {
-        let path = "/users/list";
-        let converted = OpenApiBuilder::convert_path_format(path);
-        assert_eq!(converted, "/users/list");
+    fn test_convert_path_format_wildcards() {
+        assert_eq!(OpenApiBuilder::convert_path_format("/assets/*rest"), "/assets/{rest}");
+        assert_eq!(OpenApiBuilder::convert_path_format("/files/{*path}"), "/files/{path}");
     }
 
     #[test]
-    fn test_build_document_structure() {
-        let code = r#"
-            pub struct User {
-                pub id: u32,
-                pub name: String,
-            }
-        "#;
-        
+    fn test_doc_section() {
+        let doc = "Fetch a user.\n\n# Returns\n\nThe user\nwith its roles\n\n\
+                   ## Example\n\n```\n# use app::*;\n```\n\n# Errors\n\n404 if unknown";
+
+        assert_eq!(
+            doc_section(doc, "returns"),
+            Some("The user\nwith its roles\n\n## Example\n\n```\n# use app::*;\n```".to_string())
+        );
+        assert_eq!(doc_section(doc, "Errors"), Some("404 if unknown".to_string()));
+        assert_eq!(doc_section(doc, "Panics"), None);
+        assert_eq!(doc_section("# Returns\n\n# Errors\nNever", "Returns"), None);
+    }
+
+    #[test]
+    fn test_doc_sections_describe_responses() {
         let mut builder = OpenApiBuilder::new();
-        let mut schema_gen = create_generator_from_code(code);
-        
+        let mut schema_gen = create_generator_from_code("");
+
         let mut route = RouteInfo::new(
-            "/users".to_string(),
-            HttpMethod::Post,
-            "create_user".to_string(),
+            "/users/:id".to_string(),
+            HttpMethod::Get,
+            "get_user".to_string(),
+        );
+        route.doc = Some(
+            "Fetch a user.\n\n# Returns\n\nThe requested user\n\n# Errors\n\nFails when the user \
+             does not exist"
+                .to_string(),
+        );
+        builder.add_route(&route, &mut schema_gen);
+
+        let responses = &builder.paths["/users/{id}"].get.as_ref().unwrap().responses;
+        assert_eq!(responses["200"].description, "The requested user");
+        assert_eq!(responses["default"].description, "Fails when the user does not exist");
+    }
+
+    #[test]
+    fn test_doc_comment_summary_and_description() {
+        assert_eq!(doc_overview("List users"), (Some("List users".to_string()), None));
+        assert_eq!(
+            doc_overview(
+                "List users\n\nPaged, newest first.\n```\n# not a heading\n```\n\n# Errors\n\nNever"
+            ),
+            (
+                Some("List users".to_string()),
+                Some("Paged, newest first.\n```\n# not a heading\n```".to_string())
+            )
+        );
+        assert_eq!(doc_overview("# Returns\n\nUsers"), (None, None));
+        // A summary wrapped over several lines is joined
+        assert_eq!(
+            doc_overview("List the users\n  of a tenant\n\nPaged."),
+            (
+                Some("List the users of a tenant".to_string()),
+                Some("Paged.".to_string())
+            )
+        );
+
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+        let mut list =
+            RouteInfo::new("/users".to_string(), HttpMethod::Get, "list_users".to_string());
+        list.doc = Some("List users\n\nPaged, newest first.".to_string());
+        builder.add_route(&list, &mut schema_gen);
+        let mut create =
+            RouteInfo::new("/users".to_string(), HttpMethod::Post, "create_user".to_string());
+        create.doc = Some("Create a user".to_string());
+        create.summary = Some("Sign up".to_string());
+        builder.add_route(&create, &mut schema_gen);
+
+        let users = &builder.paths["/users"];
+        let get = users.get.as_ref().unwrap();
+        assert_eq!(get.summary.as_deref(), Some("List users"));
+        assert_eq!(get.description.as_deref(), Some("Paged, newest first."));
+        // An explicit `#[openapi(summary)]` wins over the doc comment
+        let post = users.post.as_ref().unwrap();
+        assert_eq!(post.summary.as_deref(), Some("Sign up"));
+        assert!(post.description.is_none());
+    }
+
+    #[test]
+    fn test_global_parameters() {
+        let header = |name: &str, required: bool| super::Parameter {
+            name: name.to_string(),
+            location: "header".to_string(),
+            required,
+            schema: Schema {
+                schema_type: Some("string".to_string()),
+                ..Default::default()
+            },
+            description: None,
+            deprecated: None,
+            style: None,
+            explode: None,
+            extra: Default::default(),
+        };
+        let mut builder = OpenApiBuilder::new()
+            .with_global_parameter(header("X-API-Version", true))
+            .with_global_parameter(header("X-Request-Id", false));
+        let mut schema_gen = create_generator_from_code("");
+
+        let mut get_user =
+            RouteInfo::new("/users/:id".to_string(), HttpMethod::Get, "get_user".to_string());
+        get_user.parameters.push(Parameter::new(
+            "id".to_string(),
+            ParameterLocation::Path,
+            TypeInfo::new("u32".to_string()),
+            true,
+        ));
+        let mut create_user =
+            RouteInfo::new("/users".to_string(), HttpMethod::Post, "create_user".to_string());
+        create_user.parameters.push(Parameter::new(
+            "x-request-id".to_string(),
+            ParameterLocation::Header,
+            TypeInfo::new("String".to_string()),
+            true,
+        ));
+        builder.add_route(&get_user, &mut schema_gen);
+        builder.add_route(&create_user, &mut schema_gen);
+        let document = builder.build(schema_gen);
+
+        let names = |operation: &Operation| -> Vec<(String, bool)> {
+            let parameters = operation.parameters.as_ref().unwrap();
+            parameters.iter().map(|p| (p.name.clone(), p.required)).collect()
+        };
+        let get = document.paths["/users/{id}"].get.as_ref().unwrap();
+        assert_eq!(
+            names(get),
+            vec![
+                ("id".to_string(), true),
+                ("X-API-Version".to_string(), true),
+                ("X-Request-Id".to_string(), false),
+            ]
+        );
+        // The operation's own header wins over the global one
+        let post = document.paths["/users"].post.as_ref().unwrap();
+        assert_eq!(
+            names(post),
+            vec![("x-request-id".to_string(), true), ("X-API-Version".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn test_auto_head() {
+        let code = r#"
+            pub struct User {
+                pub id: u64,
+            }
+        "#;
+        let build = |auto_head: bool| {
+            let mut builder = OpenApiBuilder::new().with_auto_head(auto_head);
+            let mut schema_gen = create_generator_from_code(code);
+            let mut get_user = RouteInfo::new(
+                "/users/:id".to_string(),
+                HttpMethod::Get,
+                "get_user".to_string(),
+            );
+            get_user.parameters.push(Parameter::new(
+                "id".to_string(),
+                ParameterLocation::Path,
+                TypeInfo::new("u64".to_string()),
+                true,
+            ));
+            get_user.response_type = Some(TypeInfo::new("User".to_string()));
+            builder.add_route(&get_user, &mut schema_gen);
+            for method in [HttpMethod::Get, HttpMethod::Head] {
+                let route = RouteInfo::new("/health".to_string(), method, "health".to_string());
+                builder.add_route(&route, &mut schema_gen);
+            }
+            builder.build(schema_gen)
+        };
+
+        let document = build(true);
+        let user = &document.paths["/users/{id}"];
+        let get = user.get.as_ref().unwrap();
+        let head = user.head.as_ref().unwrap();
+        assert_eq!(head.operation_id.as_deref(), Some("get_user_head"));
+        assert_eq!(head.summary.as_deref(), Some("HEAD /users/:id"));
+        assert_eq!(
+            head.parameters.as_ref().unwrap()[0].name,
+            get.parameters.as_ref().unwrap()[0].name
+        );
+        assert!(get.responses["200"].content.is_some());
+        assert_eq!(head.responses["200"].description, get.responses["200"].description);
+        assert!(head.responses["200"].content.is_none());
+        // The User schema is still referenced by the GET operation
+        assert!(document.components.unwrap().schemas.unwrap().contains_key("User"));
+        // A HEAD route of its own is kept
+        let health = document.paths["/health"].head.as_ref().unwrap();
+        assert_eq!(health.operation_id.as_deref(), Some("health"));
+
+        assert!(build(false).paths["/users/{id}"].head.is_none());
+    }
+
+    #[test]
+    fn test_additional_and_global_responses() {
+        let mut builder = OpenApiBuilder::new()
+            .with_global_response(401, "Unauthorized".to_string())
+            .with_global_response(429, "Too many requests".to_string());
+        let mut schema_gen = create_generator_from_code(
+            r#"
+            pub struct RateLimit {
+                pub retry_after: u32,
+            }
+        "#,
+        );
+
+        let mut route = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Get,
+            "list_users".to_string(),
+        );
+        route.additional_responses.push(AdditionalResponse {
+            status: 429,
+            description: "Rate limited".to_string(),
+            body: Some(TypeInfo::new("RateLimit".to_string())),
+        });
+        route.additional_responses.push(AdditionalResponse {
+            status: 422,
+            description: String::new(),
+            body: None,
+        });
+        for (status, description) in [(400, "Invalid query string"), (429, "Rejected")] {
+            route.rejection_responses.push(AdditionalResponse {
+                status,
+                description: description.to_string(),
+                body: None,
+            });
+        }
+        builder.add_route(&route, &mut schema_gen);
+
+        let responses = &builder.paths["/users"].get.as_ref().unwrap().responses;
+        assert_eq!(responses.len(), 5);
+        assert!(responses.contains_key("200"));
+        // Descriptions are required, so a missing one falls back to the reason phrase
+        assert_eq!(responses["422"].description, "Unprocessable Entity");
+        let json = serde_json::to_value(&responses["422"]).unwrap();
+        assert_eq!(json["description"], "Unprocessable Entity");
+        assert_eq!(responses["400"].description, "Invalid query string");
+        assert_eq!(responses["401"].description, "Unauthorized");
+        assert!(responses["401"].content.is_none());
+        // The route's own response wins over the global one
+        let limited = &responses["429"];
+        assert_eq!(limited.description, "Rate limited");
+        let media = &limited.content.as_ref().unwrap()["application/json"];
+        assert_eq!(
+            media.schema.reference,
+            Some("#/components/schemas/RateLimit".to_string())
+        );
+    }
+
+    #[test]
+    fn test_convert_path_format_no_params() {
+        let path = "/users/list";
+        let converted = OpenApiBuilder::convert_path_format(path);
+        assert_eq!(converted, "/users/list");
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(OpenApiBuilder::normalize_path("/api//users"), "/api/users");
+        assert_eq!(OpenApiBuilder::normalize_path("/api/users/"), "/api/users");
+        assert_eq!(OpenApiBuilder::normalize_path("api/users"), "/api/users");
+        assert_eq!(OpenApiBuilder::normalize_path(""), "/");
+        assert_eq!(OpenApiBuilder::normalize_path("//"), "/");
+        assert_eq!(
+            OpenApiBuilder::convert_path_format("/users//:id/"),
+            "/users/{id}"
+        );
+    }
+
+    #[test]
+    fn test_trailing_slash_routes_share_path_item() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+
+        let list = RouteInfo::new("/users/".to_string(), HttpMethod::Get, "list".to_string());
+        let create = RouteInfo::new("/users".to_string(), HttpMethod::Post, "create".to_string());
+        builder.add_route(&list, &mut schema_gen);
+        builder.add_route(&create, &mut schema_gen);
+
+        assert_eq!(builder.paths.len(), 1);
+        let path_item = &builder.paths["/users"];
+        assert!(path_item.get.is_some());
+        assert!(path_item.post.is_some());
+    }
+
+    #[test]
+    fn test_build_document_structure() {
+        let code = r#"
+            pub struct User {
+                pub id: u32,
+                pub name: String,
+            }
+        "#;
+        
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code(code);
+        
+        let mut route = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Post,
+            "create_user".to_string(),
         );
         route.request_body = Some(TypeInfo::new("User".to_string()));
         
@@ -661,6 +2498,63 @@ mod tests {
         assert!(schemas.contains_key("User"));
     }
 
+    #[test]
+    fn test_shared_error_response() {
+        let code = r#"
+            pub struct ApiError {
+                pub message: String,
+            }
+        "#;
+
+        let mut builder = OpenApiBuilder::new().with_error_response(
+            404,
+            "NotFound".to_string(),
+            TypeInfo::new("ApiError".to_string()),
+        );
+        let mut schema_gen = create_generator_from_code(code);
+
+        let mut get_user = RouteInfo::new(
+            "/users/:id".to_string(),
+            HttpMethod::Get,
+            "get_user".to_string(),
+        );
+        get_user.response_type = Some(TypeInfo::result(
+            TypeInfo::new("String".to_string()),
+            Some(TypeInfo::new("ApiError".to_string())),
+        ));
+        let health = RouteInfo::new("/health".to_string(), HttpMethod::Get, "health".to_string());
+
+        builder.add_route(&get_user, &mut schema_gen);
+        builder.add_route(&health, &mut schema_gen);
+        let document = builder.build(schema_gen);
+
+        let operation = document.paths["/users/{id}"].get.as_ref().unwrap();
+        assert_eq!(
+            operation.responses["404"].reference.as_deref(),
+            Some("#/components/responses/NotFound")
+        );
+        let health_op = document.paths["/health"].get.as_ref().unwrap();
+        assert!(!health_op.responses.contains_key("404"));
+
+        let components = document.components.unwrap();
+        let not_found = &components.responses.unwrap()["NotFound"];
+        let schema = &not_found.content.as_ref().unwrap()["application/json"].schema;
+        assert_eq!(
+            schema.reference.as_deref(),
+            Some("#/components/schemas/ApiError")
+        );
+        assert!(components.schemas.unwrap().contains_key("ApiError"));
+
+        let json = serde_json::to_value(operation).unwrap();
+        assert_eq!(
+            json["responses"]["404"],
+            serde_json::json!({
+                "$ref": "#/components/responses/NotFound",
+                "description": "Not Found"
+            })
+        );
+    }
+
     #[test]
     fn test_build_document_with_multiple_schemas() {
         let code = r#"
@@ -750,11 +2644,398 @@ mod tests {
         let path_item = &builder.paths["/users"];
         let operation = path_item.delete.as_ref().unwrap();
         
-        let response = &operation.responses["200"];
-        assert_eq!(response.description, "Successful response");
+        let response = &operation.responses["204"];
+        assert_eq!(response.description, "No content");
         assert!(response.content.is_none());
     }
 
+    #[test]
+    fn test_default_status_by_method() {
+        let mut schema_gen = create_generator_from_code("");
+        let route = |method: HttpMethod, response: Option<&str>| {
+            let mut route = RouteInfo::new("/users".to_string(), method, "handler".to_string());
+            route.response_type = response.map(|name| TypeInfo::new(name.to_string()));
+            route
+        };
+
+        let mut builder = OpenApiBuilder::new();
+        builder.add_route(&route(HttpMethod::Get, Some("String")), &mut schema_gen);
+        builder.add_route(&route(HttpMethod::Post, Some("String")), &mut schema_gen);
+        builder.add_route(&route(HttpMethod::Delete, Some("String")), &mut schema_gen);
+        let users = &builder.paths["/users"];
+        assert!(users.get.as_ref().unwrap().responses.contains_key("200"));
+        let created = &users.post.as_ref().unwrap().responses["201"];
+        assert_eq!(created.description, "Created");
+        // DELETE handlers returning a body keep `200`
+        assert!(users.delete.as_ref().unwrap().responses.contains_key("200"));
+
+        let mut builder = OpenApiBuilder::new()
+            .with_default_status(HttpMethod::Post, 200)
+            .with_response_description(200, "OK".to_string());
+        builder.add_route(&route(HttpMethod::Post, None), &mut schema_gen);
+        let response = &builder.paths["/users"].post.as_ref().unwrap().responses["200"];
+        assert_eq!(response.description, "OK");
+    }
+
+    #[test]
+    fn test_response_status_from_route() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+
+        let mut route = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Post,
+            "create_user".to_string(),
+        );
+        route.response_status = Some(201);
+
+        builder.add_route(&route, &mut schema_gen);
+
+        let operation = builder.paths["/users"].post.as_ref().unwrap();
+        assert!(operation.responses.contains_key("201"));
+        assert!(!operation.responses.contains_key("200"));
+    }
+
+    #[test]
+    fn test_media_type_overrides() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+
+        let mut route = RouteInfo::new(
+            "/import".to_string(),
+            HttpMethod::Post,
+            "import_users".to_string(),
+        );
+        route.request_body = Some(TypeInfo::new("String".to_string()));
+        route.response_type = Some(TypeInfo::new("String".to_string()));
+        route.consumes = vec!["text/csv".to_string()];
+        route.produces = Some("text/plain".to_string());
+
+        builder.add_route(&route, &mut schema_gen);
+
+        let operation = builder.paths["/import"].post.as_ref().unwrap();
+        let request_body = operation.request_body.as_ref().unwrap();
+        assert_eq!(request_body.content.keys().collect::<Vec<_>>(), vec!["text/csv"]);
+        let content = operation.responses["201"].content.as_ref().unwrap();
+        assert_eq!(content.keys().collect::<Vec<_>>(), vec!["text/plain"]);
+    }
+
+    #[test]
+    fn test_multiple_request_media_types() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code(
+            r#"
+            pub struct CreateUser {
+                pub name: String,
+            }
+        "#,
+        );
+
+        let mut route =
+            RouteInfo::new("/users".to_string(), HttpMethod::Post, "create_user".to_string());
+        route.request_body = Some(TypeInfo::new("CreateUser".to_string()));
+        route.consumes = vec![
+            "application/json".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        ];
+        builder.add_route(&route, &mut schema_gen);
+
+        let operation = builder.paths["/users"].post.as_ref().unwrap();
+        let content = &operation.request_body.as_ref().unwrap().content;
+        assert_eq!(content.len(), 2);
+        let json = &content["application/json"].schema;
+        assert_eq!(json.reference.as_deref(), Some("#/components/schemas/CreateUser"));
+        let form = &content["application/x-www-form-urlencoded"].schema;
+        assert_eq!(form.reference, json.reference);
+    }
+
+    #[test]
+    fn test_request_example_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("examples")).unwrap();
+        fs::write(
+            temp_dir.path().join("examples/create_user.json"),
+            r#"{"name": "Ada", "email": "ada@example.com"}"#,
+        )
+        .unwrap();
+
+        let mut route = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Post,
+            "create_user".to_string(),
+        );
+        route.request_body = Some(TypeInfo::new("String".to_string()));
+        route.request_example_file = Some("examples/create_user.json".to_string());
+
+        let mut schema_gen = create_generator_from_code("");
+        let mut builder =
+            OpenApiBuilder::new().with_example_files(temp_dir.path().to_path_buf(), false);
+        builder.add_route(&route, &mut schema_gen);
+        let operation = builder.paths["/users"].post.as_ref().unwrap();
+        let media = &operation.request_body.as_ref().unwrap().content["application/json"];
+        assert_eq!(
+            media.example,
+            Some(serde_json::json!({"name": "Ada", "email": "ada@example.com"}))
+        );
+        assert!(media.examples.is_none());
+
+        let mut builder =
+            OpenApiBuilder::new().with_example_files(temp_dir.path().to_path_buf(), true);
+        builder.add_route(&route, &mut schema_gen);
+        let operation = builder.paths["/users"].post.as_ref().unwrap();
+        let media = &operation.request_body.as_ref().unwrap().content["application/json"];
+        assert!(media.example.is_none());
+        let examples = media.examples.as_ref().unwrap();
+        assert_eq!(
+            examples["create_user"].external_value.as_deref(),
+            Some("examples/create_user.json")
+        );
+
+        // A missing file documents the body without an example
+        route.request_example_file = Some("examples/missing.json".to_string());
+        let mut builder =
+            OpenApiBuilder::new().with_example_files(temp_dir.path().to_path_buf(), false);
+        builder.add_route(&route, &mut schema_gen);
+        let operation = builder.paths["/users"].post.as_ref().unwrap();
+        let media = &operation.request_body.as_ref().unwrap().content["application/json"];
+        assert!(media.example.is_none());
+    }
+
+    #[test]
+    fn test_octet_stream_body_is_binary() {
+        let mut route = RouteInfo::new(
+            "/uploads".to_string(),
+            HttpMethod::Post,
+            "upload".to_string(),
+        );
+        route.request_body = Some(TypeInfo::new("Bytes".to_string()));
+        route.consumes = vec!["application/octet-stream".to_string()];
+
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+        builder.add_route(&route, &mut schema_gen);
+
+        let operation = builder.paths["/uploads"].post.as_ref().unwrap();
+        let content = &operation.request_body.as_ref().unwrap().content;
+        let schema = &content["application/octet-stream"].schema;
+        assert_eq!(schema.schema_type, Some("string".to_string()));
+        assert_eq!(schema.format, Some("binary".to_string()));
+    }
+
+    #[test]
+    fn test_shared_examples() {
+        let mut route = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Post,
+            "create_user".to_string(),
+        );
+        route.request_body = Some(TypeInfo::new("String".to_string()));
+        route.request_examples = vec!["new_user".to_string(), "unknown".to_string()];
+
+        let mut schema_gen = create_generator_from_code("");
+        let mut builder = OpenApiBuilder::new()
+            .with_example("new_user".to_string(), serde_json::json!({"name": "Ada"}));
+        builder.add_route(&route, &mut schema_gen);
+        let document = builder.build(schema_gen);
+
+        let operation = document.paths["/users"].post.as_ref().unwrap();
+        let media = &operation.request_body.as_ref().unwrap().content["application/json"];
+        let examples = media.examples.as_ref().unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(
+            examples["new_user"].reference.as_deref(),
+            Some("#/components/examples/new_user")
+        );
+        let shared = document.components.unwrap().examples.unwrap();
+        assert_eq!(shared["new_user"].value, Some(serde_json::json!({"name": "Ada"})));
+
+        let json = serde_json::to_value(&examples["new_user"]).unwrap();
+        assert_eq!(json, serde_json::json!({"$ref": "#/components/examples/new_user"}));
+    }
+
+    #[test]
+    fn test_trace_and_connect_routes() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+
+        let trace = RouteInfo::new("/debug".to_string(), HttpMethod::Trace, "trace".to_string());
+        let connect =
+            RouteInfo::new("/tunnel".to_string(), HttpMethod::Connect, "tunnel".to_string());
+        builder.add_route(&trace, &mut schema_gen);
+        builder.add_route(&connect, &mut schema_gen);
+
+        let debug = &builder.paths["/debug"];
+        assert!(debug.trace.is_some());
+        assert_eq!(debug.operations()[0].0, "trace");
+        assert!(!builder.paths.contains_key("/tunnel"));
+    }
+
+    #[test]
+    fn test_schema_usage() {
+        let code = r#"
+            pub struct CreateUser {
+                pub name: String,
+            }
+
+            pub struct User {
+                pub id: u32,
+                pub address: Address,
+            }
+
+            pub struct Address {
+                pub city: String,
+            }
+
+            pub struct Unused {
+                pub flag: bool,
+            }
+        "#;
+        let mut builder = OpenApiBuilder::new().with_unused_schemas(true);
+        let mut schema_gen = create_generator_from_code(code);
+        schema_gen.generate_schema(&TypeInfo::new("Unused".to_string()));
+
+        let mut create = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Post,
+            "create_user".to_string(),
+        );
+        create.request_body = Some(TypeInfo::new("CreateUser".to_string()));
+        create.response_type = Some(TypeInfo::new("User".to_string()));
+        builder.add_route(&create, &mut schema_gen);
+
+        let mut update = RouteInfo::new(
+            "/users/{id}/address".to_string(),
+            HttpMethod::Put,
+            "update_address".to_string(),
+        );
+        update.request_body = Some(TypeInfo::new("Address".to_string()));
+        builder.add_route(&update, &mut schema_gen);
+
+        let usage = schema_usage(&builder.build(schema_gen));
+        assert_eq!(usage["CreateUser"], Usage::Request);
+        assert_eq!(usage["User"], Usage::Response);
+        // Referenced by the `User` response and the address update body
+        assert_eq!(usage["Address"], Usage::Both);
+        assert_eq!(usage["Unused"], Usage::Unused);
+    }
+
+    #[test]
+    fn test_one_way_fields() {
+        let code = r#"
+            pub struct Account {
+                pub name: String,
+                #[serde(skip_serializing)]
+                pub password: String,
+                #[serde(skip_deserializing)]
+                pub created_at: String,
+            }
+            pub struct Signup {
+                pub account: Account,
+                #[serde(skip_deserializing)]
+                pub id: u64,
+            }
+            pub struct Profile {
+                #[serde(skip_serializing)]
+                pub token: String,
+                pub bio: String,
+            }
+        "#;
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code(code);
+
+        let mut signup =
+            RouteInfo::new("/signup".to_string(), HttpMethod::Post, "signup".to_string());
+        signup.request_body = Some(TypeInfo::new("Signup".to_string()));
+        signup.response_type = Some(TypeInfo::new("Account".to_string()));
+        builder.add_route(&signup, &mut schema_gen);
+        let mut profile =
+            RouteInfo::new("/profile".to_string(), HttpMethod::Get, "profile".to_string());
+        profile.response_type = Some(TypeInfo::new("Profile".to_string()));
+        builder.add_route(&profile, &mut schema_gen);
+
+        let document = builder.build(schema_gen);
+        let schemas = document.components.unwrap().schemas.unwrap();
+        let names = |schema: &str| {
+            let mut names: Vec<String> =
+                schemas[schema].properties.iter().flatten().map(|(n, _)| n.clone()).collect();
+            names.sort();
+            names
+        };
+        // Used in both directions: one-way fields stay, marked
+        assert_eq!(names("Account"), vec!["created_at", "name", "password"]);
+        let account = schemas["Account"].properties.as_ref().unwrap();
+        assert_eq!(account["password"].write_only, Some(true));
+        assert_eq!(account["created_at"].read_only, Some(true));
+        // Request only: the field serde never reads is gone, also from `required`
+        assert_eq!(names("Signup"), vec!["account"]);
+        assert_eq!(schemas["Signup"].required, Some(vec!["account".to_string()]));
+        // Response only: the field serde never writes is gone
+        assert_eq!(names("Profile"), vec!["bio"]);
+    }
+
+    #[test]
+    fn test_prune_unreferenced_schemas() {
+        let code = r#"
+            pub struct User {
+                pub id: u32,
+                pub address: Address,
+            }
+
+            pub struct Address {
+                pub city: String,
+            }
+
+            pub struct Unused {
+                pub flag: bool,
+            }
+        "#;
+        let mut schema_gen = create_generator_from_code(code);
+        schema_gen.generate_schema(&TypeInfo::new("Unused".to_string()));
+        let mut route = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Get,
+            "list_users".to_string(),
+        );
+        route.response_type = Some(TypeInfo::vec(TypeInfo::new("User".to_string())));
+        let mut builder = OpenApiBuilder::new();
+        builder.add_route(&route, &mut schema_gen);
+
+        let document = builder.build(schema_gen);
+        let schemas = document.components.unwrap().schemas.unwrap();
+        let mut names: Vec<&String> = schemas.keys().collect();
+        names.sort();
+        // `Address` is only reachable through `User`
+        assert_eq!(names, vec!["Address", "User"]);
+
+        // A document whose only schema is unreferenced loses its components
+        let mut schema_gen = create_generator_from_code(code);
+        schema_gen.generate_schema(&TypeInfo::new("Unused".to_string()));
+        let document = OpenApiBuilder::new().build(schema_gen);
+        assert!(document.components.is_none());
+    }
+
+    #[test]
+    fn test_static_file_route() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code("");
+
+        let mut route = RouteInfo::new(
+            "/assets/{path}".to_string(),
+            HttpMethod::Get,
+            "ServeDir".to_string(),
+        );
+        route.serves_files = true;
+        builder.add_route(&route, &mut schema_gen);
+
+        let operation = builder.paths["/assets/{path}"].get.as_ref().unwrap();
+        assert!(operation.request_body.is_none());
+        let response = &operation.responses["200"];
+        assert_eq!(response.description, "Static file");
+        let schema = &response.content.as_ref().unwrap()["application/octet-stream"].schema;
+        assert_eq!(schema.schema_type, Some("string".to_string()));
+        assert_eq!(schema.format, Some("binary".to_string()));
+    }
+
     #[test]
     fn test_complex_route_with_all_features() {
         let code = r#"
@@ -802,7 +3083,7 @@ mod tests {
         assert!(operation.request_body.is_some());
         
         // Check response
-        let response = &operation.responses["200"];
+        let response = &operation.responses["201"];
         assert!(response.content.is_some());
         
         // Build and check schemas
@@ -841,4 +3122,41 @@ mod tests {
         assert!(document.paths.contains_key("/posts"));
         assert!(document.paths.contains_key("/posts/{id}"));
     }
+
+    #[test]
+    fn test_webhooks() {
+        let mut builder = OpenApiBuilder::new();
+        let mut schema_gen = create_generator_from_code(
+            r#"
+            pub struct User {
+                pub id: u32,
+            }
+        "#,
+        );
+        let webhook = WebhookInfo {
+            name: "user.created".to_string(),
+            body: TypeInfo::new("User".to_string()),
+            doc: Some("A user signed up\n\nSent once per account.".to_string()),
+        };
+        builder.add_webhook(&webhook, &mut schema_gen);
+
+        let document = builder.build(schema_gen);
+        assert_eq!(document.openapi, "3.0.0");
+        assert!(document.webhooks.is_empty());
+        let operation = document.x_webhooks["user.created"].post.as_ref().unwrap();
+        assert_eq!(operation.summary.as_deref(), Some("A user signed up"));
+        assert_eq!(operation.description.as_deref(), Some("Sent once per account."));
+        let body = &operation.request_body.as_ref().unwrap().content["application/json"];
+        assert_eq!(body.schema.reference.as_deref(), Some("#/components/schemas/User"));
+        assert!(operation.responses.contains_key("200"));
+        // The payload schema is referenced, so it survives pruning
+        let schemas = document.components.as_ref().unwrap().schemas.as_ref().unwrap();
+        assert!(schemas.contains_key("User"));
+
+        let yaml = serde_yaml::to_string(&document).unwrap();
+        assert!(yaml.contains("x-webhooks:"));
+        let read: OpenApiDocument = serde_yaml::from_str(&yaml).unwrap();
+        assert!(read.x_webhooks.contains_key("user.created"));
+        assert!(read.extensions.is_empty());
+    }
 }
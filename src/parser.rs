@@ -1,5 +1,8 @@
+use crate::diagnostics::{DiagnosticKind, Diagnostics};
+use crate::error::Error;
 use anyhow::{Context, Result};
 use log::{debug, warn};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -48,7 +51,8 @@ impl AstParser {
     ///
     /// Returns an error if:
     /// - The file cannot be read
-    /// - The file contains invalid Rust syntax
+    /// - The file contains invalid Rust syntax; the underlying error is an
+    ///   [`Error::ParseError`] carrying the file path and the line and column of the problem
     pub fn parse_file(path: &Path) -> Result<ParsedFile> {
         debug!("Parsing file: {}", path.display());
         
@@ -58,6 +62,7 @@ impl AstParser {
         
         // Parse the file using syn
         let syntax_tree = syn::parse_file(&content)
+            .map_err(|e| Self::parse_error(path, &e))
             .with_context(|| format!("Failed to parse Rust syntax in file: {}", path.display()))?;
         
         debug!("Successfully parsed file: {}", path.display());
@@ -108,6 +113,225 @@ impl AstParser {
         
         results
     }
+
+    /// Parses multiple Rust source files, salvaging what it can from files that fail to parse.
+    ///
+    /// Every parse failure is recorded in `diagnostics`. Files rejected as a whole (for example
+    /// because of nightly-only syntax in a single function) are then split into top-level items
+    /// and each item is parsed on its own, so routes and types in the remaining items are still
+    /// found. Files from which no item could be recovered are skipped.
+    pub fn parse_files_with_diagnostics(
+        paths: &[PathBuf],
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<ParsedFile> {
+        Self::parse_files_with_progress(paths, diagnostics, false, |_, _| {})
+    }
+
+    /// Like [`AstParser::parse_files_with_diagnostics`], but logs each syntax error together
+    /// with an excerpt of the offending source and a caret under the reported column, in the
+    /// style of `rustc`, instead of the one-line message.
+    pub fn parse_files_with_error_context(
+        paths: &[PathBuf],
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<ParsedFile> {
+        Self::parse_files_with_progress(paths, diagnostics, true, |_, _| {})
+    }
+
+    /// Like [`AstParser::parse_files_with_diagnostics`] (or, if `error_context` is set,
+    /// [`AstParser::parse_files_with_error_context`]), calling `progress` with the number of
+    /// files parsed so far and the total after each file.
+    pub fn parse_files_with_progress(
+        paths: &[PathBuf],
+        diagnostics: &mut Diagnostics,
+        error_context: bool,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Vec<ParsedFile> {
+        let results = paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let result = Self::parse_file(path).inspect_err(|e| {
+                    match error_context.then(|| Self::error_context(path)).flatten() {
+                        Some(context) => warn!("{}", context),
+                        None => warn!("Failed to parse {}: {}", path.display(), e),
+                    }
+                });
+                progress(i + 1, paths.len());
+                result
+            })
+            .collect();
+        Self::parse_and_recover(results, paths, diagnostics)
+    }
+
+    /// Keep the parsed files of `results`, recording failures and recovering what items can
+    /// be salvaged from the files that failed
+    fn parse_and_recover(
+        results: Vec<Result<ParsedFile>>,
+        paths: &[PathBuf],
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<ParsedFile> {
+        let mut parsed_files = Vec::new();
+
+        for (path, result) in paths.iter().zip(results) {
+            match result {
+                Ok(parsed) => parsed_files.push(parsed),
+                Err(e) => {
+                    diagnostics.push(DiagnosticKind::ParseFailure, format!("{:#}", e));
+                    if let Some(recovered) = Self::recover_file(path) {
+                        warn!(
+                            "Recovered {} items from {}",
+                            recovered.syntax_tree.items.len(),
+                            path.display()
+                        );
+                        parsed_files.push(recovered);
+                    }
+                }
+            }
+        }
+
+        parsed_files
+    }
+
+    /// Parse each top-level item of a file separately, keeping those that parse.
+    ///
+    /// Returns `None` if the file cannot be read or tokenized, or no item could be parsed.
+    fn recover_file(path: &Path) -> Option<ParsedFile> {
+        let content = fs::read_to_string(path).ok()?;
+        let tokens: TokenStream = content.parse().ok()?;
+
+        let items: Vec<syn::Item> = split_items(tokens)
+            .into_iter()
+            .filter_map(|item_tokens| syn::parse2(item_tokens).ok())
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+
+        Some(ParsedFile {
+            path: path.to_path_buf(),
+            syntax_tree: syn::File {
+                shebang: None,
+                attrs: Vec::new(),
+                items,
+            },
+        })
+    }
+
+    /// Re-parse a file that failed to parse and render its syntax error with source context.
+    ///
+    /// Returns `None` if the file cannot be read or, unexpectedly, parses.
+    fn error_context(path: &Path) -> Option<String> {
+        let content = fs::read_to_string(path).ok()?;
+        let error = syn::parse_file(&content).err()?;
+        let start = error.span().start();
+        Some(format!(
+            "{}:{}:{}: {}\n{}",
+            path.display(),
+            start.line,
+            start.column + 1,
+            error,
+            render_source_context(&content, start.line, start.column, 2)
+        ))
+    }
+
+    /// Convert a syn error into a [`Error::ParseError`] pointing at the offending line
+    fn parse_error(path: &Path, error: &syn::Error) -> Error {
+        let start = error.span().start();
+        Error::ParseError {
+            file: path.to_path_buf(),
+            message: format!(
+                "line {}, column {}: {}",
+                start.line,
+                start.column + 1,
+                error
+            ),
+        }
+    }
+}
+
+/// Render the lines of `source` around `line` (1-based) with line-number gutters and a caret
+/// under `column` (0-based, in characters), showing `context` lines before and after.
+pub fn render_source_context(source: &str, line: usize, column: usize, context: usize) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    if line == 0 || line > lines.len() {
+        return String::new();
+    }
+    let first = line.saturating_sub(context).max(1);
+    let last = (line + context).min(lines.len());
+    let width = last.to_string().len();
+
+    let mut rendered = String::new();
+    for number in first..=last {
+        let text = lines[number - 1];
+        rendered.push_str(&format!("{:>width$} | {}\n", number, text));
+        if number == line {
+            let indent: String = text
+                .chars()
+                .take(column)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            rendered.push_str(&format!("{:>width$} | {}^\n", "", indent));
+        }
+    }
+    rendered.trim_end().to_string()
+}
+
+/// Split a file's tokens into the token streams of its top-level items.
+///
+/// An item ends at a top-level `;` or `{ ... }` block, which covers all items except ones
+/// whose signatures contain braces themselves (such as const generic arguments).
+fn split_items(tokens: TokenStream) -> Vec<TokenStream> {
+    let mut items = Vec::new();
+    let mut current = Vec::new();
+
+    for token in tokens {
+        let ends_item = match &token {
+            TokenTree::Punct(punct) => punct.as_char() == ';',
+            TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+            _ => false,
+        };
+        current.push(token);
+        if ends_item {
+            items.push(current.drain(..).collect());
+        }
+    }
+    if !current.is_empty() {
+        items.push(current.into_iter().collect());
+    }
+
+    items
+}
+
+/// Collect the `///` doc comment lines from a list of attributes.
+///
+/// Lines are joined with newlines after stripping the single leading space `rustdoc` adds.
+/// Returns `None` if there is no doc comment or it is blank.
+pub fn extract_doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(name_value) => match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit_str),
+                    ..
+                }) => Some(lit_str.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| {
+            let line = line.strip_prefix(' ').unwrap_or(&line);
+            line.trim_end().to_string()
+        })
+        .collect();
+
+    let doc = lines.join("\n").trim().to_string();
+    if doc.is_empty() {
+        None
+    } else {
+        Some(doc)
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +396,101 @@ mod tests {
         assert!(err_msg.contains("Failed to parse Rust syntax"));
     }
 
+    #[test]
+    fn test_parse_error_reports_location() {
+        let temp_dir = TempDir::new().unwrap();
+        let code = "fn ok() {}\n\nfn broken() -> {}\n";
+        let file_path = create_temp_file(&temp_dir, "broken.rs", code);
+
+        let err = AstParser::parse_file(&file_path).unwrap_err();
+        match err.downcast_ref::<Error>() {
+            Some(Error::ParseError { file, message }) => {
+                assert_eq!(file, &file_path);
+                assert!(message.starts_with("line 3, column 16:"), "{}", message);
+            }
+            other => panic!("Expected ParseError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_render_source_context() {
+        let code = "fn a() {}\nfn b() {}\nfn broken() -> {}\nfn c() {}\n";
+        assert_eq!(
+            render_source_context(code, 3, 15, 1),
+            "2 | fn b() {}\n3 | fn broken() -> {}\n  |                ^\n4 | fn c() {}"
+        );
+        assert_eq!(
+            render_source_context(code, 1, 0, 2),
+            "1 | fn a() {}\n  | ^\n2 | fn b() {}\n3 | fn broken() -> {}"
+        );
+        assert!(render_source_context(code, 9, 0, 2).is_empty());
+
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = create_temp_file(&temp_dir, "broken.rs", code);
+        let context = AstParser::error_context(&file_path).unwrap();
+        assert!(context.starts_with(&format!("{}:3:16: ", file_path.display())), "{}", context);
+        assert!(context.ends_with("  |                ^\n4 | fn c() {}"), "{}", context);
+    }
+
+    #[test]
+    fn test_parse_files_with_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = vec![
+            create_temp_file(&temp_dir, "a.rs", "pub fn a() {}"),
+            create_temp_file(&temp_dir, "b.rs", "fn broken( {"),
+            create_temp_file(&temp_dir, "c.rs", "pub fn c() {}"),
+        ];
+
+        let mut reports = Vec::new();
+        let mut diagnostics = Diagnostics::new();
+        let parsed = AstParser::parse_files_with_progress(&paths, &mut diagnostics, true, |n, t| {
+            reports.push((n, t))
+        });
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(reports, vec![(1, 3), (2, 3), (3, 3)]);
+        assert_eq!(diagnostics.count(DiagnosticKind::ParseFailure), 1);
+    }
+
+    #[test]
+    fn test_parse_files_recovers_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let code = r#"
+            pub struct User {
+                pub id: u32,
+            }
+
+            fn nightly() -> u32 {
+                do yeet 1;
+            }
+
+            async fn list_users() -> Json<Vec<User>> {
+                todo!()
+            }
+        "#;
+        let broken = create_temp_file(&temp_dir, "broken.rs", code);
+        let garbage = create_temp_file(&temp_dir, "garbage.rs", "fn broken( {");
+
+        let mut diagnostics = Diagnostics::new();
+        let parsed =
+            AstParser::parse_files_with_diagnostics(&[broken.clone(), garbage], &mut diagnostics);
+
+        assert_eq!(diagnostics.count(DiagnosticKind::ParseFailure), 2);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].path, broken);
+        let names: Vec<String> = parsed[0]
+            .syntax_tree
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Struct(s) => Some(s.ident.to_string()),
+                syn::Item::Fn(f) => Some(f.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, vec!["User", "list_users"]);
+    }
+
     #[test]
     fn test_parse_nonexistent_file() {
         let result = AstParser::parse_file(Path::new("/nonexistent/file.rs"));
@@ -306,4 +625,26 @@ mod tests {
         // Should have multiple items (use statements, struct, impl, function)
         assert!(parsed.syntax_tree.items.len() >= 4);
     }
+
+    #[test]
+    fn test_extract_doc_comment() {
+        let item: syn::ItemStruct = syn::parse_str(
+            r#"
+            /// A registered user.
+            ///
+            ///   Indented detail.
+            #[derive(Debug)]
+            pub struct User;
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            extract_doc_comment(&item.attrs),
+            Some("A registered user.\n\n  Indented detail.".to_string())
+        );
+
+        let undocumented: syn::ItemStruct = syn::parse_str("pub struct Plain;").unwrap();
+        assert_eq!(extract_doc_comment(&undocumented.attrs), None);
+    }
 }
@@ -0,0 +1,155 @@
+//! Machine-readable summary of a generation run.
+//!
+//! The OpenAPI document says what was documented, not how complete it is. A
+//! [`GenerationReport`] records what the run saw (files, frameworks, routes) and what it could
+//! not work out (unresolved handlers, placeholder schemas, other diagnostics), so CI dashboards
+//! and other tooling can track documentation completeness without scraping log output.
+
+use crate::diagnostics::{Diagnostic, DiagnosticKind, Diagnostics};
+use crate::extractor::RouteInfo;
+use serde::Serialize;
+
+/// Summary of a generation run, written by the CLI's `--report` flag
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationReport {
+    /// Source files seen by the run
+    pub files: FileCounts,
+    /// Frameworks routes were extracted for, detected or specified
+    pub frameworks: Vec<String>,
+    /// Every documented route
+    pub routes: Vec<RouteReport>,
+    /// Handlers referenced by routes whose functions could not be found, as written at the
+    /// route registration
+    pub unresolved_handlers: Vec<String>,
+    /// Types documented as `object` placeholders because their definition was not found
+    pub placeholder_types: Vec<String>,
+    /// Every diagnostic recorded during the run
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Number of source files scanned, parsed and skipped because they failed to parse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct FileCounts {
+    /// Rust files found by the scanner
+    pub scanned: usize,
+    /// Files parsed successfully
+    pub parsed: usize,
+    /// Files skipped because they failed to parse
+    pub failed: usize,
+}
+
+/// A documented route
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RouteReport {
+    /// HTTP method, e.g. `GET`
+    pub method: String,
+    /// URL path as registered
+    pub path: String,
+    /// Handler path, module-qualified when the handler was resolved
+    pub handler: String,
+    /// Whether the handler function was found; services serving files count as resolved
+    pub resolved: bool,
+    /// Whether a request body type was documented
+    pub has_request_body: bool,
+    /// Whether a response body type was documented
+    pub has_response: bool,
+}
+
+impl GenerationReport {
+    /// Summarize a run that scanned `files_scanned` files, parsed `files_parsed` of them and
+    /// documented `routes`
+    pub fn new(
+        files_scanned: usize,
+        files_parsed: usize,
+        frameworks: Vec<String>,
+        routes: &[RouteInfo],
+        placeholder_types: Vec<String>,
+        diagnostics: &Diagnostics,
+    ) -> Self {
+        let routes: Vec<RouteReport> = routes.iter().map(RouteReport::from).collect();
+        let mut unresolved_handlers: Vec<String> = routes
+            .iter()
+            .filter(|route| !route.resolved)
+            .map(|route| route.handler.clone())
+            .collect();
+        unresolved_handlers.sort();
+        unresolved_handlers.dedup();
+
+        let mut placeholder_types = placeholder_types;
+        placeholder_types.sort();
+
+        Self {
+            files: FileCounts {
+                scanned: files_scanned,
+                parsed: files_parsed,
+                failed: diagnostics.count(DiagnosticKind::ParseFailure),
+            },
+            frameworks,
+            routes,
+            unresolved_handlers,
+            placeholder_types,
+            diagnostics: diagnostics.iter().cloned().collect(),
+        }
+    }
+}
+
+impl From<&RouteInfo> for RouteReport {
+    fn from(route: &RouteInfo) -> Self {
+        Self {
+            method: route.method_str().to_string(),
+            path: route.path.clone(),
+            handler: route.handler_path.clone(),
+            resolved: route.handler_resolved || route.serves_files,
+            has_request_body: route.request_body.is_some(),
+            has_response: route.response_type.is_some(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::{HttpMethod, TypeInfo};
+
+    #[test]
+    fn test_report_routes_and_unresolved_handlers() {
+        let mut resolved = RouteInfo::new(
+            "/users".to_string(),
+            HttpMethod::Post,
+            "handlers::create_user".to_string(),
+        );
+        resolved.handler_resolved = true;
+        resolved.request_body = Some(TypeInfo::new("CreateUser".to_string()));
+        let unresolved =
+            RouteInfo::new("/health".to_string(), HttpMethod::Get, "health".to_string());
+
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(DiagnosticKind::ParseFailure, "Failed to parse src/broken.rs");
+        let report = GenerationReport::new(
+            3,
+            2,
+            vec!["axum".to_string()],
+            &[resolved, unresolved],
+            vec!["Uuid".to_string()],
+            &diagnostics,
+        );
+
+        assert_eq!(report.files, FileCounts { scanned: 3, parsed: 2, failed: 1 });
+        assert_eq!(report.unresolved_handlers, vec!["health"]);
+        assert_eq!(
+            report.routes[0],
+            RouteReport {
+                method: "POST".to_string(),
+                path: "/users".to_string(),
+                handler: "handlers::create_user".to_string(),
+                resolved: true,
+                has_request_body: true,
+                has_response: false,
+            }
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["diagnostics"][0]["kind"], "parse_failure");
+        assert_eq!(json["placeholder_types"][0], "Uuid");
+    }
+}
@@ -21,6 +21,10 @@ use walkdir::WalkDir;
 /// ```
 pub struct FileScanner {
     root_path: PathBuf,
+    /// Maximum number of directory levels to descend below the root
+    max_depth: Option<usize>,
+    /// Maximum number of Rust files to collect
+    max_files: Option<usize>,
 }
 
 /// Result of directory scanning operation.
@@ -31,6 +35,10 @@ pub struct ScanResult {
     pub rust_files: Vec<PathBuf>,
     /// Warning messages for any issues encountered (e.g., inaccessible directories)
     pub warnings: Vec<String>,
+    /// Directories not scanned because they are deeper than the maximum depth
+    pub skipped_directories: usize,
+    /// Rust files found after the maximum number of files was collected
+    pub skipped_files: usize,
 }
 
 impl FileScanner {
@@ -40,7 +48,30 @@ impl FileScanner {
     ///
     /// * `root_path` - The root directory to scan for Rust files
     pub fn new(root_path: PathBuf) -> Self {
-        Self { root_path }
+        Self {
+            root_path,
+            max_depth: None,
+            max_files: None,
+        }
+    }
+
+    /// Limits how many directory levels below the root are scanned.
+    ///
+    /// A depth of `0` only scans files directly in the root directory, `1` also scans its
+    /// immediate subdirectories, and so on. A warning counting the skipped directories is added
+    /// to the [`ScanResult`] if any were deeper.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Stops collecting Rust files once `max_files` have been collected.
+    ///
+    /// A warning counting the skipped files is added to the [`ScanResult`] if more were
+    /// available.
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = Some(max_files);
+        self
     }
 
     /// Scans the directory tree and collects all `.rs` files.
@@ -63,6 +94,8 @@ impl FileScanner {
     pub fn scan(&self) -> Result<ScanResult> {
         let mut rust_files = Vec::new();
         let mut warnings = Vec::new();
+        let mut skipped_directories = 0;
+        let mut skipped_files = 0;
 
         for entry in WalkDir::new(&self.root_path)
             .into_iter()
@@ -76,8 +109,18 @@ impl FileScanner {
                 let file_name = e.file_name().to_string_lossy();
                 let is_hidden = file_name.starts_with('.');
                 let is_target = file_name == "target";
-                
-                !is_hidden && !is_target
+                if is_hidden || is_target {
+                    return false;
+                }
+
+                // WalkDir counts files directly in the root as depth 1, so directories at
+                // depth 1 hold the files of depth 1
+                let too_deep = self.max_depth.is_some_and(|max_depth| e.depth() > max_depth);
+                if too_deep && e.file_type().is_dir() {
+                    skipped_directories += 1;
+                    return false;
+                }
+                true
             })
         {
             match entry {
@@ -86,7 +129,11 @@ impl FileScanner {
                     
                     // Check if it's a .rs file
                     if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("rs") {
-                        rust_files.push(path.to_path_buf());
+                        if self.max_files == Some(rust_files.len()) {
+                            skipped_files += 1;
+                        } else {
+                            rust_files.push(path.to_path_buf());
+                        }
                     }
                 }
                 Err(e) => {
@@ -98,9 +145,30 @@ impl FileScanner {
             }
         }
 
+        if skipped_directories > 0 {
+            let warning = format!(
+                "Skipped {} directories deeper than the limit of {} levels",
+                skipped_directories,
+                self.max_depth.unwrap_or_default()
+            );
+            warn!("{}", warning);
+            warnings.push(warning);
+        }
+        if skipped_files > 0 {
+            let warning = format!(
+                "Skipped {} Rust files after reaching the limit of {} files",
+                skipped_files,
+                rust_files.len()
+            );
+            warn!("{}", warning);
+            warnings.push(warning);
+        }
+
         Ok(ScanResult {
             rust_files,
             warnings,
+            skipped_directories,
+            skipped_files,
         })
     }
 }
@@ -257,4 +325,68 @@ mod tests {
             "main.rs"
         );
     }
+
+    #[test]
+    fn test_scan_with_max_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("src/models")).unwrap();
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn test() {}").unwrap();
+        fs::write(root.join("src/models/user.rs"), "struct User {}").unwrap();
+
+        let result = FileScanner::new(root.to_path_buf())
+            .with_max_depth(0)
+            .scan()
+            .unwrap();
+        assert_eq!(result.rust_files.len(), 1);
+        assert_eq!(result.skipped_directories, 1);
+
+        let result = FileScanner::new(root.to_path_buf())
+            .with_max_depth(1)
+            .scan()
+            .unwrap();
+        assert_eq!(result.rust_files.len(), 2);
+        assert_eq!(result.skipped_directories, 1);
+        assert_eq!(
+            result.warnings,
+            vec!["Skipped 1 directories deeper than the limit of 1 levels"]
+        );
+
+        let result = FileScanner::new(root.to_path_buf())
+            .with_max_depth(2)
+            .scan()
+            .unwrap();
+        assert_eq!(result.rust_files.len(), 3);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_scan_with_max_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        for i in 0..5 {
+            fs::write(root.join(format!("file{}.rs", i)), "fn f() {}").unwrap();
+        }
+
+        let result = FileScanner::new(root.to_path_buf())
+            .with_max_files(3)
+            .scan()
+            .unwrap();
+        assert_eq!(result.rust_files.len(), 3);
+        assert_eq!(result.skipped_files, 2);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("Skipped 2 Rust files"));
+        assert!(result.warnings[0].contains("limit of 3 files"));
+
+        // Reaching the limit exactly is not a warning
+        let result = FileScanner::new(root.to_path_buf())
+            .with_max_files(5)
+            .scan()
+            .unwrap();
+        assert_eq!(result.rust_files.len(), 5);
+        assert!(result.warnings.is_empty());
+    }
 }
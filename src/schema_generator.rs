@@ -1,8 +1,11 @@
 use crate::extractor::{Parameter, ParameterLocation, TypeInfo};
-use crate::type_resolver::{PrimitiveType, TypeKind, TypeResolver};
+use crate::type_resolver::{
+    EnumRepresentation, FieldDef, LengthLimits, PrimitiveType, StructDef, TypeKind,
+    TypeResolver, VariantFields,
+};
 use log::debug;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 /// Schema generator - converts Rust types to OpenAPI schemas
 pub struct SchemaGenerator {
@@ -10,11 +13,152 @@ pub struct SchemaGenerator {
     type_resolver: TypeResolver,
     /// Cache of generated schemas to avoid duplication
     schemas: HashMap<String, Schema>,
+    /// Names of types that could not be resolved and fell back to `object` placeholders
+    unresolved_types: BTreeSet<String>,
+    /// Unresolved type names that serialize as strings -> `format` of the string
+    string_types: HashMap<String, Option<String>>,
+    /// How fields marked `#[serde(flatten)]` are documented
+    flatten_mode: FlattenMode,
+    /// Whether decimal types are documented as JSON numbers instead of strings
+    decimals_as_numbers: bool,
+    /// Instantiations of generic structs (`ApiResponse<User>`) -> name of their component
+    instances: HashMap<String, String>,
+    /// How struct fields of type `Option<T>` are documented
+    option_handling: OptionHandling,
+    /// Names of types documented as `object` placeholders instead of component schemas
+    excluded_types: HashSet<String>,
+    /// Newtype structs whose wrapped type is being documented in place of their own, so that
+    /// recursive newtypes fall back to a component reference
+    inlined_newtypes: HashSet<String>,
+    /// Whether placeholders of unresolved types carry a `TODO` description and `x-unresolved`
+    mark_unresolved: bool,
+    /// Prefix of the `$ref` of component schemas, [`SCHEMA_REF_PREFIX`] by default
+    ref_prefix: String,
+}
+
+/// How struct fields of type `Option<T>` are documented.
+///
+/// serde writes `None` as `null` unless the field is also marked
+/// `#[serde(skip_serializing_if = "Option::is_none")]`, and accepts a missing field as `None`,
+/// so by default such fields are both nullable and optional. Fields marked
+/// `skip_serializing_if` are optional and never nullable, whatever the handling.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptionHandling {
+    /// Keep the field required, but mark it `nullable`
+    Nullable,
+    /// Leave the field out of `required`, without marking it `nullable`
+    Optional,
+    /// Leave the field out of `required` and mark it `nullable`
+    #[default]
+    Both,
+}
+
+/// How struct fields marked `#[serde(flatten)]` are documented
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlattenMode {
+    /// Copy the properties of the flattened type into the enclosing object
+    #[default]
+    Inline,
+    /// Compose the enclosing struct with `allOf` from a reference to the flattened type and an
+    /// object of the struct's own fields, keeping the flattened type a separate component
+    AllOf,
+}
+
+/// Types from `std` and common crates that serialize as strings, matched by their last path
+/// segment, with the `format` of their string form. `IpAddr` may hold either address family,
+/// which OpenAPI formats cannot express, so it is a plain string.
+const STRING_TYPES: &[(&str, Option<&str>)] = &[
+    ("Ipv4Addr", Some("ipv4")),
+    ("Ipv6Addr", Some("ipv6")),
+    ("IpAddr", None),
+    ("SocketAddr", None),
+    ("SocketAddrV4", None),
+    ("SocketAddrV6", None),
+    ("PathBuf", None),
+    ("Path", None),
+    ("Url", Some("uri")),
+    ("Decimal", Some("decimal")),
+    ("BigDecimal", Some("decimal")),
+];
+
+/// Prefix of the `$ref` of a component schema, followed by the component's name
+pub const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Arbitrary-precision decimal types (`rust_decimal`, `bigdecimal`), serialized as strings
+/// unless their crate's number serialization is enabled
+const DECIMAL_TYPES: &[&str] = &["Decimal", "BigDecimal"];
+
+/// Component name of an instantiation of a generic struct: the type names joined with `_`,
+/// outermost first (`ApiResponse<User>` is `ApiResponse_User`, `Page<Vec<Post>>` is
+/// `Page_Vec_Post`, `Pair<u32, String>` is `Pair_u32_String`).
+///
+/// Distinct instantiations can share a name (`A<B<C>, D>` and `A<B<C, D>>`); the generator
+/// appends a numeric suffix to the later one.
+pub fn instance_name(type_info: &TypeInfo) -> String {
+    let outer = if type_info.is_option {
+        "Option"
+    } else if type_info.is_vec {
+        "Vec"
+    } else if type_info.is_result {
+        "Result"
+    } else {
+        &type_info.name
+    };
+    std::iter::once(outer.to_string())
+        .chain(type_info.generic_args.iter().map(instance_name))
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// The Rust spelling of a type, e.g. `ApiResponse<Vec<User>>`, identifying an instantiation
+fn type_key(type_info: &TypeInfo) -> String {
+    if type_info.generic_args.is_empty() {
+        return type_info.name.clone();
+    }
+    let outer = if type_info.is_option {
+        "Option"
+    } else if type_info.is_vec {
+        "Vec"
+    } else if type_info.is_result {
+        "Result"
+    } else {
+        &type_info.name
+    };
+    let args: Vec<String> = type_info.generic_args.iter().map(type_key).collect();
+    format!("{}<{}>", outer, args.join(", "))
+}
+
+/// Replace the type parameters named in `substitutions` by their arguments throughout
+/// `type_info`
+fn substitute(type_info: &TypeInfo, substitutions: &HashMap<String, TypeInfo>) -> TypeInfo {
+    if type_info.generic_args.is_empty() {
+        return substitutions
+            .get(&type_info.name)
+            .cloned()
+            .unwrap_or_else(|| type_info.clone());
+    }
+    let mut result = type_info.clone();
+    result.generic_args = type_info
+        .generic_args
+        .iter()
+        .map(|arg| substitute(arg, substitutions))
+        .collect();
+    // `Option`, `Vec` and `Result` carry the name of their first argument
+    if result.is_option || result.is_vec || result.is_result {
+        result.name = result.generic_args[0].name.clone();
+    }
+    result
 }
 
 /// OpenAPI Schema definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Schema {
+    /// Display title (the Rust type name for component schemas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Description taken from the type's doc comment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     /// The type of the schema (string, integer, object, array, etc.)
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub schema_type: Option<String>,
@@ -24,22 +168,70 @@ pub struct Schema {
     /// Required field names for object types
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
+    /// Schema of the values of objects with dynamic keys (maps)
+    #[serde(rename = "additionalProperties", skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<Box<Schema>>,
     /// Items schema for array types
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<Schema>>,
     /// Enum values for enum types
     #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
-    pub enum_values: Option<Vec<String>>,
+    pub enum_values: Option<Vec<serde_json::Value>>,
+    /// Alternative schemas, exactly one of which must match (enums with data variants)
+    #[serde(rename = "oneOf", skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<Schema>>,
+    /// Schemas that must all match (structs composed from flattened fields)
+    #[serde(rename = "allOf", skip_serializing_if = "Option::is_none")]
+    pub all_of: Option<Vec<Schema>>,
+    /// Property telling the `oneOf` alternatives apart (internally and adjacently tagged enums)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
     /// Reference to another schema
     #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
     pub reference: Option<String>,
     /// Format for primitive types (e.g., "int32", "int64", "float", "double")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
+    /// Lower bound for numeric types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<i64>,
+    /// Minimum length of string types
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    /// Maximum length of string types
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    /// Whether the value is only sent in responses
+    #[serde(rename = "readOnly", skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    /// Whether the value is only sent in requests
+    #[serde(rename = "writeOnly", skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+    /// Whether the value may be `null`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+    /// Vendor extensions (`x-internal`)
+    #[serde(
+        flatten,
+        deserialize_with = "crate::openapi_builder::deserialize_extensions"
+    )]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Fields this crate does not model (`pattern`, `maximum`, `default`, ...), kept so that
+    /// a document read from disk is written back without losing them
+    #[serde(flatten, deserialize_with = "crate::openapi_builder::deserialize_extra")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Discriminator of a `oneOf` schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Discriminator {
+    /// Name of the property holding the variant name
+    #[serde(rename = "propertyName")]
+    pub property_name: String,
 }
 
 /// Property definition for object schemas
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Property {
     /// The type of the property
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
@@ -53,6 +245,44 @@ pub struct Property {
     /// Format for primitive types
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
+    /// Lower bound for numeric properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<i64>,
+    /// Minimum length of string properties
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    /// Maximum length of string properties
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    /// Whether the property is only sent in responses (`#[serde(skip_deserializing)]`)
+    #[serde(rename = "readOnly", skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    /// Whether the property is only sent in requests (`#[serde(skip_serializing)]`)
+    #[serde(rename = "writeOnly", skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+    /// Allowed values (the variant name of tag properties)
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<serde_json::Value>>,
+    /// Properties of inline object properties (struct variants of enums)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<HashMap<String, Property>>,
+    /// Required property names of inline object properties
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+    /// Schema of the values of map properties
+    #[serde(rename = "additionalProperties", skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<Box<Schema>>,
+    /// Whether the value may be `null` (`Option<T>` fields)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+    /// Wrapper around the reference of a nullable component property, as OpenAPI 3.0 ignores
+    /// the siblings of a `$ref`
+    #[serde(rename = "allOf", skip_serializing_if = "Option::is_none")]
+    pub all_of: Option<Vec<Schema>>,
+    /// Fields this crate does not model (`pattern`, `maximum`, `default`, vendor
+    /// extensions), kept when a document is read from disk and written back
+    #[serde(flatten, deserialize_with = "crate::openapi_builder::deserialize_unmodeled")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Parameter schema for OpenAPI parameters
@@ -67,6 +297,18 @@ pub struct ParameterSchema {
     pub required: bool,
     /// Schema for the parameter
     pub schema: Schema,
+    /// Description of the parameter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the parameter is deprecated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// Serialization style, `form` for multi-value query parameters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// Whether array values are repeated, one `name=value` pair each (`?tags=a&tags=b`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
 }
 
 impl SchemaGenerator {
@@ -76,7 +318,90 @@ impl SchemaGenerator {
         Self {
             type_resolver,
             schemas: HashMap::new(),
+            unresolved_types: BTreeSet::new(),
+            string_types: STRING_TYPES
+                .iter()
+                .map(|(name, format)| (name.to_string(), format.map(str::to_string)))
+                .collect(),
+            flatten_mode: FlattenMode::default(),
+            decimals_as_numbers: false,
+            instances: HashMap::new(),
+            option_handling: OptionHandling::default(),
+            excluded_types: HashSet::new(),
+            inlined_newtypes: HashSet::new(),
+            mark_unresolved: false,
+            ref_prefix: SCHEMA_REF_PREFIX.to_string(),
+        }
+    }
+
+    /// Document `Decimal` and `BigDecimal` as JSON numbers, for projects serializing them with
+    /// their crate's number representation, instead of `decimal` strings
+    pub fn with_decimal_numbers(mut self, enabled: bool) -> Self {
+        self.decimals_as_numbers = enabled;
+        self
+    }
+
+    /// Mark the `object` placeholders of unresolved types with a `TODO: unresolved type X`
+    /// description and an `x-unresolved: true` extension, so they stand out from empty objects
+    pub fn with_unresolved_markers(mut self, enabled: bool) -> Self {
+        self.mark_unresolved = enabled;
+        self
+    }
+
+    /// Reference component schemas as `{prefix}{name}`, e.g. `#/definitions/User` for tools
+    /// still expecting Swagger 2.0 references. The document builder lists the schemas where
+    /// the prefix points, see [`crate::openapi_builder::schema_section`].
+    pub fn with_ref_prefix(mut self, prefix: String) -> Self {
+        self.ref_prefix = prefix;
+        self
+    }
+
+    /// Prefix of the `$ref` of component schemas
+    pub fn ref_prefix(&self) -> &str {
+        &self.ref_prefix
+    }
+
+    /// Document struct fields of type `Option<T>` according to `handling`
+    pub fn with_option_handling(mut self, handling: OptionHandling) -> Self {
+        self.option_handling = handling;
+        self
+    }
+
+    /// Document fields marked `#[serde(flatten)]` according to `mode`
+    pub fn with_flatten_mode(mut self, mode: FlattenMode) -> Self {
+        self.flatten_mode = mode;
+        self
+    }
+
+    /// Document the types named in `names` as `object` placeholders without component schemas,
+    /// for internal types such as connection pools that leak into handler signatures
+    pub fn exclude_types(mut self, names: HashSet<String>) -> Self {
+        self.excluded_types.extend(names);
+        self
+    }
+
+    /// Document the type named `name` as a string with the given `format`, unless the project
+    /// defines a type of that name. Replaces the built-in entry for `name`, if any.
+    pub fn with_string_type(mut self, name: String, format: Option<String>) -> Self {
+        self.string_types.insert(name, format);
+        self
+    }
+
+    /// The string schema of a type registered as serializing to a string (a number schema for
+    /// decimal types documented as numbers)
+    fn string_type_schema(&self, name: &str) -> Option<Schema> {
+        if self.decimals_as_numbers && DECIMAL_TYPES.contains(&name) {
+            return Some(Schema {
+                schema_type: Some("number".to_string()),
+                ..Default::default()
+            });
         }
+        let format = self.string_types.get(name)?;
+        Some(Schema {
+            schema_type: Some("string".to_string()),
+            format: format.clone(),
+            ..Default::default()
+        })
     }
 
     /// Generate a schema for a TypeInfo
@@ -90,80 +415,149 @@ impl SchemaGenerator {
             }
         }
 
+        // Handle Result<T, E> - the successful value is what gets serialized
+        if let Some(ok) = type_info.ok_type() {
+            return self.generate_schema(ok);
+        }
+
+        // Handle impl Trait (the concrete type is unknown by design)
+        if type_info.is_impl_trait {
+            return Schema {
+                schema_type: Some("object".to_string()),
+                ..Default::default()
+            };
+        }
+
+        // Trait objects are deliberately dynamic, unlike types that failed to resolve
+        if type_info.is_trait_object {
+            return Schema {
+                schema_type: Some("object".to_string()),
+                description: Some(format!(
+                    "Trait object `dyn {}`; the concrete type is only known at run time",
+                    type_info.name
+                )),
+                ..Default::default()
+            };
+        }
+
+        // Handle maps - dynamic keys serialize as a JSON object of the value type
+        if type_info.is_map {
+            return Schema {
+                schema_type: Some("object".to_string()),
+                additional_properties: self.map_values_schema(type_info),
+                ..Default::default()
+            };
+        }
+
+        // Handle Vec<u8> and Bytes - binary data is encoded as a base64 string
+        if self.is_byte_buffer(type_info) {
+            return Schema {
+                schema_type: Some("string".to_string()),
+                format: Some("byte".to_string()),
+                ..Default::default()
+            };
+        }
+
         // Handle Vec<T> - generate array schema
         if type_info.is_vec {
             if let Some(inner) = type_info.generic_args.first() {
                 let items_schema = self.generate_schema(inner);
                 return Schema {
                     schema_type: Some("array".to_string()),
-                    properties: None,
-                    required: None,
                     items: Some(Box::new(items_schema)),
-                    enum_values: None,
-                    reference: None,
-                    format: None,
+                    ..Default::default()
                 };
             }
         }
 
+        if self.excluded_types.contains(&type_info.name) {
+            debug!("Type {} is excluded, using object placeholder", type_info.name);
+            return Schema {
+                schema_type: Some("object".to_string()),
+                ..Default::default()
+            };
+        }
+
         // Try to resolve as a primitive type first
         if let Some(resolved) = self.type_resolver.resolve_type(&type_info.name) {
             match resolved.kind {
                 TypeKind::Primitive(prim) => {
                     return self.primitive_to_schema(&prim);
                 }
-                TypeKind::Struct(_) => {
+                TypeKind::Struct(struct_def) => {
+                    // Newtypes serialize as the type they wrap
+                    if let Some(inner) = self.enter_newtype(type_info, &struct_def) {
+                        let schema = self.generate_schema(&inner);
+                        self.inlined_newtypes.remove(&type_info.name);
+                        return limit_length(schema, struct_def.length);
+                    }
+
                     // For structs, return a reference and ensure the schema is generated
-                    self.generate_struct_schema(&type_info.name);
+                    let name = self.struct_component(type_info, &struct_def);
                     return Schema {
-                        schema_type: None,
-                        properties: None,
-                        required: None,
-                        items: None,
-                        enum_values: None,
-                        reference: Some(format!("#/components/schemas/{}", type_info.name)),
-                        format: None,
+                        reference: Some(self.schema_ref(&name)),
+                        ..Default::default()
                     };
                 }
                 TypeKind::Enum(_) => {
                     // For enums, return a reference and ensure the schema is generated
                     self.generate_enum_schema(&type_info.name);
                     return Schema {
-                        schema_type: None,
-                        properties: None,
-                        required: None,
-                        items: None,
-                        enum_values: None,
-                        reference: Some(format!("#/components/schemas/{}", type_info.name)),
-                        format: None,
+                        reference: Some(self.schema_ref(&type_info.name)),
+                        ..Default::default()
                     };
                 }
                 TypeKind::Generic(_) => {
                     // Generic types - use a placeholder
                     return Schema {
                         schema_type: Some("object".to_string()),
-                        properties: None,
-                        required: None,
-                        items: None,
-                        enum_values: None,
-                        reference: None,
-                        format: None,
+                        ..Default::default()
                     };
                 }
             }
         }
 
+        // Types such as `Ipv4Addr` or `Url` that serialize as (formatted) strings
+        if let Some(schema) = self.string_type_schema(&type_info.name) {
+            return schema;
+        }
+
         // Fallback for unknown types
         debug!("Unknown type: {}, using object placeholder", type_info.name);
-        Schema {
+        self.unresolved_schema(&type_info.name)
+    }
+
+    /// `$ref` of the component schema named `name`
+    fn schema_ref(&self, name: &str) -> String {
+        format!("{}{}", self.ref_prefix, name)
+    }
+
+    /// Record `name` as unresolved and return its `object` placeholder
+    fn unresolved_schema(&mut self, name: &str) -> Schema {
+        self.unresolved_types.insert(name.to_string());
+        let mut schema = Schema {
             schema_type: Some("object".to_string()),
-            properties: None,
-            required: None,
-            items: None,
-            enum_values: None,
-            reference: None,
-            format: None,
+            ..Default::default()
+        };
+        if self.mark_unresolved {
+            schema.description = Some(format!("TODO: unresolved type {}", name));
+            schema.extensions.insert("x-unresolved".to_string(), serde_json::Value::Bool(true));
+        }
+        schema
+    }
+
+    /// Whether a type holds raw binary data: `Vec<u8>`, or `bytes::Bytes`/`BytesMut` unless
+    /// the project defines its own type with that name
+    fn is_byte_buffer(&self, type_info: &TypeInfo) -> bool {
+        if type_info.is_vec {
+            return type_info
+                .generic_args
+                .first()
+                .is_some_and(|inner| inner.name == "u8" && !inner.is_vec && !inner.is_option);
         }
+        !type_info.is_option
+            && matches!(type_info.name.as_str(), "Bytes" | "BytesMut")
+            && self.type_resolver.find_struct_definition(&type_info.name).is_none()
     }
 
     /// Convert a primitive type to an OpenAPI schema
@@ -178,32 +572,122 @@ impl SchemaGenerator {
                 ("integer", Some("int32"))
             }
             PrimitiveType::U64 | PrimitiveType::U128 => ("integer", Some("int64")),
+            PrimitiveType::Isize | PrimitiveType::Usize => ("integer", Some("int64")),
             PrimitiveType::F32 => ("number", Some("float")),
             PrimitiveType::F64 => ("number", Some("double")),
             PrimitiveType::Bool => ("boolean", None),
             PrimitiveType::Char => ("string", None),
         };
 
+        // Unsigned values can never be negative
+        let minimum = matches!(
+            primitive,
+            PrimitiveType::U8
+                | PrimitiveType::U16
+                | PrimitiveType::U32
+                | PrimitiveType::U64
+                | PrimitiveType::U128
+                | PrimitiveType::Usize
+        )
+        .then_some(0);
+
         Schema {
             schema_type: Some(schema_type.to_string()),
-            properties: None,
-            required: None,
-            items: None,
-            enum_values: None,
-            reference: None,
             format: format.map(|s| s.to_string()),
+            minimum,
+            ..Default::default()
+        }
+    }
+
+    /// Schema of the values of a map type, if its value type is known
+    fn map_values_schema(&mut self, type_info: &TypeInfo) -> Option<Box<Schema>> {
+        let value = type_info.generic_args.get(1)?;
+        Some(Box::new(self.generate_schema(value)))
+    }
+
+    /// Generate the component schema of a struct type and return the component name.
+    ///
+    /// Generic structs used with type arguments are monomorphized: every distinct
+    /// instantiation gets its own component, named by [`instance_name`] (`ApiResponse<User>`
+    /// is `ApiResponse_User`) and reused whenever the same instantiation appears again.
+    fn struct_component(&mut self, type_info: &TypeInfo, struct_def: &StructDef) -> String {
+        if struct_def.generics.is_empty() || type_info.generic_args.is_empty() {
+            self.generate_struct_schema(&type_info.name, &type_info.name, &HashMap::new());
+            return type_info.name.clone();
+        }
+
+        let key = type_key(type_info);
+        if let Some(name) = self.instances.get(&key) {
+            return name.clone();
+        }
+        let name = self.unique_instance_name(instance_name(type_info));
+        debug!("Instantiating {} as component {}", key, name);
+        self.instances.insert(key, name.clone());
+
+        let substitutions = struct_def
+            .generics
+            .iter()
+            .cloned()
+            .zip(type_info.generic_args.iter().cloned())
+            .collect();
+        self.generate_struct_schema(&type_info.name, &name, &substitutions);
+        name
+    }
+
+    /// The type wrapped by a newtype struct, with the struct's type arguments filled in, unless
+    /// the newtype is already being documented in place of its wrapped type (`struct
+    /// Tree(Vec<Tree>)`). The caller removes the newtype from `inlined_newtypes` when done.
+    fn enter_newtype(&mut self, type_info: &TypeInfo, struct_def: &StructDef) -> Option<TypeInfo> {
+        let inner = struct_def.newtype.as_ref()?;
+        if !self.inlined_newtypes.insert(type_info.name.clone()) {
+            return None;
+        }
+        let substitutions = struct_def
+            .generics
+            .iter()
+            .cloned()
+            .zip(type_info.generic_args.iter().cloned())
+            .collect();
+        Some(substitute(inner, &substitutions))
+    }
+
+    /// `name`, or `name` with the first free `_2`, `_3`, ... suffix if a component, another
+    /// instantiation or a type of the project already uses it
+    fn unique_instance_name(&self, name: String) -> String {
+        let taken = |candidate: &str| {
+            self.schemas.contains_key(candidate)
+                || self.instances.values().any(|instance| instance == candidate)
+                || self.type_resolver.find_struct_definition(candidate).is_some()
+                || self.type_resolver.find_enum_definition(candidate).is_some()
+        };
+        if !taken(&name) {
+            return name;
         }
+        (2..)
+            .map(|n| format!("{}_{}", name, n))
+            .find(|candidate| !taken(candidate))
+            .expect("unbounded suffixes")
     }
 
-    /// Generate a schema for a struct type and add it to the schemas collection
-    fn generate_struct_schema(&mut self, type_name: &str) {
+    /// Generate the schema of the struct `type_name`, with its type parameters replaced
+    /// according to `substitutions`, and add it to the schemas collection as `component`
+    fn generate_struct_schema(
+        &mut self,
+        type_name: &str,
+        component: &str,
+        substitutions: &HashMap<String, TypeInfo>,
+    ) {
         // Check if already generated
-        if self.schemas.contains_key(type_name) {
-            debug!("Schema for {} already exists", type_name);
+        if self.schemas.contains_key(component) {
+            debug!("Schema for {} already exists", component);
+            return;
+        }
+        if self.excluded_types.contains(type_name) {
+            debug!("Type {} is excluded, not generating a schema", type_name);
             return;
         }
 
-        debug!("Generating struct schema for: {}", type_name);
+        debug!("Generating struct schema for: {}", component);
 
         // Resolve the type
         let resolved = match self.type_resolver.resolve_type(type_name) {
@@ -215,49 +699,195 @@ impl SchemaGenerator {
         };
 
         if let TypeKind::Struct(struct_def) = resolved.kind {
-            let mut properties = HashMap::new();
-            let mut required = Vec::new();
+            // Register a placeholder before processing fields so that recursive and
+            // mutually recursive types resolve to a reference instead of recursing forever
+            self.schemas.insert(
+                component.to_string(),
+                Schema {
+                    schema_type: Some("object".to_string()),
+                    ..Default::default()
+                },
+            );
+            let title = if substitutions.is_empty() {
+                type_name.to_string()
+            } else {
+                self.instances
+                    .iter()
+                    .find(|(_, name)| *name == component)
+                    .map_or_else(|| component.to_string(), |(key, _)| key.clone())
+            };
+
+            // serde writes unit structs as `null`, which only the any-type schema allows
+            if struct_def.unit {
+                self.schemas.insert(
+                    component.to_string(),
+                    Schema {
+                        title: Some(title),
+                        description: resolved.doc.clone(),
+                        ..Default::default()
+                    },
+                );
+                return;
+            }
+
+            if let Some(inner) = &struct_def.newtype {
+                let schema = self.generate_schema(&substitute(inner, substitutions));
+                self.schemas.insert(
+                    component.to_string(),
+                    Schema {
+                        title: Some(title),
+                        description: resolved.doc.clone(),
+                        ..limit_length(schema, struct_def.length)
+                    },
+                );
+                return;
+            }
 
+            // In `allOf` mode, flattened fields of component types become references. An
+            // optional flattened type may be absent altogether, so its fields are inlined as
+            // optional properties instead of making its required fields mandatory.
+            let mut own_fields = Vec::new();
+            let mut flattened = Vec::new();
             for field in &struct_def.fields {
-                // Skip fields marked with #[serde(skip)]
-                if field.serde_attrs.skip {
-                    continue;
+                let mut field = field.clone();
+                field.type_info = substitute(&field.type_info, substitutions);
+                if field.serde_attrs.flatten
+                    && !field.serde_attrs.skip
+                    && !field.type_info.is_option
+                    && self.flatten_mode == FlattenMode::AllOf
+                {
+                    let schema = self.generate_schema(&field.type_info);
+                    if schema.reference.is_some() {
+                        flattened.push(schema);
+                        continue;
+                    }
                 }
+                own_fields.push(field);
+            }
 
-                // Use the renamed field name if specified
-                let field_name = field
-                    .serde_attrs
-                    .rename
-                    .as_ref()
-                    .unwrap_or(&field.name)
-                    .clone();
-
-                // Generate property schema
-                let property = self.type_info_to_property(&field.type_info);
-                properties.insert(field_name.clone(), property);
-
-                // Add to required list if not optional
-                if !field.optional && !field.type_info.is_option {
-                    required.push(field_name);
+            let (properties, required) = self.object_properties(&own_fields);
+
+            let schema = if flattened.is_empty() {
+                // A struct without fields (`struct Empty {}`) is a plain `object`
+                Schema {
+                    title: Some(title),
+                    description: resolved.doc.clone(),
+                    schema_type: Some("object".to_string()),
+                    properties: (!properties.is_empty()).then_some(properties),
+                    required,
+                    ..Default::default()
                 }
+            } else {
+                if !properties.is_empty() {
+                    flattened.push(Schema {
+                        schema_type: Some("object".to_string()),
+                        properties: Some(properties),
+                        required,
+                        ..Default::default()
+                    });
+                }
+                Schema {
+                    title: Some(title),
+                    description: resolved.doc.clone(),
+                    all_of: Some(flattened),
+                    ..Default::default()
+                }
+            };
+
+            self.schemas.insert(component.to_string(), schema);
+        }
+    }
+
+    /// Generate the properties of an object with the given fields, along with the names of the
+    /// required ones (`None` if all are optional)
+    fn object_properties(
+        &mut self,
+        fields: &[FieldDef],
+    ) -> (HashMap<String, Property>, Option<Vec<String>>) {
+        let mut properties = HashMap::new();
+        let mut required = Vec::new();
+
+        for field in fields {
+            // Skip fields marked with #[serde(skip)]
+            if field.serde_attrs.skip {
+                continue;
             }
 
-            let schema = Schema {
-                schema_type: Some("object".to_string()),
-                properties: Some(properties),
-                required: if required.is_empty() {
-                    None
-                } else {
-                    Some(required)
-                },
-                items: None,
-                enum_values: None,
-                reference: None,
-                format: None,
+            // Fields marked with #[serde(flatten)] contribute the properties of their type
+            if field.serde_attrs.flatten {
+                if let Some((flat_properties, flat_required)) =
+                    self.flattened_properties(&field.type_info)
+                {
+                    properties.extend(flat_properties);
+                    if !field.type_info.is_option {
+                        required.extend(flat_required);
+                    }
+                    continue;
+                }
+            }
+
+            // Use the renamed field name if specified
+            let field_name = field
+                .serde_attrs
+                .rename
+                .as_ref()
+                .unwrap_or(&field.name)
+                .clone();
+
+            // Generate property schema, honoring serde_with wire types
+            let mut property = match field
+                .serde_attrs
+                .serde_as
+                .as_deref()
+                .and_then(Self::serde_as_to_property)
+            {
+                Some(property) => property,
+                None => self.type_info_to_property(&field.type_info),
             };
+            property = limit_property_length(property, field.length);
+            let is_option = field.optional || field.type_info.is_option;
+            // `skip_serializing_if` leaves `None` out instead of writing `null`
+            let omitted_when_none = is_option && field.serde_attrs.skip_serializing_if;
+            if is_option && !omitted_when_none && self.option_handling != OptionHandling::Optional {
+                property = nullable_property(property);
+            }
+            // Fields skipped in one direction are marked here; the document builder drops them
+            // from the schemas used in that direction only
+            if field.serde_attrs.skip_deserializing {
+                property.read_only = Some(true);
+            }
+            if field.serde_attrs.skip_serializing {
+                property.write_only = Some(true);
+            }
+            properties.insert(field_name.clone(), property);
 
-            self.schemas.insert(type_name.to_string(), schema);
+            // Add to required list if not optional and not filled in by `#[serde(default)]`
+            let may_be_missing = omitted_when_none
+                || (is_option && self.option_handling != OptionHandling::Nullable);
+            if !may_be_missing && !field.serde_attrs.default {
+                required.push(field_name);
+            }
         }
+
+        let required = if required.is_empty() {
+            None
+        } else {
+            Some(required)
+        };
+        (properties, required)
+    }
+
+    /// Properties and required property names of the component schema of a flattened type,
+    /// if it is an object
+    fn flattened_properties(
+        &mut self,
+        type_info: &TypeInfo,
+    ) -> Option<(HashMap<String, Property>, Vec<String>)> {
+        let schema = self.generate_schema(type_info);
+        let name = schema.reference?.strip_prefix(&self.ref_prefix)?.to_string();
+        let component = self.schemas.get(&name)?;
+        let properties = component.properties.clone()?;
+        Some((properties, component.required.clone().unwrap_or_default()))
     }
 
     /// Generate a schema for an enum type and add it to the schemas collection
@@ -267,6 +897,10 @@ impl SchemaGenerator {
             debug!("Schema for {} already exists", type_name);
             return;
         }
+        if self.excluded_types.contains(type_name) {
+            debug!("Type {} is excluded, not generating a schema", type_name);
+            return;
+        }
 
         debug!("Generating enum schema for: {}", type_name);
 
@@ -280,20 +914,227 @@ impl SchemaGenerator {
         };
 
         if let TypeKind::Enum(enum_def) = resolved.kind {
+            // Only externally tagged fieldless enums serialize as bare variant names
+            let tagged_names = enum_def.representation == EnumRepresentation::External
+                && !enum_def.has_data();
+            if enum_def.discriminants.is_none() && !tagged_names {
+                // Register a placeholder first, variant data may refer back to the enum
+                self.schemas.insert(type_name.to_string(), Schema::default());
+
+                let representation = &enum_def.representation;
+                let branches = enum_def
+                    .variants
+                    .iter()
+                    .zip(&enum_def.variant_fields)
+                    .zip(&enum_def.variant_docs)
+                    .map(|((name, fields), doc)| {
+                        let mut branch = self.variant_schema(name, fields, representation);
+                        // Siblings of a `$ref` are ignored
+                        if branch.reference.is_none() {
+                            branch.description = doc.clone();
+                        }
+                        branch
+                    })
+                    .collect();
+                let discriminator = match representation {
+                    EnumRepresentation::Internal { tag }
+                    | EnumRepresentation::Adjacent { tag, .. } => Some(Discriminator {
+                        property_name: tag.clone(),
+                    }),
+                    EnumRepresentation::External | EnumRepresentation::Untagged => None,
+                };
+                let schema = Schema {
+                    title: Some(type_name.to_string()),
+                    description: resolved.doc.clone(),
+                    one_of: Some(branches),
+                    discriminator,
+                    ..Default::default()
+                };
+
+                self.schemas.insert(type_name.to_string(), schema);
+                return;
+            }
+
+            // OpenAPI has no per-value descriptions; renderers and client generators read
+            // documented variants from `x-enumDescriptions`, keyed by variant name
+            let descriptions: serde_json::Map<String, serde_json::Value> = enum_def
+                .variants
+                .iter()
+                .zip(&enum_def.variant_docs)
+                .filter_map(|(name, doc)| Some((name.clone(), doc.clone()?.into())))
+                .collect();
+            let mut extensions = BTreeMap::new();
+            if !descriptions.is_empty() {
+                extensions.insert("x-enumDescriptions".to_string(), descriptions.into());
+            }
+
+            // Numeric enums list their discriminants, all others their variant names
+            let (schema_type, enum_values) = match enum_def.discriminants {
+                Some(values) => ("integer", values.into_iter().map(Into::into).collect()),
+                None => ("string", enum_def.variants.into_iter().map(Into::into).collect()),
+            };
             let schema = Schema {
-                schema_type: Some("string".to_string()),
-                properties: None,
-                required: None,
-                items: None,
-                enum_values: Some(enum_def.variants),
-                reference: None,
-                format: None,
+                title: Some(type_name.to_string()),
+                description: resolved.doc.clone(),
+                schema_type: Some(schema_type.to_string()),
+                enum_values: Some(enum_values),
+                extensions,
+                ..Default::default()
             };
 
             self.schemas.insert(type_name.to_string(), schema);
         }
     }
 
+    /// Generate the schema of one enum variant in the enum's serde representation.
+    ///
+    /// Externally tagged variants are an object with the variant name as its single key (unit
+    /// variants just the name), internally tagged ones an object with a tag property next to
+    /// the variant's fields, adjacently tagged ones an object with tag and content properties,
+    /// and untagged ones the variant data alone.
+    fn variant_schema(
+        &mut self,
+        name: &str,
+        fields: &VariantFields,
+        representation: &EnumRepresentation,
+    ) -> Schema {
+        match representation {
+            EnumRepresentation::External => {}
+            EnumRepresentation::Internal { tag } => {
+                return self.internally_tagged_variant_schema(name, fields, tag);
+            }
+            EnumRepresentation::Adjacent { tag, content } => {
+                let mut properties = HashMap::from([(tag.clone(), Self::tag_property(name))]);
+                let mut required = vec![tag.clone()];
+                if let Some(data) = self.variant_data(fields) {
+                    properties.insert(content.clone(), data);
+                    required.push(content.clone());
+                }
+                return Schema {
+                    schema_type: Some("object".to_string()),
+                    properties: Some(properties),
+                    required: Some(required),
+                    ..Default::default()
+                };
+            }
+            EnumRepresentation::Untagged => {
+                return match fields {
+                    // Untagged unit variants serialize as `null`
+                    VariantFields::Unit => Schema {
+                        enum_values: Some(vec![serde_json::Value::Null]),
+                        ..Default::default()
+                    },
+                    VariantFields::Unnamed(types) if types.len() == 1 => {
+                        self.generate_schema(&types[0])
+                    }
+                    _ => self.variant_data(fields).map(property_schema).unwrap_or_default(),
+                };
+            }
+        }
+
+        let Some(data) = self.variant_data(fields) else {
+            return Schema {
+                schema_type: Some("string".to_string()),
+                enum_values: Some(vec![name.into()]),
+                ..Default::default()
+            };
+        };
+        Schema {
+            schema_type: Some("object".to_string()),
+            properties: Some(HashMap::from([(name.to_string(), data)])),
+            required: Some(vec![name.to_string()]),
+            ..Default::default()
+        }
+    }
+
+    /// Generate the schema of an internally tagged variant: the variant's fields (or those of
+    /// the struct a newtype variant wraps) next to a tag property holding its name
+    fn internally_tagged_variant_schema(
+        &mut self,
+        name: &str,
+        fields: &VariantFields,
+        tag: &str,
+    ) -> Schema {
+        let struct_fields = match fields {
+            VariantFields::Named(fields) => fields.clone(),
+            VariantFields::Unnamed(types) if types.len() == 1 => {
+                match self.type_resolver.resolve_type(&types[0].name).map(|r| r.kind) {
+                    Some(TypeKind::Struct(struct_def)) => struct_def.fields,
+                    _ => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        };
+        let (mut properties, required) = self.object_properties(&struct_fields);
+        properties.insert(tag.to_string(), Self::tag_property(name));
+        let mut required = required.unwrap_or_default();
+        required.insert(0, tag.to_string());
+
+        Schema {
+            schema_type: Some("object".to_string()),
+            properties: Some(properties),
+            required: Some(required),
+            ..Default::default()
+        }
+    }
+
+    /// Property holding a variant's name in tagged representations
+    fn tag_property(name: &str) -> Property {
+        Property {
+            property_type: Some("string".to_string()),
+            enum_values: Some(vec![name.into()]),
+            ..Default::default()
+        }
+    }
+
+    /// Describe the data carried by an enum variant, or `None` for unit variants
+    fn variant_data(&mut self, fields: &VariantFields) -> Option<Property> {
+        let data = match fields {
+            VariantFields::Unit => return None,
+            VariantFields::Named(fields) => {
+                let (properties, required) = self.object_properties(fields);
+                Property {
+                    property_type: Some("object".to_string()),
+                    properties: Some(properties),
+                    required,
+                    ..Default::default()
+                }
+            }
+            VariantFields::Unnamed(types) if types.len() == 1 => {
+                self.type_info_to_property(&types[0])
+            }
+            VariantFields::Unnamed(types) => {
+                // OpenAPI 3.0 cannot express positional items, so the tuple is documented as
+                // an array of its (distinct) element types
+                let mut distinct: Vec<&TypeInfo> = Vec::new();
+                for ty in types {
+                    if !distinct.contains(&ty) {
+                        distinct.push(ty);
+                    }
+                }
+                let items = if distinct.len() == 1 {
+                    self.generate_schema(distinct[0])
+                } else {
+                    Schema {
+                        one_of: Some(
+                            distinct
+                                .into_iter()
+                                .map(|ty| self.generate_schema(ty))
+                                .collect(),
+                        ),
+                        ..Default::default()
+                    }
+                };
+                Property {
+                    property_type: Some("array".to_string()),
+                    items: Some(Box::new(items)),
+                    ..Default::default()
+                }
+            }
+        };
+        Some(data)
+    }
+
     /// Convert a TypeInfo to a Property
     fn type_info_to_property(&mut self, type_info: &TypeInfo) -> Property {
         // Handle Option<T> - unwrap and generate property for inner type
@@ -303,19 +1144,56 @@ impl SchemaGenerator {
             }
         }
 
+        // Handle Result<T, E> - the successful value is what gets serialized
+        if let Some(ok) = type_info.ok_type() {
+            return self.type_info_to_property(ok);
+        }
+
+        // Trait objects keep their description, wrapped in `allOf` like unresolved placeholders
+        if type_info.is_trait_object {
+            return Property {
+                all_of: Some(vec![self.generate_schema(type_info)]),
+                ..Default::default()
+            };
+        }
+
+        // Handle maps - dynamic keys serialize as a JSON object of the value type
+        if type_info.is_map {
+            return Property {
+                property_type: Some("object".to_string()),
+                additional_properties: self.map_values_schema(type_info),
+                ..Default::default()
+            };
+        }
+
+        // Handle Vec<u8> and Bytes - binary data is encoded as a base64 string
+        if self.is_byte_buffer(type_info) {
+            return Property {
+                property_type: Some("string".to_string()),
+                format: Some("byte".to_string()),
+                ..Default::default()
+            };
+        }
+
         // Handle Vec<T> - generate array property
         if type_info.is_vec {
             if let Some(inner) = type_info.generic_args.first() {
                 let items_schema = self.generate_schema(inner);
                 return Property {
                     property_type: Some("array".to_string()),
-                    reference: None,
                     items: Some(Box::new(items_schema)),
-                    format: None,
+                    ..Default::default()
                 };
             }
         }
 
+        if self.excluded_types.contains(&type_info.name) {
+            return Property {
+                property_type: Some("object".to_string()),
+                ..Default::default()
+            };
+        }
+
         // Try to resolve the type
         if let Some(resolved) = self.type_resolver.resolve_type(&type_info.name) {
             match resolved.kind {
@@ -323,51 +1201,115 @@ impl SchemaGenerator {
                     let schema = self.primitive_to_schema(&prim);
                     return Property {
                         property_type: schema.schema_type,
-                        reference: None,
-                        items: None,
                         format: schema.format,
+                        minimum: schema.minimum,
+                        ..Default::default()
                     };
                 }
-                TypeKind::Struct(_) => {
+                TypeKind::Struct(struct_def) => {
+                    if let Some(inner) = self.enter_newtype(type_info, &struct_def) {
+                        let property = self.type_info_to_property(&inner);
+                        self.inlined_newtypes.remove(&type_info.name);
+                        return limit_property_length(property, struct_def.length);
+                    }
+
                     // Generate the struct schema if not already done
-                    self.generate_struct_schema(&type_info.name);
+                    let name = self.struct_component(type_info, &struct_def);
                     return Property {
-                        property_type: None,
-                        reference: Some(format!("#/components/schemas/{}", type_info.name)),
-                        items: None,
-                        format: None,
+                        reference: Some(self.schema_ref(&name)),
+                        ..Default::default()
                     };
                 }
                 TypeKind::Enum(_) => {
                     // Generate the enum schema if not already done
                     self.generate_enum_schema(&type_info.name);
                     return Property {
-                        property_type: None,
-                        reference: Some(format!("#/components/schemas/{}", type_info.name)),
-                        items: None,
-                        format: None,
+                        reference: Some(self.schema_ref(&type_info.name)),
+                        ..Default::default()
                     };
                 }
                 TypeKind::Generic(_) => {
                     return Property {
                         property_type: Some("object".to_string()),
-                        reference: None,
-                        items: None,
-                        format: None,
+                        ..Default::default()
                     };
                 }
             }
         }
 
-        // Fallback for unknown types
+        if let Some(schema) = self.string_type_schema(&type_info.name) {
+            return Property {
+                property_type: schema.schema_type,
+                format: schema.format,
+                ..Default::default()
+            };
+        }
+
+        // Fallback for unknown types; properties cannot carry the markers themselves, so a
+        // marked placeholder is wrapped in `allOf`
+        let placeholder = self.unresolved_schema(&type_info.name);
+        if self.mark_unresolved {
+            return Property {
+                all_of: Some(vec![placeholder]),
+                ..Default::default()
+            };
+        }
         Property {
             property_type: Some("object".to_string()),
-            reference: None,
-            items: None,
-            format: None,
+            ..Default::default()
         }
     }
 
+    /// Convert a `#[serde_as(as = "...")]` type to the property it serializes as.
+    ///
+    /// Returns `None` for conversions that keep the Rust type's schema (e.g. `_`) or
+    /// that are not recognized, so the caller falls back to the field's declared type.
+    fn serde_as_to_property(as_type: &str) -> Option<Property> {
+        let ty: syn::Type = syn::parse_str(as_type).ok()?;
+        Self::serde_as_type_to_property(&ty)
+    }
+
+    /// Map a parsed `serde_as` type to a property, see [`Self::serde_as_to_property`]
+    fn serde_as_type_to_property(ty: &syn::Type) -> Option<Property> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        let inner = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(syn::GenericArgument::Type(inner)) => Some(inner),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let (property_type, format) = match segment.ident.to_string().as_str() {
+            "Option" => return Self::serde_as_type_to_property(inner?),
+            "Vec" => {
+                let item = Self::serde_as_type_to_property(inner?)?;
+                return Some(Property {
+                    property_type: Some("array".to_string()),
+                    items: Some(Box::new(Schema {
+                        schema_type: item.property_type,
+                        items: item.items,
+                        format: item.format,
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                });
+            }
+            "DisplayFromStr" | "Hex" => ("string", None),
+            "Bytes" | "BytesOrString" | "Base64" => ("string", Some("byte")),
+            _ => return None,
+        };
+
+        Some(Property {
+            property_type: Some(property_type.to_string()),
+            format: format.map(|f| f.to_string()),
+            ..Default::default()
+        })
+    }
+
     /// Generate a parameter schema from a Parameter
     pub fn generate_parameter_schema(&mut self, param: &Parameter) -> ParameterSchema {
         debug!("Generating parameter schema for: {}", param.name);
@@ -385,6 +1327,94 @@ impl SchemaGenerator {
             location: location.to_string(),
             required: param.required,
             schema,
+            description: None,
+            deprecated: None,
+            style: None,
+            explode: None,
+        }
+    }
+
+    /// Generate the parameter schemas of a Parameter.
+    ///
+    /// A `Query<T>` or `Path<T>` extractor whose `T` is a struct is expanded into one parameter
+    /// per field, carrying the field's description and `#[deprecated]` status. `Vec` query
+    /// fields are multi-value parameters (`?tags=a&tags=b`): an array with `style: form` and
+    /// `explode: true`. A map such as `Query<HashMap<String, String>>` accepts arbitrary keys
+    /// and yields a single free-form object parameter with `style: form` and `explode: true`,
+    /// whose entries are the `key=value` pairs. Any other parameter yields a single schema, see
+    /// [`Self::generate_parameter_schema`].
+    pub fn generate_parameter_schemas(&mut self, param: &Parameter) -> Vec<ParameterSchema> {
+        let fields = match param.location {
+            ParameterLocation::Query if param.type_info.is_map => {
+                let mut schema = self.generate_parameter_schema(param);
+                schema.style = Some("form".to_string());
+                schema.explode = Some(true);
+                return vec![schema];
+            }
+            ParameterLocation::Path | ParameterLocation::Query => {
+                let resolved = self.type_resolver.resolve_type(&param.type_info.name);
+                match resolved.map(|resolved| resolved.kind) {
+                    Some(TypeKind::Struct(struct_def))
+                        if !param.type_info.is_vec && !param.type_info.is_map =>
+                    {
+                        struct_def.fields
+                    }
+                    _ => return vec![self.generate_parameter_schema(param)],
+                }
+            }
+            ParameterLocation::Header => return vec![self.generate_parameter_schema(param)],
+        };
+
+        debug!("Expanding parameter {} into {} fields", param.name, fields.len());
+        let location = match param.location {
+            ParameterLocation::Path => "path",
+            _ => "query",
+        };
+        fields
+            .iter()
+            .filter(|field| !field.serde_attrs.skip && !field.serde_attrs.skip_deserializing)
+            .map(|field| {
+                let multi_value = param.location == ParameterLocation::Query
+                    && (field.type_info.is_vec
+                        || (field.type_info.is_option
+                            && field.type_info.generic_args.first().is_some_and(|arg| arg.is_vec)));
+                ParameterSchema {
+                    name: field.serde_attrs.rename.clone().unwrap_or_else(|| field.name.clone()),
+                    location: location.to_string(),
+                    // Path parameters are always required
+                    required: param.location == ParameterLocation::Path
+                        || (!field.optional && !field.type_info.is_option),
+                    schema: limit_length(self.generate_schema(&field.type_info), field.length),
+                    description: field.description.clone(),
+                    deprecated: field.deprecated.then_some(true),
+                    style: multi_value.then(|| "form".to_string()),
+                    explode: multi_value.then_some(true),
+                }
+            })
+            .collect()
+    }
+
+    /// Generate component schemas for every struct and enum of the project, whether or not a
+    /// route uses them.
+    ///
+    /// Generic types are skipped: only their instantiations, named after the type arguments,
+    /// have schemas.
+    pub fn generate_all(&mut self) {
+        let resolver = &self.type_resolver;
+        let structs = resolver.all_struct_names().into_iter().filter(|name| {
+            resolver
+                .find_struct_definition(name)
+                .is_some_and(|item| item.generics.params.is_empty())
+        });
+        let enums = resolver.all_enum_names().into_iter().filter(|name| {
+            resolver
+                .find_enum_definition(name)
+                .is_some_and(|item| item.generics.params.is_empty())
+        });
+        let names: Vec<String> = structs.chain(enums).collect();
+        debug!("Generating schemas for all {} types", names.len());
+        for name in names {
+            self.generate_schema(&TypeInfo::new(name));
         }
     }
 
@@ -392,20 +1422,94 @@ impl SchemaGenerator {
     pub fn get_schemas(&self) -> &HashMap<String, Schema> {
         &self.schemas
     }
+
+    /// Get the names of types that fell back to `object` placeholders, in sorted order
+    pub fn get_unresolved_types(&self) -> &BTreeSet<String> {
+        &self.unresolved_types
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::parser::AstParser;
-    use std::fs;
-    use std::io::Write;
-    use tempfile::TempDir;
+/// Mark a property as nullable; a reference is wrapped in `allOf`, as `nullable` next to a
+/// `$ref` would be ignored
+fn nullable_property(property: Property) -> Property {
+    match property.reference {
+        Some(reference) => Property {
+            nullable: Some(true),
+            all_of: Some(vec![Schema {
+                reference: Some(reference),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        },
+        None => Property {
+            nullable: Some(true),
+            ..property
+        },
+    }
+}
 
-    /// Helper function to create a temporary file with content
-    fn create_temp_file(dir: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
-        let file_path = dir.path().join(name);
-        let mut file = fs::File::create(&file_path).unwrap();
+/// Apply length bounds to a string schema; other schemas are returned unchanged
+fn limit_length(schema: Schema, length: LengthLimits) -> Schema {
+    if schema.schema_type.as_deref() != Some("string") {
+        return schema;
+    }
+    Schema {
+        min_length: length.min.or(schema.min_length),
+        max_length: length.max.or(schema.max_length),
+        read_only: None,
+        write_only: None,
+        ..schema
+    }
+}
+
+/// Apply length bounds to a string property; other properties are returned unchanged
+fn limit_property_length(property: Property, length: LengthLimits) -> Property {
+    if property.property_type.as_deref() != Some("string") {
+        return property;
+    }
+    Property {
+        min_length: length.min.or(property.min_length),
+        max_length: length.max.or(property.max_length),
+        read_only: None,
+        write_only: None,
+        ..property
+    }
+}
+
+/// Turn an inline property into a standalone schema
+fn property_schema(property: Property) -> Schema {
+    Schema {
+        schema_type: property.property_type,
+        properties: property.properties,
+        required: property.required,
+        items: property.items,
+        enum_values: property.enum_values,
+        reference: property.reference,
+        format: property.format,
+        minimum: property.minimum,
+        min_length: property.min_length,
+        max_length: property.max_length,
+        read_only: property.read_only,
+        write_only: property.write_only,
+        additional_properties: property.additional_properties,
+        nullable: property.nullable,
+        all_of: property.all_of,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::AstParser;
+    use std::fs;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    /// Helper function to create a temporary file with content
+    fn create_temp_file(dir: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let file_path = dir.path().join(name);
+        let mut file = fs::File::create(&file_path).unwrap();
         file.write_all(content.as_bytes()).unwrap();
         file_path
     }
@@ -451,6 +1555,69 @@ mod tests {
         assert_eq!(schema.format, Some("int64".to_string()));
     }
 
+    #[test]
+    fn test_primitive_type_pointer_sized() {
+        let mut generator = create_generator_from_code(
+            r#"
+            pub struct Page {
+                pub limit: usize,
+                pub offset: isize,
+            }
+        "#,
+        );
+
+        let schema = generator.generate_schema(&TypeInfo::new("usize".to_string()));
+        assert_eq!(schema.schema_type, Some("integer".to_string()));
+        assert_eq!(schema.format, Some("int64".to_string()));
+        assert_eq!(schema.minimum, Some(0));
+
+        let schema = generator.generate_schema(&TypeInfo::new("isize".to_string()));
+        assert_eq!(schema.format, Some("int64".to_string()));
+        assert!(schema.minimum.is_none());
+
+        generator.generate_schema(&TypeInfo::new("Page".to_string()));
+        let page = &generator.get_schemas()["Page"];
+        let properties = page.properties.as_ref().unwrap();
+        assert_eq!(properties["limit"].format, Some("int64".to_string()));
+        assert_eq!(properties["limit"].minimum, Some(0));
+        assert!(properties["offset"].minimum.is_none());
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_numeric_parameter_formats() {
+        let mut generator = create_generator_from_code("");
+        let cases = [
+            ("i8", "integer", "int32", None),
+            ("i16", "integer", "int32", None),
+            ("i32", "integer", "int32", None),
+            ("i64", "integer", "int64", None),
+            ("i128", "integer", "int64", None),
+            ("isize", "integer", "int64", None),
+            ("u8", "integer", "int32", Some(0)),
+            ("u16", "integer", "int32", Some(0)),
+            ("u32", "integer", "int32", Some(0)),
+            ("u64", "integer", "int64", Some(0)),
+            ("u128", "integer", "int64", Some(0)),
+            ("usize", "integer", "int64", Some(0)),
+            ("f32", "number", "float", None),
+            ("f64", "number", "double", None),
+        ];
+
+        for (type_name, schema_type, format, minimum) in cases {
+            let param = Parameter {
+                name: "value".to_string(),
+                location: ParameterLocation::Query,
+                type_info: TypeInfo::new(type_name.to_string()),
+                required: true,
+            };
+            let schema = generator.generate_parameter_schema(&param).schema;
+            assert_eq!(schema.schema_type.as_deref(), Some(schema_type), "{}", type_name);
+            assert_eq!(schema.format.as_deref(), Some(format), "{}", type_name);
+            assert_eq!(schema.minimum, minimum, "{}", type_name);
+        }
+    }
+
     #[test]
     fn test_primitive_type_f32() {
         let mut generator = create_generator_from_code("");
@@ -507,6 +1674,259 @@ mod tests {
         assert_eq!(schema.format, Some("int32".to_string()));
     }
 
+    #[test]
+    fn test_smart_pointer_strings() {
+        let mut generator = create_generator_from_code(
+            r#"
+            pub struct Article<'a> {
+                pub slug: Arc<str>,
+                pub title: Cow<'static, str>,
+                pub body: Box<str>,
+                pub author: Rc<String>,
+                pub summary: Option<Cow<'a, str>>,
+            }
+        "#,
+        );
+
+        generator.generate_schema(&TypeInfo::new("Article".to_string()));
+        let article = &generator.get_schemas()["Article"];
+        let properties = article.properties.as_ref().unwrap();
+        for field in ["slug", "title", "body", "author", "summary"] {
+            assert_eq!(properties[field].property_type, Some("string".to_string()), "{}", field);
+        }
+        assert!(!article.required.as_ref().unwrap().contains(&"summary".to_string()));
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    const FLATTENED_CODE: &str = r#"
+        pub struct Entity {
+            pub id: u64,
+            pub created_at: Option<String>,
+        }
+
+        pub struct Audit {
+            pub editor: String,
+        }
+
+        pub struct UserResponse {
+            #[serde(flatten)]
+            pub base: Entity,
+            #[serde(flatten)]
+            pub audit: Option<Audit>,
+            pub name: String,
+        }
+    "#;
+
+    #[test]
+    fn test_flatten_inline() {
+        let mut generator = create_generator_from_code(FLATTENED_CODE);
+
+        generator.generate_schema(&TypeInfo::new("UserResponse".to_string()));
+        let user = &generator.get_schemas()["UserResponse"];
+        let properties = user.properties.as_ref().unwrap();
+        let mut names: Vec<&String> = properties.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["created_at", "editor", "id", "name"]);
+        let mut required = user.required.clone().unwrap();
+        required.sort();
+        // Fields of an optional flattened type are never required
+        assert_eq!(required, vec!["id", "name"]);
+        assert!(user.all_of.is_none());
+    }
+
+    #[test]
+    fn test_ref_prefix() {
+        let code = r#"
+            pub enum Role {
+                Admin,
+                Member,
+            }
+
+            pub struct Team {
+                pub lead: Option<Member>,
+                pub members: Vec<Member>,
+            }
+
+            pub struct Member {
+                #[serde(flatten)]
+                pub base: Base,
+                pub role: Role,
+            }
+
+            pub struct Base {
+                pub id: u64,
+            }
+        "#;
+        let mut generator =
+            create_generator_from_code(code).with_ref_prefix("#/definitions/".to_string());
+
+        let team = generator.generate_schema(&TypeInfo::new("Team".to_string()));
+        assert_eq!(team.reference.as_deref(), Some("#/definitions/Team"));
+        let role = generator.generate_schema(&TypeInfo::new("Role".to_string()));
+        assert_eq!(role.reference.as_deref(), Some("#/definitions/Role"));
+
+        let schemas = serde_json::to_value(generator.get_schemas()).unwrap();
+        assert_eq!(
+            schemas["Team"]["properties"]["lead"]["allOf"][0]["$ref"],
+            "#/definitions/Member"
+        );
+        assert_eq!(
+            schemas["Team"]["properties"]["members"]["items"]["$ref"],
+            "#/definitions/Member"
+        );
+        assert_eq!(schemas["Member"]["properties"]["role"]["$ref"], "#/definitions/Role");
+        // Flattened components are still found behind the configured prefix
+        assert_eq!(schemas["Member"]["properties"]["id"]["type"], "integer");
+        assert!(!schemas.to_string().contains("#/components/schemas/"));
+    }
+
+    #[test]
+    fn test_flatten_all_of() {
+        let mut generator =
+            create_generator_from_code(FLATTENED_CODE).with_flatten_mode(FlattenMode::AllOf);
+
+        generator.generate_schema(&TypeInfo::new("UserResponse".to_string()));
+        let schemas = generator.get_schemas();
+        let user = &schemas["UserResponse"];
+        assert!(user.properties.is_none());
+        let parts = user.all_of.as_ref().unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].reference, Some("#/components/schemas/Entity".to_string()));
+        // The optional flattened `Audit` is inlined, its fields not required
+        let own = parts[1].properties.as_ref().unwrap();
+        let mut names: Vec<&String> = own.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["editor", "name"]);
+        assert_eq!(parts[1].required, Some(vec!["name".to_string()]));
+        assert!(schemas.contains_key("Entity"));
+    }
+
+    #[test]
+    fn test_decimal_types() {
+        let code = r#"
+            pub struct Invoice {
+                pub total: rust_decimal::Decimal,
+                pub tax: Option<BigDecimal>,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Invoice".to_string()));
+        let properties = generator.get_schemas()["Invoice"].properties.clone().unwrap();
+        for field in ["total", "tax"] {
+            assert_eq!(properties[field].property_type, Some("string".to_string()));
+            assert_eq!(properties[field].format, Some("decimal".to_string()));
+        }
+
+        let mut generator = create_generator_from_code(code).with_decimal_numbers(true);
+        generator.generate_schema(&TypeInfo::new("Invoice".to_string()));
+        let properties = generator.get_schemas()["Invoice"].properties.clone().unwrap();
+        for field in ["total", "tax"] {
+            assert_eq!(properties[field].property_type, Some("number".to_string()));
+            assert_eq!(properties[field].format, None);
+        }
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_std_string_types() {
+        let mut generator = create_generator_from_code(
+            r#"
+            pub struct Node {
+                pub address: Ipv4Addr,
+                pub address_v6: Option<std::net::Ipv6Addr>,
+                pub peer: IpAddr,
+                pub listen: SocketAddr,
+                pub data_dir: PathBuf,
+                pub homepage: url::Url,
+                pub region: Region,
+            }
+        "#,
+        )
+        .with_string_type("Region".to_string(), Some("aws-region".to_string()));
+
+        generator.generate_schema(&TypeInfo::new("Node".to_string()));
+        let node = &generator.get_schemas()["Node"];
+        let properties = node.properties.as_ref().unwrap();
+        let format = |field: &str| {
+            assert_eq!(properties[field].property_type, Some("string".to_string()), "{}", field);
+            properties[field].format.clone()
+        };
+        assert_eq!(format("address").as_deref(), Some("ipv4"));
+        assert_eq!(format("address_v6").as_deref(), Some("ipv6"));
+        assert_eq!(format("peer"), None);
+        assert_eq!(format("listen"), None);
+        assert_eq!(format("data_dir"), None);
+        assert_eq!(format("homepage").as_deref(), Some("uri"));
+        assert_eq!(format("region").as_deref(), Some("aws-region"));
+        assert!(generator.get_unresolved_types().is_empty());
+
+        let schema = generator.generate_schema(&TypeInfo::new("Url".to_string()));
+        assert_eq!(schema.format, Some("uri".to_string()));
+    }
+
+    #[test]
+    fn test_project_types_shadow_string_types() {
+        let mut generator = create_generator_from_code(
+            r#"
+            pub struct Url {
+                pub scheme: String,
+                pub host: String,
+            }
+        "#,
+        );
+
+        let schema = generator.generate_schema(&TypeInfo::new("Url".to_string()));
+        assert_eq!(schema.reference, Some("#/components/schemas/Url".to_string()));
+    }
+
+    #[test]
+    fn test_nested_collections() {
+        let mut generator = create_generator_from_code(
+            r#"
+            pub struct Grid {
+                pub matrix: Vec<Vec<f64>>,
+                pub slots: Vec<Option<u64>>,
+                pub labels: Option<Vec<Vec<String>>>,
+            }
+        "#,
+        );
+
+        let matrix = TypeInfo::vec(TypeInfo::vec(TypeInfo::new("f64".to_string())));
+        let schema = generator.generate_schema(&matrix);
+        assert_eq!(schema.schema_type, Some("array".to_string()));
+        let inner = schema.items.as_ref().unwrap();
+        assert_eq!(inner.schema_type, Some("array".to_string()));
+        let item = inner.items.as_ref().unwrap();
+        assert_eq!(item.schema_type, Some("number".to_string()));
+        assert_eq!(item.format, Some("double".to_string()));
+
+        generator.generate_schema(&TypeInfo::new("Grid".to_string()));
+        let grid = &generator.get_schemas()["Grid"];
+        let properties = grid.properties.as_ref().unwrap();
+
+        let matrix = &properties["matrix"];
+        assert_eq!(matrix.property_type, Some("array".to_string()));
+        let inner = matrix.items.as_ref().unwrap();
+        assert_eq!(inner.schema_type, Some("array".to_string()));
+        assert_eq!(inner.items.as_ref().unwrap().format, Some("double".to_string()));
+
+        // `Option` inside a `Vec` documents the element type itself
+        let slots = properties["slots"].items.as_ref().unwrap();
+        assert_eq!(slots.schema_type, Some("integer".to_string()));
+        assert_eq!(slots.format, Some("int64".to_string()));
+        assert_eq!(slots.minimum, Some(0));
+
+        let labels = &properties["labels"];
+        assert_eq!(labels.property_type, Some("array".to_string()));
+        let inner = labels.items.as_ref().unwrap();
+        assert_eq!(inner.schema_type, Some("array".to_string()));
+        let item = inner.items.as_ref().unwrap();
+        assert_eq!(item.schema_type, Some("string".to_string()));
+        assert!(!grid.required.as_ref().unwrap().contains(&"labels".to_string()));
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
     #[test]
     fn test_struct_schema_generation() {
         let code = r#"
@@ -677,9 +2097,228 @@ mod tests {
 
         let variants = status_schema.enum_values.as_ref().unwrap();
         assert_eq!(variants.len(), 3);
-        assert!(variants.contains(&"Active".to_string()));
-        assert!(variants.contains(&"Inactive".to_string()));
-        assert!(variants.contains(&"Pending".to_string()));
+        assert!(variants.contains(&serde_json::json!("Active")));
+        assert!(variants.contains(&serde_json::json!("Inactive")));
+        assert!(variants.contains(&serde_json::json!("Pending")));
+        // Enums without variant docs carry no extension
+        assert!(status_schema.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_enum_variant_descriptions() {
+        let code = r#"
+            /// Lifecycle of an order
+            pub enum OrderStatus {
+                /// Awaiting payment
+                Pending,
+                Shipped,
+                /// Refunded or voided
+                Cancelled,
+            }
+
+            pub enum Payment {
+                /// Paid on delivery
+                Cash,
+                /// Charged to a card
+                Card { last4: String },
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("OrderStatus".to_string()));
+        generator.generate_schema(&TypeInfo::new("Payment".to_string()));
+        let schemas = serde_json::to_value(generator.get_schemas()).unwrap();
+
+        let status = &schemas["OrderStatus"];
+        assert_eq!(status["description"], "Lifecycle of an order");
+        assert_eq!(
+            status["x-enumDescriptions"],
+            serde_json::json!({
+                "Pending": "Awaiting payment",
+                "Cancelled": "Refunded or voided"
+            })
+        );
+
+        let branches = schemas["Payment"]["oneOf"].as_array().unwrap();
+        assert_eq!(branches[0]["description"], "Paid on delivery");
+        assert_eq!(branches[1]["description"], "Charged to a card");
+    }
+
+    #[test]
+    fn test_byte_buffer_fields() {
+        let code = r#"
+            pub struct Upload {
+                pub data: Vec<u8>,
+                pub thumbnail: Option<bytes::Bytes>,
+                pub chunks: Vec<Vec<u8>>,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Upload".to_string()));
+
+        let properties = generator.get_schemas()["Upload"].properties.as_ref().unwrap();
+        for field in ["data", "thumbnail"] {
+            assert_eq!(properties[field].property_type, Some("string".to_string()));
+            assert_eq!(properties[field].format, Some("byte".to_string()));
+        }
+
+        let chunks = &properties["chunks"];
+        assert_eq!(chunks.property_type, Some("array".to_string()));
+        let items = chunks.items.as_ref().unwrap();
+        assert_eq!(items.schema_type, Some("string".to_string()));
+        assert_eq!(items.format, Some("byte".to_string()));
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_data_enum_schema() {
+        let code = r#"
+            pub struct Point {
+                pub x: f64,
+                pub y: f64,
+            }
+
+            pub enum Shape {
+                Circle { center: Point, radius: f64 },
+                Rect(f64, f64),
+                Polygon(Vec<Point>),
+                Labeled(String, u32),
+                Empty,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Shape".to_string()));
+
+        let shape = &generator.get_schemas()["Shape"];
+        assert!(shape.schema_type.is_none());
+        assert!(shape.enum_values.is_none());
+        let branches = shape.one_of.as_ref().unwrap();
+        assert_eq!(branches.len(), 5);
+
+        // Struct variant: inline object whose custom field types are referenced
+        let circle = &branches[0].properties.as_ref().unwrap()["Circle"];
+        assert_eq!(branches[0].required, Some(vec!["Circle".to_string()]));
+        assert_eq!(circle.property_type, Some("object".to_string()));
+        let fields = circle.properties.as_ref().unwrap();
+        assert_eq!(
+            fields["center"].reference,
+            Some("#/components/schemas/Point".to_string())
+        );
+        assert_eq!(fields["radius"].format, Some("double".to_string()));
+        assert_eq!(circle.required.as_ref().unwrap().len(), 2);
+        assert!(generator.get_schemas().contains_key("Point"));
+
+        // Tuple variants: arrays of their element types
+        let rect = &branches[1].properties.as_ref().unwrap()["Rect"];
+        assert_eq!(rect.property_type, Some("array".to_string()));
+        assert_eq!(rect.items.as_ref().unwrap().format, Some("double".to_string()));
+        let labeled = &branches[3].properties.as_ref().unwrap()["Labeled"];
+        assert_eq!(labeled.items.as_ref().unwrap().one_of.as_ref().unwrap().len(), 2);
+
+        // Newtype variant: the wrapped type itself
+        let polygon = &branches[2].properties.as_ref().unwrap()["Polygon"];
+        assert_eq!(polygon.property_type, Some("array".to_string()));
+        assert_eq!(
+            polygon.items.as_ref().unwrap().reference,
+            Some("#/components/schemas/Point".to_string())
+        );
+
+        // Unit variant: its name
+        assert_eq!(branches[4].enum_values, Some(vec![serde_json::json!("Empty")]));
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_enum_representations() {
+        let code = r#"
+            pub struct Point {
+                pub x: f64,
+                pub y: f64,
+            }
+
+            #[derive(Serialize)]
+            #[serde(untagged)]
+            pub enum Value {
+                Number(f64),
+                Point(Point),
+                Pair { left: String, right: String },
+            }
+
+            #[derive(Serialize)]
+            #[serde(tag = "type", rename_all = "snake_case")]
+            pub enum Event {
+                Moved(Point),
+                Renamed { name: String },
+                Deleted,
+            }
+
+            #[derive(Serialize)]
+            #[serde(tag = "t", content = "c")]
+            pub enum Message {
+                Text(String),
+                Ping,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        for name in ["Value", "Event", "Message"] {
+            generator.generate_schema(&TypeInfo::new(name.to_string()));
+        }
+        let schemas = generator.get_schemas();
+
+        // Untagged: the variant data alone, with nothing to discriminate on
+        let value = &schemas["Value"];
+        assert!(value.discriminator.is_none());
+        let branches = value.one_of.as_ref().unwrap();
+        assert_eq!(branches[0].schema_type, Some("number".to_string()));
+        assert_eq!(branches[1].reference, Some("#/components/schemas/Point".to_string()));
+        assert_eq!(branches[2].schema_type, Some("object".to_string()));
+        assert!(branches[2].properties.as_ref().unwrap().contains_key("left"));
+
+        // Internally tagged: the tag property next to the variant's (or wrapped struct's) fields
+        let event = &schemas["Event"];
+        assert_eq!(event.discriminator.as_ref().unwrap().property_name, "type");
+        let branches = event.one_of.as_ref().unwrap();
+        let moved = branches[0].properties.as_ref().unwrap();
+        assert_eq!(moved["type"].enum_values, Some(vec![serde_json::json!("Moved")]));
+        assert!(moved.contains_key("x") && moved.contains_key("y"));
+        assert_eq!(branches[0].required.as_ref().unwrap()[0], "type");
+        assert!(branches[1].properties.as_ref().unwrap().contains_key("name"));
+        assert_eq!(branches[2].properties.as_ref().unwrap().len(), 1);
+
+        // Adjacently tagged: tag and content properties
+        let message = &schemas["Message"];
+        assert_eq!(message.discriminator.as_ref().unwrap().property_name, "t");
+        let branches = message.one_of.as_ref().unwrap();
+        let text = branches[0].properties.as_ref().unwrap();
+        assert_eq!(text["c"].property_type, Some("string".to_string()));
+        assert_eq!(branches[0].required, Some(vec!["t".to_string(), "c".to_string()]));
+        assert_eq!(branches[1].required, Some(vec!["t".to_string()]));
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_integer_enum_schema() {
+        let code = r#"
+            #[repr(u8)]
+            pub enum Priority {
+                Low = 0,
+                Medium = 1,
+                High = 2,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Priority".to_string()));
+
+        let schema = &generator.get_schemas()["Priority"];
+        assert_eq!(schema.schema_type, Some("integer".to_string()));
+        assert_eq!(
+            serde_json::to_value(schema).unwrap()["enum"],
+            serde_json::json!([0, 1, 2])
+        );
     }
 
     #[test]
@@ -736,6 +2375,123 @@ mod tests {
         assert_eq!(param_schema.schema.schema_type, Some("integer".to_string()));
     }
 
+    #[test]
+    fn test_parameter_schemas_from_query_struct() {
+        let mut generator = create_generator_from_code(
+            r#"
+            pub struct Pagination {
+                /// Page number, starting at 1
+                pub page: u32,
+                #[openapi(param_description = "Maximum number of items")]
+                /// Page size
+                pub limit: Option<u32>,
+                #[deprecated]
+                #[serde(rename = "sort")]
+                pub order_by: Option<String>,
+                #[serde(skip)]
+                pub cursor: String,
+            }
+        "#,
+        );
+        let param = Parameter::new(
+            "query_params".to_string(),
+            ParameterLocation::Query,
+            TypeInfo::new("Pagination".to_string()),
+            false,
+        );
+
+        let params = generator.generate_parameter_schemas(&param);
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["page", "limit", "sort"]);
+
+        assert!(params.iter().all(|p| p.location == "query"));
+        assert!(params[0].required);
+        assert_eq!(params[0].description, Some("Page number, starting at 1".to_string()));
+        assert_eq!(params[0].schema.format, Some("int32".to_string()));
+        assert!(params[0].deprecated.is_none());
+
+        assert!(!params[1].required);
+        assert_eq!(params[1].description, Some("Maximum number of items".to_string()));
+
+        assert_eq!(params[2].deprecated, Some(true));
+        assert!(params[2].description.is_none());
+
+        // Non-struct parameters are not expanded
+        let page = Parameter::new(
+            "page".to_string(),
+            ParameterLocation::Query,
+            TypeInfo::new("i32".to_string()),
+            false,
+        );
+        assert_eq!(generator.generate_parameter_schemas(&page).len(), 1);
+    }
+
+    #[test]
+    fn test_multi_value_query_parameters() {
+        let mut generator = create_generator_from_code(
+            r#"
+            pub struct Filter {
+                pub tags: Vec<String>,
+                pub ids: Option<Vec<u64>>,
+                pub q: String,
+            }
+        "#,
+        );
+        let param = Parameter::new(
+            "filter".to_string(),
+            ParameterLocation::Query,
+            TypeInfo::new("Filter".to_string()),
+            false,
+        );
+
+        let params = generator.generate_parameter_schemas(&param);
+        let json = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            json[0],
+            serde_json::json!({
+                "name": "tags",
+                "in": "query",
+                "required": true,
+                "schema": {"type": "array", "items": {"type": "string"}},
+                "style": "form",
+                "explode": true
+            })
+        );
+        assert_eq!(json[1]["schema"]["type"], "array");
+        assert_eq!(json[1]["required"], false);
+        assert_eq!(json[1]["style"], "form");
+        assert!(params[2].style.is_none() && params[2].explode.is_none());
+    }
+
+    #[test]
+    fn test_query_map_parameter() {
+        let mut generator = create_generator_from_code("");
+        let param = Parameter::new(
+            "query_params".to_string(),
+            ParameterLocation::Query,
+            TypeInfo::map(
+                "HashMap".to_string(),
+                TypeInfo::new("String".to_string()),
+                TypeInfo::new("String".to_string()),
+            ),
+            false,
+        );
+
+        let params = generator.generate_parameter_schemas(&param);
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            serde_json::json!([{
+                "name": "query_params",
+                "in": "query",
+                "required": false,
+                "schema": {"type": "object", "additionalProperties": {"type": "string"}},
+                "style": "form",
+                "explode": true
+            }])
+        );
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
     #[test]
     fn test_parameter_schema_query() {
         let mut generator = create_generator_from_code("");
@@ -814,6 +2570,61 @@ mod tests {
         assert!(required.is_none() || !required.unwrap().contains(&"data".to_string()));
     }
 
+    #[test]
+    fn test_result_and_impl_trait_types() {
+        let code = r#"
+            pub struct User {
+                pub id: u32,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+
+        let result = TypeInfo::result(
+            TypeInfo::new("User".to_string()),
+            Some(TypeInfo::new("AppError".to_string())),
+        );
+        let schema = generator.generate_schema(&result);
+        assert_eq!(schema.reference, Some("#/components/schemas/User".to_string()));
+
+        let impl_trait = TypeInfo::impl_trait("IntoResponse".to_string());
+        let schema = generator.generate_schema(&impl_trait);
+        assert_eq!(schema.schema_type, Some("object".to_string()));
+
+        // Neither the error arm nor the trait is reported as an unresolved type
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_trait_object_types() {
+        let code = r#"
+            pub struct Event {
+                pub name: String,
+                pub payload: Box<dyn erased_serde::Serialize + Send>,
+                pub handlers: Vec<Box<dyn Handler>>,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        let schema = generator.generate_schema(&TypeInfo::trait_object("Serialize".to_string()));
+        assert_eq!(schema.schema_type.as_deref(), Some("object"));
+        assert_eq!(
+            schema.description.as_deref(),
+            Some("Trait object `dyn Serialize`; the concrete type is only known at run time")
+        );
+
+        generator.generate_schema(&TypeInfo::new("Event".to_string()));
+        let event = serde_json::to_value(&generator.get_schemas()["Event"]).unwrap();
+        let payload = &event["properties"]["payload"]["allOf"][0];
+        assert_eq!(payload["type"], "object");
+        assert!(payload["description"].as_str().unwrap().contains("dyn Serialize"));
+        let handlers = &event["properties"]["handlers"]["items"];
+        assert!(handlers["description"].as_str().unwrap().contains("dyn Handler"));
+
+        // Deliberately dynamic types are not reported as unresolved
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
     #[test]
     fn test_unknown_type_fallback() {
         let mut generator = create_generator_from_code("");
@@ -823,6 +2634,41 @@ mod tests {
         // Should fallback to object type
         assert_eq!(schema.schema_type, Some("object".to_string()));
         assert!(schema.reference.is_none());
+
+        // The placeholder should be recorded
+        assert!(generator.get_unresolved_types().contains("UnknownType"));
+        assert!(schema.description.is_none());
+        assert!(schema.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_unresolved_markers() {
+        let code = r#"
+            #[derive(Serialize)]
+            struct Invoice {
+                id: u64,
+                amount: Money,
+            }
+        "#;
+        let mut generator = create_generator_from_code(code).with_unresolved_markers(true);
+
+        let schema = generator.generate_schema(&TypeInfo::new("UnknownType".to_string()));
+        assert_eq!(schema.schema_type.as_deref(), Some("object"));
+        assert_eq!(schema.description.as_deref(), Some("TODO: unresolved type UnknownType"));
+        assert_eq!(schema.extensions["x-unresolved"], serde_json::json!(true));
+
+        generator.generate_schema(&TypeInfo::new("Invoice".to_string()));
+        let invoice = serde_json::to_value(&generator.get_schemas()["Invoice"]).unwrap();
+        assert_eq!(
+            invoice["properties"]["amount"],
+            serde_json::json!({"allOf": [{
+                "type": "object",
+                "description": "TODO: unresolved type Money",
+                "x-unresolved": true
+            }]})
+        );
+        assert_eq!(invoice["properties"]["id"]["type"], "integer");
+        assert!(generator.get_unresolved_types().contains("Money"));
     }
 
     #[test]
@@ -846,4 +2692,480 @@ mod tests {
         assert_eq!(schemas.len(), 1);
         assert!(schemas.contains_key("User"));
     }
+
+    #[test]
+    fn test_serde_as_overrides_field_type() {
+        let code = r#"
+            #[serde_as]
+            pub struct Account {
+                #[serde_as(as = "DisplayFromStr")]
+                pub balance: u64,
+                #[serde_as(as = "Bytes")]
+                pub avatar: Vec<u8>,
+                #[serde_as(as = "Vec<DisplayFromStr>")]
+                pub ids: Vec<u64>,
+                #[serde_as(as = "Option<DisplayFromStr>")]
+                pub limit: Option<u32>,
+                #[serde_as(as = "_")]
+                pub count: u32,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Account".to_string()));
+
+        let schema = generator.get_schemas().get("Account").unwrap();
+        let properties = schema.properties.as_ref().unwrap();
+
+        let balance = &properties["balance"];
+        assert_eq!(balance.property_type, Some("string".to_string()));
+        assert_eq!(balance.format, None);
+
+        let avatar = &properties["avatar"];
+        assert_eq!(avatar.property_type, Some("string".to_string()));
+        assert_eq!(avatar.format, Some("byte".to_string()));
+
+        let ids = &properties["ids"];
+        assert_eq!(ids.property_type, Some("array".to_string()));
+        let items = ids.items.as_ref().unwrap();
+        assert_eq!(items.schema_type, Some("string".to_string()));
+
+        let limit = &properties["limit"];
+        assert_eq!(limit.property_type, Some("string".to_string()));
+        let required = schema.required.as_ref().unwrap();
+        assert!(!required.contains(&"limit".to_string()));
+
+        let count = &properties["count"];
+        assert_eq!(count.property_type, Some("integer".to_string()));
+        assert_eq!(count.format, Some("int32".to_string()));
+    }
+
+    #[test]
+    fn test_mutually_recursive_structs() {
+        let code = r#"
+            pub struct Author {
+                pub name: String,
+                pub books: Vec<Book>,
+            }
+
+            pub struct Book {
+                pub title: String,
+                pub author: Author,
+                pub sequel: Option<Box<Book>>,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        let schema = generator.generate_schema(&TypeInfo::new("Author".to_string()));
+        assert_eq!(
+            schema.reference,
+            Some("#/components/schemas/Author".to_string())
+        );
+
+        let schemas = generator.get_schemas();
+        let author = schemas.get("Author").unwrap();
+        let book = schemas.get("Book").unwrap();
+
+        // Both schemas are fully generated, not left as placeholders
+        let author_props = author.properties.as_ref().unwrap();
+        let books = author_props["books"].items.as_ref().unwrap();
+        assert_eq!(books.reference, Some("#/components/schemas/Book".to_string()));
+
+        let book_props = book.properties.as_ref().unwrap();
+        assert_eq!(
+            book_props["author"].reference,
+            Some("#/components/schemas/Author".to_string())
+        );
+    }
+
+    #[test]
+    fn test_self_recursive_struct() {
+        let code = r#"
+            pub struct TreeNode {
+                pub value: i32,
+                pub children: Vec<TreeNode>,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("TreeNode".to_string()));
+
+        let node = generator.get_schemas().get("TreeNode").unwrap();
+        let children = &node.properties.as_ref().unwrap()["children"];
+        assert_eq!(
+            children.items.as_ref().unwrap().reference,
+            Some("#/components/schemas/TreeNode".to_string())
+        );
+    }
+
+    #[test]
+    fn test_schema_title_and_description() {
+        let code = r#"
+            /// A registered user.
+            pub struct User {
+                pub id: u32,
+            }
+
+            /// Lifecycle state of an account.
+            pub enum Status {
+                Active,
+                Disabled,
+            }
+
+            pub struct Plain {
+                pub value: u32,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("User".to_string()));
+        generator.generate_schema(&TypeInfo::new("Status".to_string()));
+        generator.generate_schema(&TypeInfo::new("Plain".to_string()));
+
+        let schemas = generator.get_schemas();
+        let user = &schemas["User"];
+        assert_eq!(user.title, Some("User".to_string()));
+        assert_eq!(user.description, Some("A registered user.".to_string()));
+
+        let status = &schemas["Status"];
+        assert_eq!(status.title, Some("Status".to_string()));
+        assert_eq!(
+            status.description,
+            Some("Lifecycle state of an account.".to_string())
+        );
+
+        let plain = &schemas["Plain"];
+        assert_eq!(plain.title, Some("Plain".to_string()));
+        assert_eq!(plain.description, None);
+    }
+
+    #[test]
+    fn test_qualified_field_types_match_bare_types() {
+        let code = r#"
+            pub struct Bare {
+                pub name: String,
+                pub count: u32,
+                pub tags: Vec<String>,
+                pub labels: HashMap<String, String>,
+            }
+
+            pub struct Qualified {
+                pub name: std::string::String,
+                pub count: core::primitive::u32,
+                pub tags: std::vec::Vec<std::string::String>,
+                pub labels: std::collections::HashMap<String, String>,
+            }
+        "#;
+
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Bare".to_string()));
+        generator.generate_schema(&TypeInfo::new("Qualified".to_string()));
+
+        let schemas = generator.get_schemas();
+        let bare = serde_json::to_value(&schemas["Bare"].properties).unwrap();
+        let qualified = serde_json::to_value(&schemas["Qualified"].properties).unwrap();
+        assert_eq!(bare, qualified);
+        assert_eq!(bare["labels"]["type"], "object");
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_generic_struct_instances() {
+        let code = r#"
+            pub struct ApiResponse<T> {
+                pub data: T,
+                pub items: Vec<T>,
+                pub message: Option<String>,
+            }
+            pub struct User { pub id: u32 }
+            pub struct Post { pub title: String }
+            pub struct ApiResponse_Post { pub legacy: bool }
+        "#;
+        let mut generator = create_generator_from_code(code);
+        let instance = |arg: &str| TypeInfo {
+            is_generic: true,
+            generic_args: vec![TypeInfo::new(arg.to_string())],
+            ..TypeInfo::new("ApiResponse".to_string())
+        };
+
+        let users = generator.generate_schema(&instance("User"));
+        let again = generator.generate_schema(&instance("User"));
+        let posts = generator.generate_schema(&instance("Post"));
+        assert_eq!(
+            users.reference.as_deref(),
+            Some("#/components/schemas/ApiResponse_User")
+        );
+        assert_eq!(again.reference, users.reference);
+        // `ApiResponse_Post` is a type of the project, so the instance is disambiguated
+        assert_eq!(
+            posts.reference.as_deref(),
+            Some("#/components/schemas/ApiResponse_Post_2")
+        );
+
+        let schemas = generator.get_schemas();
+        let response = &schemas["ApiResponse_User"];
+        assert_eq!(response.title.as_deref(), Some("ApiResponse<User>"));
+        let properties = response.properties.as_ref().unwrap();
+        assert_eq!(
+            properties["data"].reference.as_deref(),
+            Some("#/components/schemas/User")
+        );
+        assert_eq!(
+            properties["items"].items.as_ref().unwrap().reference.as_deref(),
+            Some("#/components/schemas/User")
+        );
+        assert!(!schemas.contains_key("ApiResponse"));
+        assert!(generator.get_unresolved_types().is_empty());
+
+        let nested = TypeInfo {
+            is_generic: true,
+            generic_args: vec![TypeInfo::vec(TypeInfo::new("Post".to_string()))],
+            ..TypeInfo::new("Page".to_string())
+        };
+        assert_eq!(instance_name(&nested), "Page_Vec_Post");
+        assert_eq!(type_key(&nested), "Page<Vec<Post>>");
+    }
+
+    #[test]
+    fn test_unit_and_empty_structs() {
+        let code = r#"
+            /// Marks a ping
+            pub struct Marker;
+            pub struct Empty {}
+            pub struct Ping {
+                pub marker: Marker,
+                pub empty: Empty,
+            }
+        "#;
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Ping".to_string()));
+
+        let schemas = serde_json::to_value(generator.get_schemas()).unwrap();
+        assert_eq!(
+            schemas["Marker"],
+            serde_json::json!({"title": "Marker", "description": "Marks a ping"})
+        );
+        assert_eq!(
+            schemas["Empty"],
+            serde_json::json!({"title": "Empty", "type": "object"})
+        );
+        assert_eq!(
+            schemas["Ping"]["properties"]["marker"]["$ref"],
+            "#/components/schemas/Marker"
+        );
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_serde_default_fields_are_not_required() {
+        let code = r#"
+            pub enum Status { Active, Inactive }
+            pub struct Account {
+                pub id: u32,
+                #[serde(default)]
+                pub status: Status,
+                #[serde(default = "default_limit")]
+                pub limit: u32,
+                #[serde(rename = "default_name")]
+                pub name: String,
+            }
+        "#;
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Account".to_string()));
+
+        let account = &generator.get_schemas()["Account"];
+        assert_eq!(
+            account.required,
+            Some(vec!["id".to_string(), "default_name".to_string()])
+        );
+        assert_eq!(
+            account.properties.as_ref().unwrap()["status"].reference.as_deref(),
+            Some("#/components/schemas/Status")
+        );
+    }
+
+    #[test]
+    fn test_rename_all_fields_on_struct_variants() {
+        let code = r#"
+            #[derive(Serialize)]
+            #[serde(tag = "type", rename_all_fields = "camelCase")]
+            pub enum Event {
+                Created { user_id: u32, #[serde(rename = "ts")] created_at: String },
+                Moved(Point),
+            }
+        "#;
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Event".to_string()));
+
+        let event = &generator.get_schemas()["Event"];
+        let created = event.one_of.as_ref().unwrap()[0].properties.as_ref().unwrap();
+        let mut names: Vec<&String> = created.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["ts", "type", "userId"]);
+    }
+
+    #[test]
+    fn test_option_handling() {
+        let code = r#"
+            pub struct Profile {
+                pub bio: String,
+            }
+            pub struct User {
+                pub id: u32,
+                pub nickname: Option<String>,
+                pub profile: Option<Profile>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                pub email: Option<String>,
+            }
+        "#;
+        let generate = |handling: OptionHandling| {
+            let mut generator = create_generator_from_code(code).with_option_handling(handling);
+            generator.generate_schema(&TypeInfo::new("User".to_string()));
+            serde_json::to_value(&generator.get_schemas()["User"]).unwrap()
+        };
+
+        let both = generate(OptionHandling::Both);
+        assert_eq!(both["required"], serde_json::json!(["id"]));
+        assert_eq!(
+            both["properties"]["nickname"],
+            serde_json::json!({"type": "string", "nullable": true})
+        );
+        assert_eq!(
+            both["properties"]["profile"],
+            serde_json::json!({
+                "nullable": true,
+                "allOf": [{"$ref": "#/components/schemas/Profile"}]
+            })
+        );
+
+        // Fields left out when `None` are optional, but never `null`
+        assert_eq!(both["properties"]["email"], serde_json::json!({"type": "string"}));
+
+        let nullable = generate(OptionHandling::Nullable);
+        assert_eq!(nullable["required"], serde_json::json!(["id", "nickname", "profile"]));
+        assert_eq!(nullable["properties"]["nickname"]["nullable"], true);
+        assert_eq!(nullable["properties"]["email"], serde_json::json!({"type": "string"}));
+
+        let optional = generate(OptionHandling::Optional);
+        assert_eq!(optional["required"], serde_json::json!(["id"]));
+        assert_eq!(optional["properties"]["nickname"], serde_json::json!({"type": "string"}));
+        assert_eq!(
+            optional["properties"]["profile"]["$ref"],
+            "#/components/schemas/Profile"
+        );
+    }
+
+    #[test]
+    fn test_generate_all() {
+        let code = r#"
+            pub struct User {
+                pub id: u32,
+                pub role: Role,
+            }
+            pub enum Role { Admin, Member }
+            pub struct AuditEntry {
+                pub message: String,
+            }
+            pub struct Page<T> {
+                pub items: Vec<T>,
+            }
+        "#;
+        let mut generator = create_generator_from_code(code);
+        assert_eq!(
+            generator.type_resolver.all_struct_names(),
+            vec!["AuditEntry", "Page", "User"]
+        );
+        assert_eq!(generator.type_resolver.all_enum_names(), vec!["Role"]);
+
+        generator.generate_all();
+        let mut names: Vec<&String> = generator.get_schemas().keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["AuditEntry", "Role", "User"]);
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_validated_lengths_and_newtypes() {
+        let code = r#"
+            #[validate(length(min = 1))]
+            pub struct NonEmptyString(String);
+            pub struct Code(#[validate(length(equal = 6))] String);
+            pub struct Tree(Vec<Tree>);
+            pub struct Signup {
+                #[validate(length(min = 3, max = 32), custom(function = "check_name"))]
+                pub username: String,
+                pub display_name: NonEmptyString,
+                #[validate(length(max = 64))]
+                pub bio: Option<NonEmptyString>,
+                pub code: Code,
+                #[validate(length(min = 1))]
+                pub tags: Vec<String>,
+                pub tree: Tree,
+            }
+        "#;
+        let mut generator = create_generator_from_code(code);
+        generator.generate_schema(&TypeInfo::new("Signup".to_string()));
+
+        let schemas = serde_json::to_value(generator.get_schemas()).unwrap();
+        let properties = &schemas["Signup"]["properties"];
+        assert_eq!(
+            properties["username"],
+            serde_json::json!({"type": "string", "minLength": 3, "maxLength": 32})
+        );
+        assert_eq!(
+            properties["display_name"],
+            serde_json::json!({"type": "string", "minLength": 1})
+        );
+        assert_eq!(
+            properties["bio"],
+            serde_json::json!({
+                "type": "string", "minLength": 1, "maxLength": 64, "nullable": true
+            })
+        );
+        assert_eq!(properties["code"]["minLength"], 6);
+        assert_eq!(properties["code"]["maxLength"], 6);
+        assert!(properties["tags"].get("minLength").is_none());
+        assert_eq!(properties["tree"]["items"]["$ref"], "#/components/schemas/Tree");
+        assert_eq!(schemas["Tree"]["type"], "array");
+        assert!(schemas.get("NonEmptyString").is_none());
+
+        let schema = generator.generate_schema(&TypeInfo::new("NonEmptyString".to_string()));
+        assert_eq!(schema.schema_type.as_deref(), Some("string"));
+        assert_eq!(schema.min_length, Some(1));
+        assert!(generator.get_unresolved_types().is_empty());
+    }
+
+    #[test]
+    fn test_excluded_types() {
+        let code = r#"
+            pub struct Pool { pub size: u32 }
+            pub struct Config { pub url: String }
+            pub enum Mode { Fast, Safe }
+            pub struct Status {
+                pub healthy: bool,
+                pub pool: Pool,
+                pub config: Option<Config>,
+                pub modes: Vec<Mode>,
+            }
+        "#;
+        let excluded = ["Pool", "Config", "Mode"].map(String::from).into_iter().collect();
+        let mut generator = create_generator_from_code(code).exclude_types(excluded);
+
+        let schema = generator.generate_schema(&TypeInfo::new("Pool".to_string()));
+        assert_eq!(schema.schema_type.as_deref(), Some("object"));
+        assert!(schema.reference.is_none());
+
+        generator.generate_schema(&TypeInfo::new("Status".to_string()));
+        let schemas = serde_json::to_value(generator.get_schemas()).unwrap();
+        let properties = &schemas["Status"]["properties"];
+        assert_eq!(properties["pool"], serde_json::json!({"type": "object"}));
+        assert_eq!(properties["config"]["type"], "object");
+        assert_eq!(properties["modes"]["items"], serde_json::json!({"type": "object"}));
+
+        generator.generate_all();
+        let mut names: Vec<&String> = generator.get_schemas().keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["Status"]);
+        assert!(generator.get_unresolved_types().is_empty());
+    }
 }
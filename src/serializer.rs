@@ -1,12 +1,13 @@
 //! Serialization module for converting OpenAPI documents to YAML or JSON format.
 //!
 //! This module provides functions to serialize OpenAPI documents into standard formats
-//! and write them to files or return them as strings.
+//! and write them to files or any [`Write`] implementation, or return them as strings.
 
 use crate::openapi_builder::OpenApiDocument;
 use anyhow::{Context, Result};
 use log::debug;
 use std::fs;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
 /// Serializes an OpenAPI document to YAML format.
@@ -42,9 +43,63 @@ use std::path::Path;
 /// println!("{}", yaml);
 /// ```
 pub fn serialize_yaml(doc: &OpenApiDocument) -> Result<String> {
-    debug!("Serializing OpenAPI document to YAML");
-    serde_yaml::to_string(doc)
-        .context("Failed to serialize OpenAPI document to YAML")
+    serialize_yaml_with(doc, &YamlOptions::default())
+}
+
+/// Options controlling YAML output.
+///
+/// `serde_yaml` has no settings for line width or scalar styles: strings are never wrapped and
+/// are quoted only where YAML requires it. The options cover what can be controlled around it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YamlOptions {
+    /// Start the output with a `---` document start marker
+    pub document_start: bool,
+}
+
+/// Serializes an OpenAPI document to YAML format with the given options.
+///
+/// [`serialize_yaml`] is this function with [`YamlOptions::default`], which omits the `---`
+/// document start marker.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn serialize_yaml_with(doc: &OpenApiDocument, options: &YamlOptions) -> Result<String> {
+    let mut buffer = Vec::new();
+    write_yaml_with(doc, &mut buffer, options)?;
+    String::from_utf8(buffer).context("Serialized YAML is not valid UTF-8")
+}
+
+/// Serializes an OpenAPI document to YAML format directly into `writer`.
+///
+/// Unlike [`serialize_yaml`], no string holding the whole document is built, which matters for
+/// specs with hundreds of schemas. The writer is not flushed.
+///
+/// # Errors
+///
+/// Returns an error if serialization or writing fails.
+pub fn write_yaml<W: Write>(doc: &OpenApiDocument, writer: W) -> Result<()> {
+    write_yaml_with(doc, writer, &YamlOptions::default())
+}
+
+/// Serializes an OpenAPI document to YAML format with the given options directly into `writer`.
+///
+/// # Errors
+///
+/// Returns an error if serialization or writing fails.
+pub fn write_yaml_with<W: Write>(
+    doc: &OpenApiDocument,
+    mut writer: W,
+    options: &YamlOptions,
+) -> Result<()> {
+    debug!("Serializing OpenAPI document to YAML ({:?})", options);
+    // `serde_yaml` never starts a single document with a marker
+    if options.document_start {
+        writer
+            .write_all(b"---\n")
+            .context("Failed to write OpenAPI document")?;
+    }
+    serde_yaml::to_writer(writer, doc).context("Failed to serialize OpenAPI document to YAML")
 }
 
 /// Serializes an OpenAPI document to JSON format with pretty printing.
@@ -80,11 +135,50 @@ pub fn serialize_yaml(doc: &OpenApiDocument) -> Result<String> {
 /// println!("{}", json);
 /// ```
 pub fn serialize_json(doc: &OpenApiDocument) -> Result<String> {
+    let mut buffer = Vec::new();
+    write_json(doc, &mut buffer)?;
+    String::from_utf8(buffer).context("Serialized JSON is not valid UTF-8")
+}
+
+/// Serializes an OpenAPI document to pretty-printed JSON directly into `writer`.
+///
+/// Unlike [`serialize_json`], no string holding the whole document is built, which matters for
+/// specs with hundreds of schemas. The writer is not flushed.
+///
+/// # Errors
+///
+/// Returns an error if serialization or writing fails.
+pub fn write_json<W: Write>(doc: &OpenApiDocument, writer: W) -> Result<()> {
     debug!("Serializing OpenAPI document to JSON");
-    serde_json::to_string_pretty(doc)
+    serde_json::to_writer_pretty(writer, doc)
         .context("Failed to serialize OpenAPI document to JSON")
 }
 
+/// Reads an OpenAPI document from a file, as JSON if its extension is `.json` and as YAML
+/// otherwise.
+///
+/// Fields [`OpenApiDocument`] does not model (`externalDocs`, operation `callbacks`,
+/// `components.securitySchemes`, `x-` extensions, ...) are kept in the `extra` maps of the
+/// document, its components and its operations, so writing the document back preserves them.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a valid document.
+pub fn load_document(path: &Path) -> Result<OpenApiDocument> {
+    debug!("Loading OpenAPI document from {}", path.display());
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let is_json = path.extension().and_then(|s| s.to_str()) == Some("json");
+    let document = if is_json {
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON document: {}", path.display()))?
+    } else {
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse YAML document: {}", path.display()))?
+    };
+    Ok(document)
+}
+
 /// Writes string content to a file.
 ///
 /// Creates the file if it doesn't exist, or overwrites it if it does.
@@ -118,6 +212,24 @@ pub fn write_to_file(content: &str, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Creates (or truncates) a file for writing, creating its parent directories.
+///
+/// The file is buffered, for use with [`write_json`] and [`write_yaml`]; flush it once the
+/// document is written to see write errors.
+///
+/// # Errors
+///
+/// Returns an error if a directory or the file cannot be created.
+pub fn create_output_file(path: &Path) -> Result<BufWriter<fs::File>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let file = fs::File::create(path)
+        .with_context(|| format!("Failed to create file: {}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,9 +245,18 @@ mod tests {
                 title: "Test API".to_string(),
                 version: "1.0.0".to_string(),
                 description: Some("A test API".to_string()),
+                extensions: Default::default(),
+                extra: Default::default(),
             },
+            servers: None,
             paths: HashMap::new(),
+            webhooks: HashMap::new(),
+            x_webhooks: HashMap::new(),
             components: None,
+            security: None,
+            tags: None,
+            extensions: Default::default(),
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -160,6 +281,23 @@ mod tests {
         assert!(yaml.contains("paths:"));
     }
 
+    #[test]
+    fn test_serialize_yaml_document_start() {
+        let doc = create_test_document();
+
+        let plain = serialize_yaml(&doc).unwrap();
+        assert!(plain.starts_with("openapi:"));
+
+        let options = YamlOptions {
+            document_start: true,
+        };
+        let marked = serialize_yaml_with(&doc, &options).unwrap();
+        assert_eq!(marked, format!("---\n{}", plain));
+
+        let roundtrip: OpenApiDocument = serde_yaml::from_str(&marked).unwrap();
+        assert_eq!(roundtrip.info.title, "Test API");
+    }
+
     #[test]
     fn test_serialize_json() {
         let doc = create_test_document();
@@ -200,6 +338,28 @@ mod tests {
         assert!(line_count > 5, "Pretty printed JSON should have multiple lines");
     }
 
+    #[test]
+    fn test_writers_match_strings() {
+        let doc = create_test_document();
+
+        let mut json = Vec::new();
+        write_json(&doc, &mut json).unwrap();
+        assert_eq!(String::from_utf8(json).unwrap(), serialize_json(&doc).unwrap());
+
+        let mut yaml = Vec::new();
+        write_yaml(&doc, &mut yaml).unwrap();
+        assert_eq!(String::from_utf8(yaml).unwrap(), serialize_yaml(&doc).unwrap());
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("out").join("openapi.yaml");
+        let mut file = create_output_file(&path).unwrap();
+        let options = YamlOptions { document_start: true };
+        write_yaml_with(&doc, &mut file, &options).unwrap();
+        file.flush().unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("---\nopenapi: 3.0.0\n"));
+    }
+
     #[test]
     fn test_write_to_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -390,4 +550,48 @@ mod tests {
         
         assert_eq!(deserialized.info.title, "Test API");
     }
+
+    #[test]
+    fn test_load_document_round_trips_unmodeled_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("base.yaml");
+        let yaml = r#"
+openapi: 3.1.0
+info:
+  title: Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      security:
+        - bearer: []
+      responses:
+        200:
+          description: OK
+webhooks:
+  newPet:
+    post:
+      responses:
+        '200':
+          description: OK
+"#;
+        fs::write(&path, yaml).unwrap();
+
+        let doc = load_document(&path).unwrap();
+        assert!(doc.webhooks["newPet"].post.is_some());
+        let get = doc.paths["/pets"].get.as_ref().unwrap();
+        assert!(get.responses.contains_key("200"));
+        assert!(get.extra.contains_key("security"));
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&serialize_yaml(&doc).unwrap()).unwrap();
+        let original: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(written["webhooks"], original["webhooks"]);
+        assert_eq!(
+            written["paths"]["/pets"]["get"]["security"],
+            original["paths"]["/pets"]["get"]["security"]
+        );
+
+        assert!(load_document(&temp_dir.path().join("missing.json")).is_err());
+    }
 }
@@ -1,7 +1,9 @@
-use crate::extractor::TypeInfo;
-use crate::parser::ParsedFile;
+use crate::extractor::{
+    bound_trait_name, parse_openapi_attributes, TypeInfo, MAP_TYPES, TRANSPARENT_WRAPPERS,
+};
+use crate::parser::{extract_doc_comment, ParsedFile};
 use log::{debug, warn};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 /// Type resolver - resolves Rust type definitions to structured type information
 pub struct TypeResolver {
@@ -20,6 +22,8 @@ pub struct ResolvedType {
     pub name: String,
     /// The kind of type (struct, enum, primitive, etc.)
     pub kind: TypeKind,
+    /// Doc comment on the type definition, if any
+    pub doc: Option<String>,
 }
 
 /// Type kind - represents different categories of types
@@ -40,6 +44,16 @@ pub enum TypeKind {
 pub struct StructDef {
     /// The fields of the struct
     pub fields: Vec<FieldDef>,
+    /// Names of the struct's type parameters, in declaration order (`["T"]` for
+    /// `ApiResponse<T>`)
+    pub generics: Vec<String>,
+    /// Whether this is a unit struct (`struct Marker;`), which serde writes as `null`
+    pub unit: bool,
+    /// Type wrapped by a newtype struct (`struct Email(String)`), which serde writes as the
+    /// wrapped value
+    pub newtype: Option<TypeInfo>,
+    /// Length bounds of a newtype's value, validated on the struct or on its field
+    pub length: LengthLimits,
 }
 
 /// Field definition in a struct
@@ -53,6 +67,21 @@ pub struct FieldDef {
     pub optional: bool,
     /// Serde attributes applied to this field
     pub serde_attrs: SerdeAttributes,
+    /// Description from `#[openapi(param_description = "...")]`, or else the doc comment
+    pub description: Option<String>,
+    /// Whether the field is marked `#[deprecated]`
+    pub deprecated: bool,
+    /// Length bounds from `#[validate(length(...))]`
+    pub length: LengthLimits,
+}
+
+/// Length bounds from a validator attribute: `#[validate(length(min = 1, max = 64))]`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LengthLimits {
+    /// Minimum length (`min`, or `equal`)
+    pub min: Option<u64>,
+    /// Maximum length (`max`, or `equal`)
+    pub max: Option<u64>,
 }
 
 /// Enum definition with variants
@@ -60,6 +89,50 @@ pub struct FieldDef {
 pub struct EnumDef {
     /// The variants of the enum
     pub variants: Vec<String>,
+    /// The data carried by each variant, in the same order as `variants`
+    pub variant_fields: Vec<VariantFields>,
+    /// Doc comment of each variant, in the same order as `variants`
+    pub variant_docs: Vec<Option<String>>,
+    /// Integer values of the variants when the enum serializes as a number
+    /// (`#[repr(u8)]` with explicit discriminants, or `serde_repr`)
+    pub discriminants: Option<Vec<i64>>,
+    /// How variants are represented on the wire, from the container's serde attributes
+    pub representation: EnumRepresentation,
+}
+
+/// Serde representation of an enum's variants
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EnumRepresentation {
+    /// `{"Variant": data}`, or `"Variant"` for unit variants (serde's default)
+    #[default]
+    External,
+    /// `#[serde(tag = "type")]`: `{"type": "Variant", ..fields}`
+    Internal { tag: String },
+    /// `#[serde(tag = "t", content = "c")]`: `{"t": "Variant", "c": data}`
+    Adjacent { tag: String, content: String },
+    /// `#[serde(untagged)]`: the variant data alone
+    Untagged,
+}
+
+/// Data carried by an enum variant
+#[derive(Debug, Clone)]
+pub enum VariantFields {
+    /// A variant without data (`Empty`)
+    Unit,
+    /// A struct variant (`Circle { radius: f64 }`)
+    Named(Vec<FieldDef>),
+    /// A tuple variant (`Rect(f64, f64)`); a single element is a newtype variant
+    Unnamed(Vec<TypeInfo>),
+}
+
+impl EnumDef {
+    /// Whether any variant carries data, so the enum cannot be documented as a plain list of
+    /// names or discriminants
+    pub fn has_data(&self) -> bool {
+        self.variant_fields
+            .iter()
+            .any(|fields| !matches!(fields, VariantFields::Unit))
+    }
 }
 
 /// Primitive types supported
@@ -76,6 +149,10 @@ pub enum PrimitiveType {
     U32,
     U64,
     U128,
+    /// Pointer-sized signed integer
+    Isize,
+    /// Pointer-sized unsigned integer
+    Usize,
     F32,
     F64,
     Bool,
@@ -87,10 +164,24 @@ pub enum PrimitiveType {
 pub struct SerdeAttributes {
     /// Renamed field name
     pub rename: Option<String>,
-    /// Whether to skip this field during serialization
+    /// Whether to skip this field during serialization and deserialization
     pub skip: bool,
+    /// Whether the field is never serialized (`#[serde(skip_serializing)]`), so only
+    /// requests carry it
+    pub skip_serializing: bool,
+    /// Whether the field is never deserialized (`#[serde(skip_deserializing)]`), so only
+    /// responses carry it
+    pub skip_deserializing: bool,
+    /// Whether the field is left out of the output under a condition
+    /// (`#[serde(skip_serializing_if = "Option::is_none")]`) rather than written as `null`
+    pub skip_serializing_if: bool,
     /// Whether to flatten this field
     pub flatten: bool,
+    /// Wire type from a `serde_with` annotation: `#[serde_as(as = "DisplayFromStr")]`
+    pub serde_as: Option<String>,
+    /// Whether the field may be missing on input: `#[serde(default)]` or
+    /// `#[serde(default = "path")]`
+    pub default: bool,
 }
 
 impl TypeResolver {
@@ -142,6 +233,35 @@ impl TypeResolver {
         None
     }
 
+    /// Names of every struct defined at the top level of the parsed files, sorted and without
+    /// duplicates
+    pub fn all_struct_names(&self) -> Vec<String> {
+        self.item_names(|item| match item {
+            syn::Item::Struct(item_struct) => Some(&item_struct.ident),
+            _ => None,
+        })
+    }
+
+    /// Names of every enum defined at the top level of the parsed files, sorted and without
+    /// duplicates
+    pub fn all_enum_names(&self) -> Vec<String> {
+        self.item_names(|item| match item {
+            syn::Item::Enum(item_enum) => Some(&item_enum.ident),
+            _ => None,
+        })
+    }
+
+    /// Names of the top-level items `select` picks out of the parsed files
+    fn item_names(&self, select: impl Fn(&syn::Item) -> Option<&syn::Ident>) -> Vec<String> {
+        let names: BTreeSet<String> = self
+            .parsed_files
+            .iter()
+            .flat_map(|parsed_file| &parsed_file.syntax_tree.items)
+            .filter_map(|item| select(item).map(|ident| ident.to_string()))
+            .collect();
+        names.into_iter().collect()
+    }
+
     /// Resolve a type by name
     pub fn resolve_type(&mut self, type_name: &str) -> Option<ResolvedType> {
         debug!("Resolving type: {}", type_name);
@@ -159,6 +279,7 @@ impl TypeResolver {
             let placeholder = ResolvedType {
                 name: type_name.to_string(),
                 kind: TypeKind::Generic(format!("CircularRef<{}>", type_name)),
+                doc: None,
             };
             return Some(placeholder);
         }
@@ -171,6 +292,7 @@ impl TypeResolver {
             let resolved = ResolvedType {
                 name: type_name.to_string(),
                 kind: TypeKind::Primitive(primitive),
+                doc: None,
             };
             self.type_cache.insert(type_name.to_string(), resolved.clone());
             self.resolving_stack.remove(type_name);
@@ -219,10 +341,31 @@ impl TypeResolver {
         debug!("Parsing struct definition: {}", struct_name);
         
         let fields = self.parse_struct_fields(item_struct);
+        let generics = item_struct
+            .generics
+            .type_params()
+            .map(|param| param.ident.to_string())
+            .collect();
+        let (newtype, length) = match &item_struct.fields {
+            syn::Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                let field = &unnamed.unnamed[0];
+                let attrs: Vec<syn::Attribute> =
+                    item_struct.attrs.iter().chain(&field.attrs).cloned().collect();
+                (Some(Self::extract_type_info(&field.ty)), Self::length_limits(&attrs))
+            }
+            _ => (None, LengthLimits::default()),
+        };
         
         ResolvedType {
             name: struct_name,
-            kind: TypeKind::Struct(StructDef { fields }),
+            kind: TypeKind::Struct(StructDef {
+                fields,
+                generics,
+                unit: matches!(item_struct.fields, syn::Fields::Unit),
+                newtype,
+                length,
+            }),
+            doc: extract_doc_comment(&item_struct.attrs),
         }
     }
 
@@ -238,10 +381,218 @@ impl TypeResolver {
             .collect();
         
         debug!("Parsed {} variants", variants.len());
+        let variant_docs = item_enum
+            .variants
+            .iter()
+            .map(|v| extract_doc_comment(&v.attrs))
+            .collect();
+
+        // `#[serde(rename_all_fields = "...")]` renames the fields of every struct variant
+        // that are not renamed individually
+        let field_rule = Self::rename_all_fields(&item_enum.attrs);
+        let variant_fields = item_enum
+            .variants
+            .iter()
+            .map(|v| match &v.fields {
+                syn::Fields::Unit => VariantFields::Unit,
+                syn::Fields::Named(named) => VariantFields::Named(
+                    named
+                        .named
+                        .iter()
+                        .filter_map(|field| self.parse_field(field))
+                        .map(|mut field| {
+                            if field.serde_attrs.rename.is_none() {
+                                field.serde_attrs.rename = field_rule
+                                    .as_deref()
+                                    .and_then(|rule| rename_field(rule, &field.name));
+                            }
+                            field
+                        })
+                        .collect(),
+                ),
+                syn::Fields::Unnamed(unnamed) => VariantFields::Unnamed(
+                    unnamed
+                        .unnamed
+                        .iter()
+                        .map(|field| Self::extract_type_info(&field.ty))
+                        .collect(),
+                ),
+            })
+            .collect();
+
+        let discriminants = if Self::is_numeric_enum(item_enum) {
+            Self::enum_discriminants(item_enum)
+        } else {
+            None
+        };
         
         ResolvedType {
             name: enum_name,
-            kind: TypeKind::Enum(EnumDef { variants }),
+            kind: TypeKind::Enum(EnumDef {
+                variants,
+                variant_fields,
+                variant_docs,
+                discriminants,
+                representation: Self::enum_representation(&item_enum.attrs),
+            }),
+            doc: extract_doc_comment(&item_enum.attrs),
+        }
+    }
+
+    /// Read the enum representation from `#[serde(tag = "...", content = "...")]` and
+    /// `#[serde(untagged)]` container attributes
+    fn enum_representation(attrs: &[syn::Attribute]) -> EnumRepresentation {
+        let mut tag = None;
+        let mut content = None;
+        let mut untagged = false;
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("untagged") {
+                    untagged = true;
+                } else if meta.path.is_ident("tag") {
+                    tag = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("content") {
+                    content = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Skip other key-value pairs such as `rename_all`
+                    let _: syn::Expr = meta.value()?.parse()?;
+                } else if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                    // Skip nested lists such as `rename(serialize = "...")`
+                    meta.parse_nested_meta(|_| Ok(()))?;
+                }
+                Ok(())
+            });
+        }
+
+        match (untagged, tag, content) {
+            (true, _, _) => EnumRepresentation::Untagged,
+            (false, Some(tag), Some(content)) => EnumRepresentation::Adjacent { tag, content },
+            (false, Some(tag), None) => EnumRepresentation::Internal { tag },
+            (false, None, _) => EnumRepresentation::External,
+        }
+    }
+
+    /// Read the rule of a `#[serde(rename_all_fields = "...")]` container attribute
+    fn rename_all_fields(attrs: &[syn::Attribute]) -> Option<String> {
+        let mut rule = None;
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all_fields") {
+                    rule = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    let _: syn::Expr = meta.value()?.parse()?;
+                } else if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                    meta.parse_nested_meta(|_| Ok(()))?;
+                }
+                Ok(())
+            });
+        }
+        rule
+    }
+
+    /// Read the bounds of `#[validate(length(...))]` attributes.
+    ///
+    /// `equal` sets both bounds; bounds that are not integer literals (constants) are ignored.
+    fn length_limits(attrs: &[syn::Attribute]) -> LengthLimits {
+        fn skip(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+            if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| skip(&nested))?;
+            }
+            Ok(())
+        }
+
+        let mut limits = LengthLimits::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("validate")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("length") {
+                    return skip(&meta);
+                }
+                meta.parse_nested_meta(|bound| {
+                    let value: syn::Expr = bound.value()?.parse()?;
+                    let value = Self::int_literal(&value).and_then(|v| u64::try_from(v).ok());
+                    if bound.path.is_ident("min") {
+                        limits.min = value;
+                    } else if bound.path.is_ident("max") {
+                        limits.max = value;
+                    } else if bound.path.is_ident("equal") {
+                        limits.min = value;
+                        limits.max = value;
+                    }
+                    Ok(())
+                })
+            });
+        }
+        limits
+    }
+
+    /// Whether an enum serializes as its integer discriminant.
+    ///
+    /// This is the case for `serde_repr` derives, and is assumed for `#[repr(<int>)]` enums
+    /// that spell out at least one discriminant.
+    fn is_numeric_enum(item_enum: &syn::ItemEnum) -> bool {
+        const INT_REPRS: &[&str] = &[
+            "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        ];
+
+        let mut has_int_repr = false;
+        let mut has_serde_repr = false;
+        for attr in &item_enum.attrs {
+            if attr.path().is_ident("repr") {
+                let _ = attr.parse_nested_meta(|meta| {
+                    has_int_repr |= INT_REPRS.iter().any(|repr| meta.path.is_ident(repr));
+                    Ok(())
+                });
+            } else if attr.path().is_ident("derive") {
+                let _ = attr.parse_nested_meta(|meta| {
+                    has_serde_repr |= meta.path.segments.last().is_some_and(|s| {
+                        s.ident == "Serialize_repr" || s.ident == "Deserialize_repr"
+                    });
+                    Ok(())
+                });
+            }
+        }
+
+        let has_discriminant = item_enum.variants.iter().any(|v| v.discriminant.is_some());
+        has_serde_repr || (has_int_repr && has_discriminant)
+    }
+
+    /// Compute the integer value of every variant of a fieldless enum.
+    ///
+    /// Variants without an explicit discriminant take the previous value plus one, starting
+    /// at zero. Returns `None` if a variant has fields or a non-literal discriminant.
+    fn enum_discriminants(item_enum: &syn::ItemEnum) -> Option<Vec<i64>> {
+        let mut values = Vec::new();
+        let mut next = 0i64;
+        for variant in &item_enum.variants {
+            if !matches!(variant.fields, syn::Fields::Unit) {
+                return None;
+            }
+            let value = match &variant.discriminant {
+                Some((_, expr)) => Self::int_literal(expr)?,
+                None => next,
+            };
+            values.push(value);
+            next = value.checked_add(1)?;
+        }
+        Some(values)
+    }
+
+    /// Evaluate an integer literal expression, allowing a leading minus sign
+    fn int_literal(expr: &syn::Expr) -> Option<i64> {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) => int.base10_parse().ok(),
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => Self::int_literal(expr).map(|v| -v),
+            syn::Expr::Paren(paren) => Self::int_literal(&paren.expr),
+            _ => None,
         }
     }
 
@@ -269,12 +620,20 @@ impl TypeResolver {
         let type_info = Self::extract_type_info(&field.ty);
         let optional = type_info.is_option;
         let serde_attrs = Self::parse_serde_attributes(&field.attrs);
+        let description = parse_openapi_attributes(&field.attrs)
+            .param_description
+            .or_else(|| extract_doc_comment(&field.attrs));
+        let deprecated = field.attrs.iter().any(|attr| attr.path().is_ident("deprecated"));
+        let length = Self::length_limits(&field.attrs);
         
         Some(FieldDef {
             name: field_name,
             type_info,
             optional,
             serde_attrs,
+            description,
+            deprecated,
+            length,
         })
     }
 
@@ -283,6 +642,15 @@ impl TypeResolver {
         let mut serde_attrs = SerdeAttributes::default();
         
         for attr in attrs {
+            // Parse serde_with attribute: #[serde_as(as = "...")]
+            if attr.path().is_ident("serde_as") {
+                if let Some(value) = Self::extract_serde_as_value(attr) {
+                    debug!("Found serde_as: {}", value);
+                    serde_attrs.serde_as = Some(value);
+                }
+                continue;
+            }
+
             // Check if this is a serde attribute
             if !attr.path().is_ident("serde") {
                 continue;
@@ -299,8 +667,21 @@ impl TypeResolver {
                     serde_attrs.rename = Some(value);
                 }
                 
-                // Parse skip attribute: #[serde(skip)]
-                if tokens_str.contains("skip") && !tokens_str.contains("skip_serializing_if") {
+                // Flags are matched as whole words so that names such as
+                // `rename = "default_name"` or `skip_serializing_if` do not count
+                let has_flag = |flag: &str| {
+                    meta_list.tokens.clone().into_iter().any(|token| {
+                        matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == flag)
+                    })
+                };
+
+                // Parse skip attributes: #[serde(skip)], or a skip in one direction only
+                serde_attrs.skip_serializing |= has_flag("skip_serializing");
+                serde_attrs.skip_deserializing |= has_flag("skip_deserializing");
+                serde_attrs.skip_serializing_if |= has_flag("skip_serializing_if");
+                if has_flag("skip")
+                    || (serde_attrs.skip_serializing && serde_attrs.skip_deserializing)
+                {
                     debug!("Found serde skip");
                     serde_attrs.skip = true;
                 }
@@ -310,6 +691,12 @@ impl TypeResolver {
                     debug!("Found serde flatten");
                     serde_attrs.flatten = true;
                 }
+
+                // Parse default attribute: #[serde(default)] or #[serde(default = "path")]
+                if has_flag("default") {
+                    debug!("Found serde default");
+                    serde_attrs.default = true;
+                }
             }
         }
         
@@ -336,12 +723,36 @@ impl TypeResolver {
         None
     }
 
+    /// Extract the `as` type from a `#[serde_as(as = "...")]` attribute
+    fn extract_serde_as_value(attr: &syn::Attribute) -> Option<String> {
+        let mut value = None;
+        if let syn::Meta::List(_) = &attr.meta {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("as") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    value = Some(lit.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Skip other key-value pairs such as `serialize_as`
+                    let _: syn::LitStr = meta.value()?.parse()?;
+                }
+                Ok(())
+            });
+        }
+        value
+    }
+
     /// Extract TypeInfo from a syn::Type
     fn extract_type_info(ty: &syn::Type) -> TypeInfo {
         match ty {
             syn::Type::Path(type_path) => {
                 Self::extract_type_info_from_path(&type_path.path)
             }
+            // `&'static str` and `(T)` describe the same wire type as `str` and `T`
+            syn::Type::Reference(type_ref) => Self::extract_type_info(&type_ref.elem),
+            syn::Type::Paren(paren) => Self::extract_type_info(&paren.elem),
+            syn::Type::TraitObject(trait_object) => {
+                TypeInfo::trait_object(bound_trait_name(&trait_object.bounds))
+            }
             _ => {
                 // For other types, use a generic placeholder
                 TypeInfo::new("Unknown".to_string())
@@ -349,7 +760,11 @@ impl TypeResolver {
         }
     }
 
-    /// Extract TypeInfo from a syn::Path
+    /// Extract TypeInfo from a syn::Path.
+    ///
+    /// Only the last segment is significant, so fully-qualified forms such as
+    /// `std::string::String`, `core::primitive::u32` or `std::collections::HashMap<K, V>`
+    /// behave exactly like their bare names.
     fn extract_type_info_from_path(path: &syn::Path) -> TypeInfo {
         if let Some(segment) = path.segments.last() {
             let type_name = segment.ident.to_string();
@@ -374,6 +789,32 @@ impl TypeResolver {
                 }
             }
             
+            // `Box<T>`, `Arc<T>`, `Rc<T>` and `Cow<'a, T>` serialize as `T`
+            if TRANSPARENT_WRAPPERS.contains(&type_name.as_str()) {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    let inner_ty = args.args.iter().find_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    });
+                    if let Some(inner_ty) = inner_ty {
+                        return Self::extract_type_info(inner_ty);
+                    }
+                }
+            }
+            
+            // Check for HashMap<K, V> and other maps
+            if MAP_TYPES.contains(&type_name.as_str()) {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    let mut types = args.args.iter().filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(Self::extract_type_info(ty)),
+                        _ => None,
+                    });
+                    if let (Some(key), Some(value)) = (types.next(), types.next()) {
+                        return TypeInfo::map(type_name, key, value);
+                    }
+                }
+            }
+            
             // Handle generic types
             if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
                 let mut generic_args = Vec::new();
@@ -389,6 +830,10 @@ impl TypeResolver {
                     generic_args,
                     is_option: false,
                     is_vec: false,
+                    is_result: false,
+                    is_impl_trait: false,
+                    is_trait_object: false,
+                    is_map: false,
                 };
             }
             
@@ -413,6 +858,8 @@ impl TypeResolver {
             "u32" => Some(PrimitiveType::U32),
             "u64" => Some(PrimitiveType::U64),
             "u128" => Some(PrimitiveType::U128),
+            "isize" => Some(PrimitiveType::Isize),
+            "usize" => Some(PrimitiveType::Usize),
             "f32" => Some(PrimitiveType::F32),
             "f64" => Some(PrimitiveType::F64),
             "bool" => Some(PrimitiveType::Bool),
@@ -422,6 +869,41 @@ impl TypeResolver {
     }
 }
 
+/// Apply a serde `rename_all` style rule (`camelCase`, `kebab-case`, ...) to a snake_case
+/// field name, as serde does; `None` for unknown rules
+pub fn rename_field(rule: &str, field: &str) -> Option<String> {
+    let pascal = || {
+        field
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_uppercase().to_string() + chars.as_str()
+                })
+            })
+            .collect::<String>()
+    };
+    let renamed = match rule {
+        "lowercase" | "snake_case" => field.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_lowercase().to_string() + chars.as_str()
+            })
+        }
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.to_ascii_uppercase().replace('_', "-"),
+        _ => {
+            warn!("Ignoring unknown serde rename rule: {}", rule);
+            return None;
+        }
+    };
+    Some(renamed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -457,6 +939,8 @@ mod tests {
             ("String", PrimitiveType::String),
             ("i32", PrimitiveType::I32),
             ("u64", PrimitiveType::U64),
+            ("usize", PrimitiveType::Usize),
+            ("isize", PrimitiveType::Isize),
             ("f32", PrimitiveType::F32),
             ("bool", PrimitiveType::Bool),
         ];
@@ -605,6 +1089,14 @@ mod tests {
                 pub id: u32,
                 #[serde(skip)]
                 pub password: String,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                pub nickname: Option<String>,
+                #[serde(skip_serializing)]
+                pub new_password: String,
+                #[serde(skip_deserializing, default)]
+                pub created_at: String,
+                #[serde(skip_serializing, skip_deserializing)]
+                pub cache: u32,
             }
         "#;
         
@@ -615,9 +1107,31 @@ mod tests {
         let resolved = resolved.unwrap();
         
         if let TypeKind::Struct(struct_def) = resolved.kind {
-            let password_field = &struct_def.fields[1];
-            assert_eq!(password_field.name, "password");
-            assert!(password_field.serde_attrs.skip);
+            // skip, skip_serializing, skip_deserializing, skip_serializing_if
+            let flags: Vec<(&str, bool, bool, bool, bool)> = struct_def.fields[1..]
+                .iter()
+                .map(|f| {
+                    let serde = &f.serde_attrs;
+                    (
+                        f.name.as_str(),
+                        serde.skip,
+                        serde.skip_serializing,
+                        serde.skip_deserializing,
+                        serde.skip_serializing_if,
+                    )
+                })
+                .collect();
+            assert_eq!(
+                flags,
+                vec![
+                    ("password", true, false, false, false),
+                    ("nickname", false, false, false, true),
+                    ("new_password", false, true, false, false),
+                    ("created_at", false, false, true, false),
+                    ("cache", true, true, true, false),
+                ]
+            );
+            assert!(struct_def.fields[4].serde_attrs.default);
         } else {
             panic!("Expected struct type");
         }
@@ -655,6 +1169,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_serde_as() {
+        let code = r#"
+            use serde_with::{serde_as, DisplayFromStr};
+            
+            #[serde_as]
+            #[derive(Serialize, Deserialize)]
+            pub struct Account {
+                #[serde_as(as = "DisplayFromStr")]
+                pub balance: u64,
+                pub name: String,
+            }
+        "#;
+        
+        let mut resolver = create_resolver_from_code(code);
+        let resolved = resolver.resolve_type("Account").unwrap();
+        
+        if let TypeKind::Struct(struct_def) = resolved.kind {
+            assert_eq!(
+                struct_def.fields[0].serde_attrs.serde_as,
+                Some("DisplayFromStr".to_string())
+            );
+            assert_eq!(struct_def.fields[1].serde_attrs.serde_as, None);
+        } else {
+            panic!("Expected struct type");
+        }
+    }
+
     #[test]
     fn test_resolve_nested_struct() {
         let code = r#"
@@ -693,8 +1235,11 @@ mod tests {
     fn test_resolve_enum() {
         let code = r#"
             pub enum Status {
+                /// Currently in use
                 Active,
                 Inactive,
+                /// Awaiting review
+                /// by an admin
                 Pending,
             }
         "#;
@@ -711,11 +1256,93 @@ mod tests {
             assert_eq!(enum_def.variants[0], "Active");
             assert_eq!(enum_def.variants[1], "Inactive");
             assert_eq!(enum_def.variants[2], "Pending");
+            assert_eq!(
+                enum_def.variant_docs,
+                vec![
+                    Some("Currently in use".to_string()),
+                    None,
+                    Some("Awaiting review\nby an admin".to_string()),
+                ]
+            );
         } else {
             panic!("Expected enum type");
         }
     }
 
+    #[test]
+    fn test_resolve_enum_variant_fields() {
+        let code = r#"
+            pub enum Shape {
+                Circle { radius: f64 },
+                Rect(f64, f64),
+                Empty,
+            }
+        "#;
+
+        let mut resolver = create_resolver_from_code(code);
+        let resolved = resolver.resolve_type("Shape").unwrap();
+
+        let TypeKind::Enum(enum_def) = resolved.kind else {
+            panic!("Expected enum type");
+        };
+        assert!(enum_def.has_data());
+        match &enum_def.variant_fields[0] {
+            VariantFields::Named(fields) => {
+                assert_eq!(fields.len(), 1);
+                assert_eq!(fields[0].name, "radius");
+            }
+            other => panic!("Expected struct variant, got {:?}", other),
+        }
+        match &enum_def.variant_fields[1] {
+            VariantFields::Unnamed(types) => assert_eq!(types.len(), 2),
+            other => panic!("Expected tuple variant, got {:?}", other),
+        }
+        assert!(matches!(enum_def.variant_fields[2], VariantFields::Unit));
+    }
+
+    #[test]
+    fn test_resolve_numeric_enum_discriminants() {
+        let code = r#"
+            #[repr(u8)]
+            pub enum Priority {
+                Low = 1,
+                Medium,
+                High = 10,
+            }
+
+            #[derive(Serialize_repr)]
+            #[repr(i32)]
+            pub enum Level {
+                Debug = -1,
+                Info,
+            }
+
+            #[repr(u8)]
+            pub enum Implicit {
+                A,
+                B,
+            }
+
+            pub enum Named {
+                A = 1,
+                B = 2,
+            }
+        "#;
+
+        let mut resolver = create_resolver_from_code(code);
+        let discriminants = |resolver: &mut TypeResolver, name: &str| {
+            match resolver.resolve_type(name).unwrap().kind {
+                TypeKind::Enum(enum_def) => enum_def.discriminants,
+                _ => panic!("Expected enum type"),
+            }
+        };
+
+        assert_eq!(discriminants(&mut resolver, "Priority"), Some(vec![1, 2, 10]));
+        assert_eq!(discriminants(&mut resolver, "Level"), Some(vec![-1, 0]));
+        assert_eq!(discriminants(&mut resolver, "Implicit"), None);
+        assert_eq!(discriminants(&mut resolver, "Named"), None);
+    }
+
     #[test]
     fn test_type_caching() {
         let code = r#"
@@ -805,6 +1432,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fully_qualified_field_types() {
+        let code = r#"
+            pub struct Record {
+                pub name: std::string::String,
+                pub count: core::primitive::u32,
+                pub tags: std::vec::Vec<alloc::string::String>,
+                pub labels: std::collections::BTreeMap<String, String>,
+                pub slug: &'static str,
+            }
+        "#;
+
+        let mut resolver = create_resolver_from_code(code);
+        let resolved = resolver.resolve_type("Record").unwrap();
+
+        let TypeKind::Struct(struct_def) = resolved.kind else {
+            panic!("Expected struct type");
+        };
+        let types: Vec<&TypeInfo> = struct_def.fields.iter().map(|f| &f.type_info).collect();
+        assert_eq!(types[0], &TypeInfo::new("String".to_string()));
+        assert_eq!(types[1], &TypeInfo::new("u32".to_string()));
+        assert_eq!(
+            types[2],
+            &TypeInfo::vec(TypeInfo::new("String".to_string()))
+        );
+        assert!(types[3].is_map);
+        assert_eq!(types[3].name, "BTreeMap");
+        assert_eq!(types[4], &TypeInfo::new("str".to_string()));
+    }
+
+    #[test]
+    fn test_rename_field_rules() {
+        let rename = |rule| rename_field(rule, "created_at_utc").unwrap();
+        assert_eq!(rename("camelCase"), "createdAtUtc");
+        assert_eq!(rename("PascalCase"), "CreatedAtUtc");
+        assert_eq!(rename("snake_case"), "created_at_utc");
+        assert_eq!(rename("SCREAMING_SNAKE_CASE"), "CREATED_AT_UTC");
+        assert_eq!(rename("kebab-case"), "created-at-utc");
+        assert_eq!(rename("SCREAMING-KEBAB-CASE"), "CREATED-AT-UTC");
+        assert_eq!(rename_field("Title Case", "id"), None);
+    }
+
     #[test]
     fn test_complex_generic_types() {
         let code = r#"
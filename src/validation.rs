@@ -0,0 +1,199 @@
+//! Consistency checks between route paths and their handlers.
+//!
+//! A route such as `/users/:id` whose handler never extracts `id`, or a `Path<T>` struct with
+//! fields that do not appear in the URL, compiles fine but fails (or silently misbehaves) at
+//! runtime, and produces a spec that does not match the implementation. These checks compare
+//! the parameters derived from the URL with the ones consumed by the handler's `Path<T>`
+//! extractor and record a [`DiagnosticKind::PathParameterMismatch`] for every discrepancy.
+
+use crate::diagnostics::{DiagnosticKind, Diagnostics};
+use crate::extractor::{ParameterLocation, RouteInfo, TypeInfo, PATH_EXTRACTOR_PARAM};
+use crate::type_resolver::{TypeKind, TypeResolver};
+use log::warn;
+
+/// Cross-check URL path parameters against the parameters extracted by each route's handler.
+///
+/// Routes whose `Path<T>` type cannot be inspected (tuples, unresolved types) are assumed to
+/// consume every URL parameter.
+pub fn check_path_parameters(
+    routes: &[RouteInfo],
+    type_resolver: &mut TypeResolver,
+    diagnostics: &mut Diagnostics,
+) {
+    // Services such as `ServeDir` have no handler extracting the URL parameters
+    for route in routes.iter().filter(|route| !route.serves_files) {
+        for message in path_parameter_mismatches(route, type_resolver) {
+            warn!("{}", message);
+            diagnostics.push(DiagnosticKind::PathParameterMismatch, message);
+        }
+    }
+}
+
+/// Describe every mismatch between the URL and the handler of a single route
+fn path_parameter_mismatches(route: &RouteInfo, type_resolver: &mut TypeResolver) -> Vec<String> {
+    let location = format!("{} {}", route.method_str(), route.path);
+    let path_params = route
+        .parameters
+        .iter()
+        .filter(|p| p.location == ParameterLocation::Path);
+    let (extractors, url_params): (Vec<_>, Vec<_>) =
+        path_params.partition(|p| p.name == PATH_EXTRACTOR_PARAM);
+    let url_names: Vec<&str> = url_params.iter().map(|p| p.name.as_str()).collect();
+
+    let mut mismatches = Vec::new();
+    if extractors.is_empty() {
+        for name in &url_names {
+            mismatches.push(format!(
+                "{}: URL parameter `{}` is not extracted by handler `{}`",
+                location, name, route.handler_name
+            ));
+        }
+        return mismatches;
+    }
+
+    for extractor in extractors {
+        match extracted_names(&extractor.type_info, type_resolver) {
+            Extracted::Fields(fields) => {
+                for field in fields.iter().filter(|f| !url_names.contains(&f.as_str())) {
+                    mismatches.push(format!(
+                        "{}: field `{}` of `Path<{}>` in handler `{}` is not in the URL",
+                        location, field, extractor.type_info.name, route.handler_name
+                    ));
+                }
+                for name in url_names.iter().filter(|n| !fields.iter().any(|f| f == *n)) {
+                    mismatches.push(format!(
+                        "{}: URL parameter `{}` is not extracted by handler `{}`",
+                        location, name, route.handler_name
+                    ));
+                }
+            }
+            Extracted::Single if url_names.len() != 1 => {
+                mismatches.push(format!(
+                    "{}: handler `{}` extracts a single path parameter but the URL declares {}",
+                    location,
+                    route.handler_name,
+                    url_names.len()
+                ));
+            }
+            Extracted::Single | Extracted::Unknown => {}
+        }
+    }
+
+    mismatches
+}
+
+/// What a `Path<T>` extractor consumes from the URL
+enum Extracted {
+    /// A struct deserialized by field name
+    Fields(Vec<String>),
+    /// A single value: a primitive, or a newtype wrapping one
+    Single,
+    /// A type that could not be inspected
+    Unknown,
+}
+
+/// Determine the URL parameters consumed by a `Path<T>` extractor of the given type
+fn extracted_names(type_info: &TypeInfo, type_resolver: &mut TypeResolver) -> Extracted {
+    match type_resolver.resolve_type(&type_info.name).map(|r| r.kind) {
+        Some(TypeKind::Struct(def)) if def.newtype.is_some() => Extracted::Single,
+        Some(TypeKind::Struct(def)) => Extracted::Fields(
+            def.fields
+                .into_iter()
+                .filter(|f| !f.serde_attrs.skip && !f.serde_attrs.skip_deserializing)
+                .map(|f| f.serde_attrs.rename.unwrap_or(f.name))
+                .collect(),
+        ),
+        Some(TypeKind::Primitive(_)) => Extracted::Single,
+        _ => Extracted::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::axum::AxumExtractor;
+    use crate::extractor::RouteExtractor;
+    use crate::parser::ParsedFile;
+    use std::path::PathBuf;
+
+    /// Extract Axum routes from `code` and run the path checks on them
+    fn check(code: &str) -> Vec<String> {
+        let parsed = ParsedFile {
+            path: PathBuf::from("test.rs"),
+            syntax_tree: syn::parse_file(code).unwrap(),
+        };
+        let routes = AxumExtractor.extract_routes(std::slice::from_ref(&parsed));
+        let mut resolver = TypeResolver::new(vec![parsed]);
+        let mut diagnostics = Diagnostics::new();
+        check_path_parameters(&routes, &mut resolver, &mut diagnostics);
+        diagnostics.iter().map(|d| d.message.clone()).collect()
+    }
+
+    #[test]
+    fn test_matching_path_parameters() {
+        let messages = check(
+            r#"
+            use axum::{Router, routing::get, extract::Path};
+
+            pub struct PostPath {
+                pub user_id: u32,
+                #[serde(rename = "post_id")]
+                pub id: u32,
+            }
+
+            pub struct OrderId(u64);
+
+            async fn get_user(Path(id): Path<u32>) {}
+            async fn get_post(Path(path): Path<PostPath>) {}
+            async fn get_order(Path(id): Path<OrderId>) {}
+            async fn list_users() {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users", get(list_users))
+                    .route("/users/:id", get(get_user))
+                    .route("/users/:user_id/posts/:post_id", get(get_post))
+                    .route("/orders/:id", get(get_order))
+            }
+        "#,
+        );
+
+        assert!(messages.is_empty(), "{:?}", messages);
+    }
+
+    #[test]
+    fn test_mismatched_path_parameters() {
+        let mut messages = check(
+            r#"
+            use axum::{Router, routing::get, extract::Path};
+
+            pub struct PostPath {
+                pub user_id: u32,
+                pub slug: String,
+            }
+
+            async fn get_user() {}
+            async fn get_post(Path(path): Path<PostPath>) {}
+            async fn get_comment(Path(id): Path<u32>) {}
+
+            fn app() -> Router {
+                Router::new()
+                    .route("/users/:id", get(get_user))
+                    .route("/users/:user_id/posts/:post_id", get(get_post))
+                    .route("/posts/:post_id/comments/:id", get(get_comment))
+            }
+        "#,
+        );
+
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "GET /posts/:post_id/comments/:id: handler `get_comment` extracts a single path parameter but the URL declares 2",
+                "GET /users/:id: URL parameter `id` is not extracted by handler `get_user`",
+                "GET /users/:user_id/posts/:post_id: URL parameter `post_id` is not extracted by handler `get_post`",
+                "GET /users/:user_id/posts/:post_id: field `slug` of `Path<PostPath>` in handler `get_post` is not in the URL",
+            ]
+        );
+    }
+}
@@ -0,0 +1,519 @@
+// Tests for command-line workflow options
+use clap::Parser;
+use openapi_from_source::cli::{self, CliArgs};
+use openapi_from_source::serializer::load_document;
+use tempfile::TempDir;
+
+/// Helper function to create a temporary test project
+fn create_test_project(files: Vec<(&str, &str)>) -> TempDir {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+
+    for (path, content) in files {
+        let file_path = temp_dir.path().join(path);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent).expect("Failed to create parent directories");
+        }
+        std::fs::write(&file_path, content).expect("Failed to write test file");
+    }
+
+    temp_dir
+}
+
+/// Helper function to build CLI arguments for a project, writing output into the project dir
+fn args_for(project: &TempDir, extra: &[&str]) -> CliArgs {
+    let project_path = project.path().to_string_lossy().to_string();
+    let output_path = project.path().join("openapi.yaml").to_string_lossy().to_string();
+    let mut argv = vec!["openapi-from-source", project_path.as_str(), "-o", output_path.as_str()];
+    argv.extend_from_slice(extra);
+    CliArgs::parse_from(argv)
+}
+
+const COMPLETE_PROJECT: &str = r#"
+    use axum::{Router, routing::get, Json};
+
+    pub struct User {
+        pub id: u32,
+        pub name: String,
+    }
+
+    pub async fn get_user() -> Json<User> {
+        todo!()
+    }
+
+    pub fn app() -> Router {
+        Router::new().route("/user", get(get_user))
+    }
+"#;
+
+const INCOMPLETE_PROJECT: &str = r#"
+    use axum::{Router, routing::get};
+
+    pub fn app() -> Router {
+        Router::new().route("/missing", get(missing_handler))
+    }
+"#;
+
+#[test]
+fn test_fail_on_warnings_passes_for_complete_project() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+    let args = args_for(&project, &["--fail-on-warnings"]);
+
+    assert!(cli::run(args).is_ok());
+}
+
+#[test]
+fn test_fail_on_warnings_fails_for_unresolved_handler() {
+    let project = create_test_project(vec![("src/main.rs", INCOMPLETE_PROJECT)]);
+    let args = args_for(&project, &["--fail-on-warnings"]);
+
+    let result = cli::run(args);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("(1 unresolved handler)"));
+
+    // The document is still written before failing
+    assert!(project.path().join("openapi.yaml").exists());
+}
+
+#[test]
+fn test_fail_on_warnings_fails_for_parse_failure() {
+    let project = create_test_project(vec![
+        ("src/main.rs", COMPLETE_PROJECT),
+        ("src/broken.rs", "pub fn broken( {"),
+    ]);
+    let args = args_for(&project, &["--fail-on-warnings"]);
+
+    let result = cli::run(args);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("(1 parse failure)"));
+}
+
+#[test]
+fn test_fail_on_warnings_fails_for_scan_limits() {
+    let project = create_test_project(vec![
+        ("src/main.rs", COMPLETE_PROJECT),
+        ("src/handlers/users.rs", COMPLETE_PROJECT),
+    ]);
+
+    let result = cli::run(args_for(&project, &["--max-files", "1", "--fail-on-warnings"]));
+    assert!(result.unwrap_err().to_string().contains("(1 scan limit)"));
+    let result = cli::run(args_for(&project, &["--max-depth", "1", "--fail-on-warnings"]));
+    assert!(result.unwrap_err().to_string().contains("(1 scan limit)"));
+    assert!(cli::run(args_for(&project, &["--max-depth", "2", "--fail-on-warnings"])).is_ok());
+}
+
+#[test]
+fn test_warnings_ignored_without_flag() {
+    let project = create_test_project(vec![("src/main.rs", INCOMPLETE_PROJECT)]);
+    let args = args_for(&project, &[]);
+
+    assert!(cli::run(args).is_ok());
+}
+
+#[test]
+fn test_diff_detects_added_route() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+    cli::run(args_for(&project, &[])).expect("Initial generation failed");
+
+    let baseline = project.path().join("baseline.yaml");
+    std::fs::rename(project.path().join("openapi.yaml"), &baseline).unwrap();
+    let baseline_arg = baseline.to_string_lossy().to_string();
+
+    // Unchanged source produces no differences
+    assert!(cli::run(args_for(&project, &["--diff", &baseline_arg])).is_ok());
+
+    // Adding a route is reported as a difference
+    let changed = COMPLETE_PROJECT.replace(
+        r#".route("/user", get(get_user))"#,
+        r#".route("/user", get(get_user)).route("/user2", get(get_user))"#,
+    );
+    std::fs::write(project.path().join("src/main.rs"), changed).unwrap();
+
+    let result = cli::run(args_for(&project, &["--diff", &baseline_arg]));
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("differs from"));
+
+    // Report-only mode never fails
+    assert!(cli::run(args_for(&project, &["--diff", &baseline_arg, "--diff-report-only"])).is_ok());
+}
+
+#[test]
+fn test_diff_against_output_file() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+    cli::run(args_for(&project, &[])).expect("Initial generation failed");
+    let output_arg = project.path().join("openapi.yaml").to_string_lossy().to_string();
+
+    // The output file is read as the baseline before it is overwritten
+    let changed = COMPLETE_PROJECT.replace(
+        r#".route("/user", get(get_user))"#,
+        r#".route("/user", get(get_user)).route("/user2", get(get_user))"#,
+    );
+    std::fs::write(project.path().join("src/main.rs"), changed).unwrap();
+
+    let result = cli::run(args_for(&project, &["--diff", &output_arg]));
+    assert!(result.unwrap_err().to_string().contains("differs from"));
+    assert!(cli::run(args_for(&project, &["--diff", &output_arg])).is_ok());
+}
+
+#[test]
+fn test_private_handlers_excluded_with_public_only() {
+    let project = create_test_project(vec![(
+        "src/main.rs",
+        r#"
+        use axum::{Router, routing::get};
+
+        pub async fn list_users() {}
+        async fn internal_stats() {}
+
+        pub fn app() -> Router {
+            Router::new()
+                .route("/users", get(list_users))
+                .route("/internal/stats", get(internal_stats))
+        }
+    "#,
+    )]);
+    let output = project.path().join("openapi.yaml");
+
+    cli::run(args_for(&project, &[])).expect("Generation failed");
+    let yaml = std::fs::read_to_string(&output).unwrap();
+    assert!(yaml.contains("/users"));
+    assert!(yaml.contains("/internal/stats"));
+
+    cli::run(args_for(&project, &["--public-only"])).expect("Generation failed");
+    let yaml = std::fs::read_to_string(&output).unwrap();
+    assert!(yaml.contains("/users"));
+    assert!(!yaml.contains("/internal/stats"));
+}
+
+#[test]
+fn test_annotate_keeps_writing_output_file() {
+    let project = create_test_project(vec![("src/main.rs", INCOMPLETE_PROJECT)]);
+
+    cli::run(args_for(&project, &["--annotate"])).expect("Annotation failed");
+    assert!(project.path().join("openapi.yaml").exists());
+}
+
+#[test]
+fn test_merge_into_base_spec() {
+    let project = create_test_project(vec![
+        ("src/main.rs", COMPLETE_PROJECT),
+        (
+            "base.yaml",
+            "openapi: 3.0.3\ninfo:\n  title: Petstore\n  version: 2.0.0\nservers:\n  - url: https://api.example.com\npaths: {}\n",
+        ),
+    ]);
+    let base = project.path().join("base.yaml").to_string_lossy().to_string();
+    let output = project.path().join("openapi.yaml");
+
+    cli::run(args_for(&project, &["--merge", &base])).expect("Generation failed");
+    let yaml = std::fs::read_to_string(&output).unwrap();
+    assert!(yaml.contains("title: Petstore"));
+    assert!(yaml.contains("https://api.example.com"));
+    assert!(yaml.contains("/user"));
+
+    cli::run(args_for(&project, &["--merge", &base, "--overwrite-info"])).expect("Generation failed");
+    let yaml = std::fs::read_to_string(&output).unwrap();
+    assert!(yaml.contains("title: Generated API"));
+    assert!(yaml.contains("https://api.example.com"));
+}
+
+#[test]
+fn test_default_status_and_description() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+    let args = args_for(
+        &project,
+        &["--default-status", "get=202", "--response-description", "202=Accepted"],
+    );
+
+    cli::run(args).expect("Generation failed");
+    let yaml = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    assert!(yaml.contains("'202':"));
+    assert!(yaml.contains("description: Accepted"));
+
+    let invalid =
+        CliArgs::try_parse_from(["openapi-from-source", ".", "--default-status", "get=2000"]);
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_yaml_document_start_marker() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+    let output = project.path().join("openapi.yaml");
+
+    cli::run(args_for(&project, &[])).expect("Generation failed");
+    assert!(std::fs::read_to_string(&output).unwrap().starts_with("---\n"));
+
+    cli::run(args_for(&project, &["--no-doc-start"])).expect("Generation failed");
+    assert!(std::fs::read_to_string(&output).unwrap().starts_with("openapi:"));
+}
+
+#[test]
+fn test_shared_example() {
+    let project = create_test_project(vec![
+        (
+            "src/main.rs",
+            r#"
+    use axum::{Router, routing::post, Json};
+
+    pub struct NewUser {
+        pub name: String,
+    }
+
+    #[openapi(request_example = "ada")]
+    pub async fn create_user(Json(user): Json<NewUser>) {}
+
+    pub fn app() -> Router {
+        Router::new().route("/users", post(create_user))
+    }
+"#,
+        ),
+        ("examples/ada.json", r#"{"name": "Ada"}"#),
+    ]);
+    let example = project.path().join("examples/ada.json").to_string_lossy().to_string();
+    let example_arg = format!("ada={}", example);
+
+    cli::run(args_for(&project, &["--example", &example_arg])).expect("Generation failed");
+    let yaml = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    assert!(yaml.contains("$ref: '#/components/examples/ada'"));
+    assert!(yaml.contains("examples:\n    ada:\n      value:\n        name: Ada"));
+
+    let invalid = CliArgs::try_parse_from(["openapi-from-source", ".", "--example", "ada"]);
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_generation_report() {
+    let project = create_test_project(vec![
+        ("src/main.rs", COMPLETE_PROJECT),
+        ("src/incomplete.rs", INCOMPLETE_PROJECT),
+    ]);
+    let report_path = project.path().join("report.json");
+    let report_arg = report_path.to_string_lossy().to_string();
+
+    cli::run(args_for(&project, &["--report", &report_arg])).expect("Generation failed");
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+
+    assert_eq!(report["files"]["scanned"], 2);
+    assert_eq!(report["files"]["parsed"], 2);
+    assert_eq!(report["frameworks"], serde_json::json!(["axum"]));
+    assert_eq!(report["routes"].as_array().unwrap().len(), 2);
+    assert_eq!(report["unresolved_handlers"], serde_json::json!(["missing_handler"]));
+    assert_eq!(report["diagnostics"][0]["kind"], "unresolved_handler");
+}
+
+#[test]
+fn test_include_handler_source() {
+    let project = create_test_project(vec![(
+        "src/main.rs",
+        r#"
+    use axum::{Router, routing::get, Json, extract::Path};
+
+    pub struct User {
+        pub id: u32,
+    }
+
+    /// Look up a user
+    pub async fn get_user(
+        Path(id): Path<u32>,
+    ) -> Json<User> {
+        todo!()
+    }
+
+    pub fn app() -> Router {
+        Router::new().route("/users/:id", get(get_user))
+    }
+"#,
+    )]);
+
+    cli::run(args_for(&project, &["--include-handler-source", "--format", "json"]))
+        .expect("Generation failed");
+    let content = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let operation = &document["paths"]["/users/{id}"]["get"];
+    assert_eq!(operation["x-source-location"], "src/main.rs:9");
+    assert_eq!(
+        operation["x-source-snippet"],
+        "async fn get_user(\n    Path(id): Path<u32>,\n) -> Json<User>"
+    );
+
+    // Without the flag no source is attached
+    cli::run(args_for(&project, &[])).expect("Generation failed");
+    let yaml = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    assert!(!yaml.contains("x-source"));
+}
+
+#[test]
+fn test_all_schemas() {
+    let project = create_test_project(vec![
+        ("src/main.rs", COMPLETE_PROJECT),
+        ("src/models.rs", "pub struct AuditEntry {\n    pub message: String,\n}\n"),
+    ]);
+
+    cli::run(args_for(&project, &["--format", "json"])).expect("Generation failed");
+    let content = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(document["components"]["schemas"].get("AuditEntry").is_none());
+
+    cli::run(args_for(&project, &["--all-schemas", "--format", "json"]))
+        .expect("Generation failed");
+    let content = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(
+        document["components"]["schemas"]["AuditEntry"]["properties"]["message"]["type"],
+        "string"
+    );
+    assert!(document["components"]["schemas"].get("User").is_some());
+}
+
+#[test]
+fn test_exclude_schema() {
+    let project = create_test_project(vec![
+        ("src/main.rs", COMPLETE_PROJECT),
+        ("src/models.rs", "pub struct AuditEntry {\n    pub message: String,\n}\n"),
+    ]);
+
+    let args = args_for(
+        &project,
+        &["--all-schemas", "--exclude-schema", "AuditEntry", "--format", "json"],
+    );
+    cli::run(args).expect("Generation failed");
+    let content = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(document["components"]["schemas"].get("AuditEntry").is_none());
+    assert!(document["components"]["schemas"].get("User").is_some());
+}
+
+#[test]
+fn test_ref_prefix() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+
+    let args = args_for(&project, &["--ref-prefix", "#/definitions/", "--format", "json"]);
+    cli::run(args).expect("Generation failed");
+    let content = std::fs::read_to_string(project.path().join("openapi.yaml")).unwrap();
+    assert!(content.contains("\"#/definitions/User\""));
+    assert!(!content.contains("#/components/schemas/"));
+    // Referenced schemas are not pruned as unused, and are listed where the prefix points
+    let document: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(document["definitions"].get("User").is_some());
+    assert!(document.get("components").is_none());
+    assert_eq!(dangling_refs(&document, &document), Vec::<String>::new());
+
+    for unresolvable in ["definitions/", "#/components/responses/", "#/paths/", "#/a/b/"] {
+        let args = ["openapi-from-source", ".", "--ref-prefix", unresolvable];
+        assert!(CliArgs::try_parse_from(args).is_err(), "{}", unresolvable);
+    }
+}
+
+/// The `$ref`s in `value` that do not point to a part of `document`
+fn dangling_refs(document: &serde_json::Value, value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .flat_map(|(key, field)| match field.as_str() {
+                Some(reference) if key == "$ref" => reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| document.pointer(pointer))
+                    .is_none()
+                    .then(|| reference.to_string())
+                    .into_iter()
+                    .collect(),
+                _ => dangling_refs(document, field),
+            })
+            .collect(),
+        serde_json::Value::Array(items) => {
+            items.iter().flat_map(|item| dangling_refs(document, item)).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[test]
+fn test_stamp() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+    let output = project.path().join("openapi.yaml");
+
+    cli::run(args_for(&project, &["--stamp"])).expect("Generation failed");
+    let document = load_document(&output).unwrap();
+    let stamp = &document.extensions["x-generated"];
+    assert_eq!(stamp["generator"], "openapi-from-source");
+    assert_eq!(stamp["version"], env!("CARGO_PKG_VERSION"));
+    // `2024-10-23T08:15:00Z`; the time itself is covered by the unit tests of the stamp
+    let timestamp = stamp["timestamp"].as_str().unwrap();
+    assert_eq!((timestamp.len(), &timestamp[10..11], &timestamp[19..]), (20, "T", "Z"));
+    assert_eq!(stamp["frameworks"], serde_json::json!(["axum"]));
+    if let Some(commit) = stamp.get("commit") {
+        assert!(commit.as_str().unwrap().chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    cli::run(args_for(&project, &[])).expect("Generation failed");
+    assert!(!load_document(&output).unwrap().extensions.contains_key("x-generated"));
+}
+
+#[test]
+fn test_servers() {
+    let project = create_test_project(vec![("src/main.rs", COMPLETE_PROJECT)]);
+    let output = project.path().join("openapi.yaml");
+    std::env::set_var("OPENAPI_TEST_BASE_URL", "https://api.example.com");
+
+    let args = args_for(
+        &project,
+        &[
+            "--base-url-from-env",
+            "OPENAPI_TEST_BASE_URL",
+            "--server",
+            "https://{env}.example.com/{version}",
+            "--server-variable",
+            "env=prod|staging",
+            "--server-variable",
+            "version=v1",
+        ],
+    );
+    cli::run(args).expect("Generation failed");
+    let servers = load_document(&output).unwrap().servers.unwrap();
+    assert_eq!(servers.len(), 2);
+    assert_eq!(servers[0].url, "https://api.example.com");
+    assert!(servers[0].variables.is_none());
+    let variables = servers[1].variables.as_ref().unwrap();
+    assert_eq!(variables["env"].default, "prod");
+    assert_eq!(
+        variables["env"].enum_values,
+        Some(vec!["prod".to_string(), "staging".to_string()])
+    );
+    assert_eq!(variables["version"].default, "v1");
+    assert!(variables["version"].enum_values.is_none());
+
+    let args = args_for(&project, &["--server", "https://{region}.example.com"]);
+    let error = cli::run(args).unwrap_err();
+    assert!(error.to_string().contains("{region}"), "{}", error);
+
+    let args = args_for(&project, &["--base-url-from-env", "OPENAPI_TEST_UNSET_URL"]);
+    assert!(cli::run(args).is_err());
+}
+
+#[test]
+fn test_info_from_cargo_toml() {
+    let project = create_test_project(vec![
+        ("src/main.rs", COMPLETE_PROJECT),
+        ("Cargo.toml", "[package]\nname = \"user-service\"\nversion = \"2.3.0\"\n"),
+    ]);
+    let output = project.path().join("openapi.yaml");
+
+    cli::run(args_for(&project, &[])).expect("Generation failed");
+    let document = load_document(&output).unwrap();
+    assert_eq!(document.info.title, "user-service");
+    assert_eq!(document.info.version, "2.3.0");
+
+    let args = args_for(
+        &project,
+        &["--title", "Users", "--api-version", "3.0.0-beta", "--description", "User accounts"],
+    );
+    cli::run(args).expect("Generation failed");
+    let document = load_document(&output).unwrap();
+    assert_eq!(document.info.title, "Users");
+    assert_eq!(document.info.version, "3.0.0-beta");
+    assert_eq!(document.info.description.as_deref(), Some("User accounts"));
+
+    let project_path = project.path().to_string_lossy().to_string();
+    let blank_version = ["openapi-from-source", &project_path, "--api-version", " "];
+    assert!(CliArgs::try_parse_from(blank_version).is_err());
+}
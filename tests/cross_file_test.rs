@@ -84,3 +84,48 @@ fn test_cross_file_function_resolution() {
     
     println!("\n✅ Cross-file function resolution works correctly!");
 }
+
+#[test]
+fn test_module_qualified_handlers_across_files() {
+    // `mod handlers;` with `handlers/users.rs` and `handlers/admin.rs` both defining `get_user`
+    let users_code = r#"
+        pub async fn get_user() -> Json<User> {
+            todo!()
+        }
+    "#;
+    let admin_code = r#"
+        pub async fn get_user() -> Json<AdminUser> {
+            todo!()
+        }
+    "#;
+    let main_code = r#"
+        mod handlers;
+
+        pub fn app() -> Router {
+            Router::new()
+                .route("/users/:id", get(handlers::users::get_user))
+                .route("/admin/users/:id", get(crate::handlers::admin::get_user))
+        }
+    "#;
+
+    let parse = |path: &str, code: &str| ParsedFile {
+        path: PathBuf::from(path),
+        syntax_tree: syn::parse_file(code).expect("Failed to parse test code"),
+    };
+    let parsed_files = vec![
+        parse("src/handlers/users.rs", users_code),
+        parse("src/handlers/admin.rs", admin_code),
+        parse("src/main.rs", main_code),
+    ];
+
+    let routes = AxumExtractor.extract_routes(&parsed_files);
+    assert_eq!(routes.len(), 2);
+
+    let users = routes.iter().find(|r| r.path == "/users/:id").unwrap();
+    assert_eq!(users.handler_path, "handlers::users::get_user");
+    assert_eq!(users.response_type.as_ref().unwrap().name, "User");
+
+    let admin = routes.iter().find(|r| r.path == "/admin/users/:id").unwrap();
+    assert_eq!(admin.handler_path, "handlers::admin::get_user");
+    assert_eq!(admin.response_type.as_ref().unwrap().name, "AdminUser");
+}
@@ -1,11 +1,13 @@
+use clap::Parser;
 use openapi_from_source::{
+    cli::{self, CliArgs},
     detector::FrameworkDetector,
     extractor::{actix::ActixExtractor, axum::AxumExtractor, RouteExtractor},
     openapi_builder::OpenApiBuilder,
     parser::AstParser,
     scanner::FileScanner,
     schema_generator::SchemaGenerator,
-    serializer::{serialize_json, serialize_yaml},
+    serializer::{load_document, serialize_json, serialize_yaml},
     type_resolver::TypeResolver,
 };
 use tempfile::TempDir;
@@ -474,3 +476,219 @@ fn test_response_type_extraction() {
         }
     }
 }
+
+#[test]
+fn test_map_request_body() {
+    let axum_code = r#"
+use axum::{routing::post, Json, Router};
+use std::collections::HashMap;
+
+pub async fn set_scores(Json(scores): Json<HashMap<String, Vec<i32>>>) {}
+
+fn app() -> Router {
+    Router::new().route("/scores", post(set_scores))
+}
+"#;
+    let temp_dir = create_test_project(vec![("src/main.rs", axum_code)]);
+
+    let scanner = FileScanner::new(temp_dir.path().to_path_buf());
+    let scan_result = scanner.scan().expect("Failed to scan");
+    let parse_results = AstParser::parse_files(&scan_result.rust_files);
+    let parsed_files: Vec<_> = parse_results.into_iter().filter_map(Result::ok).collect();
+
+    let routes = AxumExtractor.extract_routes(&parsed_files);
+    let type_resolver = TypeResolver::new(parsed_files);
+    let mut schema_gen = SchemaGenerator::new(type_resolver);
+    let mut builder = OpenApiBuilder::new();
+    for route in &routes {
+        builder.add_route(route, &mut schema_gen);
+    }
+    let document = builder.build(schema_gen);
+
+    let operation = document.paths["/scores"].post.as_ref().expect("POST /scores");
+    let body = operation.request_body.as_ref().expect("request body");
+    let schema = &body.content["application/json"].schema;
+    assert_eq!(schema.schema_type.as_deref(), Some("object"));
+    assert!(schema.reference.is_none(), "map bodies are not component schemas");
+    let values = schema.additional_properties.as_ref().expect("additionalProperties");
+    assert_eq!(values.schema_type.as_deref(), Some("array"));
+    let items = values.items.as_ref().unwrap();
+    assert_eq!(items.schema_type.as_deref(), Some("integer"));
+    assert_eq!(items.format.as_deref(), Some("int32"));
+}
+
+#[test]
+fn test_paginated_generic_response() {
+    let axum_code = r#"
+use axum::{routing::get, Json, Router};
+
+pub struct User {
+    pub id: u64,
+    pub name: String,
+}
+
+/// One page of a list
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub page: u32,
+}
+
+pub async fn list_users() -> Json<Page<User>> {
+    todo!()
+}
+
+fn app() -> Router {
+    Router::new().route("/users", get(list_users))
+}
+"#;
+    let temp_dir = create_test_project(vec![("src/main.rs", axum_code)]);
+
+    let scanner = FileScanner::new(temp_dir.path().to_path_buf());
+    let scan_result = scanner.scan().expect("Failed to scan");
+    let parse_results = AstParser::parse_files(&scan_result.rust_files);
+    let parsed_files: Vec<_> = parse_results.into_iter().filter_map(Result::ok).collect();
+
+    let routes = AxumExtractor.extract_routes(&parsed_files);
+    let type_resolver = TypeResolver::new(parsed_files);
+    let mut schema_gen = SchemaGenerator::new(type_resolver);
+    let mut builder = OpenApiBuilder::new();
+    for route in &routes {
+        builder.add_route(route, &mut schema_gen);
+    }
+    let document = builder.build(schema_gen);
+
+    let operation = document.paths["/users"].get.as_ref().expect("GET /users");
+    let content = operation.responses["200"].content.as_ref().expect("response content");
+    assert_eq!(
+        content["application/json"].schema.reference.as_deref(),
+        Some("#/components/schemas/Page_User")
+    );
+
+    let schemas = document.components.unwrap().schemas.unwrap();
+    assert!(!schemas.contains_key("Page"), "generic definitions have no schema of their own");
+    let page = &schemas["Page_User"];
+    assert_eq!(page.title.as_deref(), Some("Page<User>"));
+    let properties = page.properties.as_ref().unwrap();
+    let items = &properties["items"];
+    assert_eq!(items.property_type.as_deref(), Some("array"));
+    assert_eq!(
+        items.items.as_ref().unwrap().reference.as_deref(),
+        Some("#/components/schemas/User")
+    );
+    assert_eq!(properties["total"].format.as_deref(), Some("int64"));
+    assert_eq!(properties["page"].minimum, Some(0));
+    let mut required = page.required.clone().unwrap();
+    required.sort();
+    assert_eq!(required, vec!["items", "page", "total"]);
+    assert!(schemas.contains_key("User"));
+}
+
+#[test]
+fn test_axum_app_defined_in_main() {
+    let axum_code = include_str!("fixtures/axum_main_app.rs");
+    let temp_dir = create_test_project(vec![("src/main.rs", axum_code)]);
+
+    let scanner = FileScanner::new(temp_dir.path().to_path_buf());
+    let scan_result = scanner.scan().expect("Failed to scan");
+    let parse_results = AstParser::parse_files(&scan_result.rust_files);
+    let parsed_files: Vec<_> = parse_results.into_iter().filter_map(Result::ok).collect();
+
+    let routes = AxumExtractor.extract_routes(&parsed_files);
+    let mut found: Vec<String> = routes
+        .iter()
+        .map(|r| format!("{:?} {} {}", r.method, r.path, r.handler_name))
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        vec![
+            "Get / root",
+            "Get /users list_users",
+            "Get /users/:id get_user",
+            "Post /users create_user",
+        ]
+    );
+    assert!(routes.iter().all(|r| r.handler_resolved), "every handler is a free function");
+
+    let type_resolver = TypeResolver::new(parsed_files);
+    let mut schema_gen = SchemaGenerator::new(type_resolver);
+    let mut builder = OpenApiBuilder::new();
+    for route in &routes {
+        builder.add_route(route, &mut schema_gen);
+    }
+    let document = builder.build(schema_gen);
+
+    let root = document.paths["/"].get.as_ref().expect("GET /");
+    let content = root.responses["200"].content.as_ref().expect("root response content");
+    let media = content.values().next().unwrap();
+    assert_eq!(media.schema.schema_type.as_deref(), Some("string"));
+
+    let users = &document.paths["/users"];
+    let list = users.get.as_ref().expect("GET /users");
+    let names: Vec<&str> = list
+        .parameters
+        .iter()
+        .flatten()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["page", "per_page"]);
+    let list_content = list.responses["200"].content.as_ref().expect("list response content");
+    assert_eq!(
+        list_content["application/json"].schema.items.as_ref().unwrap().reference.as_deref(),
+        Some("#/components/schemas/User")
+    );
+
+    let create = users.post.as_ref().expect("POST /users");
+    let body = create.request_body.as_ref().expect("request body");
+    assert_eq!(
+        body.content["application/json"].schema.reference.as_deref(),
+        Some("#/components/schemas/CreateUser")
+    );
+    let created = create.responses["201"].content.as_ref().expect("201 response content");
+    assert_eq!(
+        created["application/json"].schema.reference.as_deref(),
+        Some("#/components/schemas/User")
+    );
+
+    let get_user = document.paths["/users/{id}"].get.as_ref().expect("GET /users/{id}");
+    let id = &get_user.parameters.as_ref().unwrap()[0];
+    assert_eq!((id.name.as_str(), id.location.as_str()), ("id", "path"));
+    assert_eq!(id.schema.format.as_deref(), Some("int64"));
+
+    let schemas = document.components.unwrap().schemas.unwrap();
+    let mut schema_names: Vec<&String> = schemas.keys().collect();
+    schema_names.sort();
+    assert_eq!(schema_names, vec!["CreateUser", "User"]);
+
+    // The CLI documents the same app
+    let output = temp_dir.path().join("openapi.yaml");
+    let args = CliArgs::parse_from([
+        "openapi-from-source",
+        temp_dir.path().to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    cli::run(args).expect("Generation failed");
+    let written = load_document(&output).expect("Failed to read the generated document");
+    let mut operations: Vec<String> = written
+        .paths
+        .iter()
+        .flat_map(|(path, item)| {
+            item.operations().into_iter().map(move |(method, _)| format!("{} {}", method, path))
+        })
+        .collect();
+    operations.sort();
+    assert_eq!(
+        operations,
+        vec!["get /", "get /users", "get /users/{id}", "post /users"]
+    );
+    let created = &written.paths["/users"].post.as_ref().unwrap().responses["201"];
+    let created = &created.content.as_ref().expect("201 response content")["application/json"];
+    assert_eq!(
+        created.schema.reference.as_deref(),
+        Some("#/components/schemas/User")
+    );
+    let schemas = written.components.unwrap().schemas.unwrap();
+    assert!(schemas.contains_key("CreateUser") && schemas.contains_key("User"));
+}